@@ -1,9 +1,7 @@
-use chrono::{DateTime, Utc};
 use ed_parse_log_files::{
-    community_goal::{CommunityGoal, EDLogCommunityGoalJoin, EDLogCommunityGoalReward},
+    community_goal::CommunityGoals,
     log_line::{EDLogEvent, EDLogLine},
 };
-use itertools::Itertools;
 use numfmt::{Formatter, Precision, Scales};
 use prettytable::{Table, row};
 use rayon::iter::{IntoParallelRefIterator, ParallelBridge, ParallelIterator};
@@ -114,6 +112,7 @@ fn filter_loglines(db: Mutex<Vec<EDLogLine>>) -> Result<Vec<EDLogLine>, MyError>
                 EDLogEvent::CommunityGoal(_)
                     | EDLogEvent::CommunityGoalJoin(_)
                     | EDLogEvent::CommunityGoalReward(_)
+                    | EDLogEvent::CommunityGoalDiscard(_)
             )
         })
         .cloned()
@@ -124,57 +123,6 @@ fn filter_loglines(db: Mutex<Vec<EDLogLine>>) -> Result<Vec<EDLogLine>, MyError>
     Ok(lines)
 }
 
-#[derive(Debug)]
-struct GoalData {
-    cgid: u64,
-    goal: Option<CommunityGoal>,
-    join: Option<EDLogCommunityGoalJoin>,
-    reward: Option<EDLogCommunityGoalReward>,
-    goal_timestamp: Option<DateTime<Utc>>,
-    join_timestamp: Option<DateTime<Utc>>,
-    reward_timestamp: Option<DateTime<Utc>>,
-}
-
-fn find_goal(lines: &[EDLogLine], id: u64) -> GoalData {
-    let mut data = GoalData {
-        cgid: id,
-        goal: None,
-        join: None,
-        reward: None,
-        goal_timestamp: None,
-        join_timestamp: None,
-        reward_timestamp: None,
-    };
-
-    for line in lines.iter().rev() {
-        match line.event() {
-            EDLogEvent::CommunityGoal(g) => {
-                for g in g.current_goals.iter() {
-                    if data.goal.is_none() && g.cgid == id {
-                        data.goal = Some(g.clone());
-                        data.goal_timestamp = Some(*line.timestamp());
-                    }
-                }
-            }
-            EDLogEvent::CommunityGoalJoin(g) => {
-                if data.join.is_none() && g.cgid == id {
-                    data.join = Some(g.clone());
-                    data.join_timestamp = Some(*line.timestamp());
-                }
-            }
-            EDLogEvent::CommunityGoalReward(g) => {
-                if data.reward.is_none() && g.cgid == id {
-                    data.reward = Some(g.clone());
-                    data.reward_timestamp = Some(*line.timestamp());
-                }
-            }
-            _ => continue,
-        }
-    }
-
-    data
-}
-
 fn line_break(line: &str, width: usize) -> String {
     let mut result = String::new();
     let mut len = 0;
@@ -206,24 +154,12 @@ pub fn main() {
     println!("Filtering and sorting relevant lines");
     let items = filter_loglines(db).unwrap();
 
-    let goals = items
-        .iter()
-        .flat_map(|line| match line.event() {
-            EDLogEvent::CommunityGoal(g) => {
-                g.current_goals.iter().map(|g| g.cgid).collect::<Vec<_>>()
-            }
-            EDLogEvent::CommunityGoalJoin(g) => vec![g.cgid],
-            EDLogEvent::CommunityGoalReward(g) => vec![g.cgid],
-            _ => vec![],
-        })
-        .unique()
-        .map(|id| find_goal(&items, id))
-        .collect::<Vec<_>>();
+    let goals = CommunityGoals::new(&items);
 
     println!(
         "Found {} lines about Community Goals for {} unique goals",
         items.len(),
-        goals.len()
+        goals.iter().count()
     );
 
     let mut dec_formatter = Formatter::new()
@@ -234,72 +170,63 @@ pub fn main() {
 
     let mut table = Table::new();
     table.add_row(row!["ID", "Complete", "Title", "When", "Details", "Reward"]);
-    for goal in goals {
+    for history in goals.iter() {
+        let goal = history.latest();
+        let projection = history.projection();
         let when = format!(
             "Joined: {}\nStatus: {}\nExpire: {}\nReward: {}",
-            goal.join_timestamp
+            history
+                .joined
                 .map(|d| d.date_naive().to_string())
                 .unwrap_or_else(|| "n/a".to_string()),
-            goal.goal_timestamp
-                .map(|d| d.date_naive().to_string())
+            history
+                .snapshots
+                .last()
+                .map(|(d, _)| d.date_naive().to_string())
                 .unwrap_or_else(|| "n/a".to_string()),
-            goal.goal
-                .as_ref()
-                .map(|g| g.expiry.date_naive().to_string())
+            goal.map(|g| g.expiry.date_naive().to_string())
                 .unwrap_or_else(|| "n/a".to_string()),
-            goal.reward_timestamp
-                .map(|d| d.date_naive().to_string())
+            history
+                .reward
+                .map(|(d, _)| d.date_naive().to_string())
                 .unwrap_or_else(|| "n/a".to_string()),
         );
 
         let details = format!(
-            "Total: {}\nNumber of contributors: {}\nTop Tier: {}\nTier reached: {}",
-            goal.goal
-                .as_ref()
-                .map(|g| dec_formatter.fmt2(g.current_total).to_string())
+            "Total: {}\nNumber of contributors: {}\nTop Tier: {}\nTier reached: {}\nProjected tier: {}",
+            goal.map(|g| dec_formatter.fmt2(g.current_total).to_string())
                 .unwrap_or_else(|| "n/a".to_string()),
-            goal.goal
-                .as_ref()
-                .map(|g| dec_formatter.fmt2(g.num_contributors).to_string())
+            goal.map(|g| dec_formatter.fmt2(g.num_contributors).to_string())
                 .unwrap_or_else(|| "n/a".to_string()),
-            goal.goal
-                .as_ref()
-                .map(|g| g.top_tier.name.as_str())
-                .unwrap_or("n/a"),
-            goal.goal
-                .as_ref()
-                .and_then(|g| g.tier_reached.map(|s| s.as_str()))
+            goal.map(|g| g.top_tier.name.as_str()).unwrap_or("n/a"),
+            goal.and_then(|g| g.tier_reached.map(|s| s.as_str()))
                 .unwrap_or("n/a"),
+            projection
+                .map(|p| format!("Tier {}", p.tier))
+                .unwrap_or_else(|| "n/a".to_string()),
         );
         let reward = format!(
-            "Player contribution: {}\nYou're in top {}%\nBonus:{}\nReward:{}",
-            goal.goal
-                .as_ref()
-                .map(|g| dec_formatter.fmt2(g.player_contribution).to_string())
+            "Player contribution: {}\nYou're in top {}%\nProjected: top {}%\nBonus:{}\nReward:{}",
+            goal.map(|g| dec_formatter.fmt2(g.player_contribution).to_string())
+                .unwrap_or_else(|| "n/a".to_string()),
+            goal.map(|g| g.player_percentile_band.to_string())
                 .unwrap_or_else(|| "n/a".to_string()),
-            goal.goal
-                .as_ref()
-                .map(|g| g.player_percentile_band.to_string())
+            projection
+                .map(|p| p.player_percentile_band.to_string())
                 .unwrap_or_else(|| "n/a".to_string()),
-            goal.goal
-                .as_ref()
-                .and_then(|g| g.bonus.map(|b| dec_formatter.fmt2(b.0).to_string()))
+            goal.and_then(|g| g.bonus.map(|b| dec_formatter.fmt2(b.0).to_string()))
                 .unwrap_or_else(|| "n/a".to_string()),
-            goal.reward
-                .as_ref()
-                .map(|g| dec_formatter.fmt2(g.reward.0).to_string())
+            history
+                .reward
+                .map(|(_, r)| dec_formatter.fmt2(r.0).to_string())
                 .unwrap_or_else(|| "n/a".to_string()),
         );
 
         table.add_row(row![
-            goal.cgid,
-            goal.goal
-                .as_ref()
-                .map(|g| format!("{:?}", g.is_complete))
+            history.cgid,
+            goal.map(|g| format!("{:?}", g.is_complete))
                 .unwrap_or_else(|| "n/a".to_string()),
-            goal.goal
-                .as_ref()
-                .map(|g| line_break(g.title.as_str(), 30))
+            goal.map(|g| line_break(g.title.as_str(), 30))
                 .unwrap_or_else(|| "n/a".to_string()),
             when,
             details,
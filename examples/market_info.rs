@@ -303,7 +303,7 @@ fn show_market_data(market_data: &MarketData, market_id: u64) {
                     let (mut sum_remaining, mut total_value) = (0,0);
                     for r in resources.iter() {
                         resources_required.add_row(row![
-                            r.name_localised.as_ref().unwrap_or(&r.name),
+                            r.name_localised.map(|n| n.to_string()).unwrap_or_else(|| r.name.to_string()),
                             r -> dec_formatter.fmt2(r.required_amount - r.provided_amount),
                             r -> dec_formatter.fmt2(r.payment.0),
                             r -> dec_formatter.fmt2(r.payment.0 * (r.required_amount - r.provided_amount) as i64)
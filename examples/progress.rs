@@ -1,13 +1,12 @@
 use chrono::{DateTime, Duration, Utc};
 use ed_parse_log_files::{
-    commander::{
-        COMBAT_RANK, CQCRank, CombatRank, EDLogRank, EDLogReputation, EMPIRE_RANK,
-        EXOBIOLOGIST_RANK, EXPLORE_RANK, EmpireRank, ExobiologistRank, ExploreRank,
-        FEDERATION_RANK, FederationRank, SOLDIER_RANK, SoldierRank, TRADE_RANK, TradeRank,
-    },
+    commander::EDLogReputation,
     common_types::{Credits, Merits},
-    log_line::{EDLogEvent, EDLogLine},
+    engineer_tracker::EngineerTracker,
+    log_line::{EDLogEvent, EDLogEventDiscriminants, EDLogLine},
     powerplay::{EDLogPowerplay, power_play_rank_range},
+    powerplay_tracker::{POWERPLAY_EVENTS, PowerplayTracker},
+    rank::{RankHistory, RankSample, Ranks},
 };
 use prettytable::{Table, cell, row};
 use rayon::prelude::*;
@@ -354,154 +353,57 @@ impl Display for PowerPlay {
     }
 }
 
-#[derive(Debug)]
-pub struct Rank {
-    pub combat: CombatRank,
-    pub trade: TradeRank,
-    pub explore: ExploreRank,
-    pub soldier: SoldierRank,
-    pub exobiologist: ExobiologistRank,
-    pub empire: EmpireRank,
-    pub federation: FederationRank,
-    pub cqc: CQCRank,
-}
-
-impl From<&EDLogRank> for Rank {
-    fn from(value: &EDLogRank) -> Self {
-        Self {
-            combat: value.combat,
-            trade: value.trade,
-            explore: value.explore,
-            soldier: value.soldier.unwrap_or(0),
-            exobiologist: value.exobiologist.unwrap_or(0),
-            empire: value.empire,
-            federation: value.federation,
-            cqc: value.cqc,
-        }
-    }
-}
-
+/// ranks and rank progress at the start and the end of a period
 #[derive(Debug)]
 pub struct RankProgress {
-    start: Rank,
-    end: Rank,
+    ranks: Ranks,
 }
 
 impl RankProgress {
     pub fn new(data: &[EDLogLine]) -> Option<RankProgress> {
-        let mut reps = data
-            .iter()
-            .filter(|line| matches!(line.event(), EDLogEvent::Rank(_)));
-        let first = reps.next();
-        let last = reps.next_back();
-
-        first.and_then(|first| {
-            let last = last.unwrap_or(first);
-            match (first.event(), last.event()) {
-                (EDLogEvent::Rank(first), EDLogEvent::Rank(last)) => Some(RankProgress {
-                    start: first.into(),
-                    end: last.into(),
-                }),
-                _ => None,
-            }
-        })
-    }
-}
-
-impl Display for RankProgress {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let combat = progression_string_map(self.start.combat, self.end.combat, &COMBAT_RANK);
-        let trade = progression_string_map(self.start.trade, self.end.trade, &TRADE_RANK);
-        let explore = progression_string_map(self.start.explore, self.end.explore, &EXPLORE_RANK);
-        let soldier = progression_string_map(self.start.soldier, self.end.soldier, &SOLDIER_RANK);
-        let exobiology = progression_string_map(
-            self.start.exobiologist,
-            self.end.exobiologist,
-            &EXOBIOLOGIST_RANK,
-        );
-        let empire = progression_string_map(self.start.empire, self.end.empire, &EMPIRE_RANK);
-        let federation =
-            progression_string_map(self.start.federation, self.end.federation, &FEDERATION_RANK);
-        let cqc = progression_string(&self.start.cqc, &self.end.cqc);
-        write!(
-            f,
-            "Combat: {combat}\nTrade: {trade}\nExplore: {explore}\nSoldier: {soldier}\nExobiology: {exobiology}\nEmpire: {empire}\nFederation: {federation}\nCQC: {cqc}"
-        )
-    }
-}
-
-#[derive(Debug)]
-pub struct RankProgression {
-    combat: CombatRank,
-    trade: TradeRank,
-    explore: ExploreRank,
-    soldier: SoldierRank,
-    exobiologist: ExobiologistRank,
-    empire: EmpireRank,
-    federation: FederationRank,
-    cqc: CQCRank,
-}
-
-impl From<&EDLogRank> for RankProgression {
-    fn from(value: &EDLogRank) -> Self {
-        Self {
-            combat: value.combat,
-            trade: value.trade,
-            explore: value.explore,
-            soldier: value.soldier.unwrap_or(0),
-            exobiologist: value.exobiologist.unwrap_or(0),
-            empire: value.empire,
-            federation: value.federation,
-            cqc: value.cqc,
+        let ranks = Ranks::new(data);
+        if ranks.iter().all(|r| r.progress.is_empty()) {
+            return None;
         }
+        Some(RankProgress { ranks })
     }
-}
 
-#[derive(Debug)]
-pub struct RankProgressionChange {
-    start: RankProgression,
-    end: RankProgression,
-}
-
-impl RankProgressionChange {
-    pub fn new(data: &[EDLogLine]) -> Option<RankProgressionChange> {
-        let mut reps = data
+    // first and last progress sample of each rank
+    fn samples(&self) -> impl Iterator<Item = (&RankHistory, &RankSample, &RankSample)> {
+        self.ranks
             .iter()
-            .filter(|line| matches!(line.event(), EDLogEvent::Progress(_)));
-        let first = reps.next();
-        let last = reps.next_back();
-
-        first.and_then(|first| {
-            let last = last.unwrap_or(first);
-            match (first.event(), last.event()) {
-                (EDLogEvent::Progress(first), EDLogEvent::Progress(last)) => {
-                    Some(RankProgressionChange {
-                        start: first.into(),
-                        end: last.into(),
-                    })
-                }
-                _ => None,
-            }
-        })
+            .filter_map(|r| Some((r, r.progress.first()?, r.progress.last()?)))
     }
-}
 
-impl Display for RankProgressionChange {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let combat = progression_string_perc(&self.start.combat, &self.end.combat);
-        let trade = progression_string_perc(&self.start.trade, &self.end.trade);
-        let explore = progression_string_perc(&self.start.explore, &self.end.explore);
-        let soldier = progression_string_perc(&self.start.soldier, &self.end.soldier);
-        let exobiologist =
-            progression_string_perc(&self.start.exobiologist, &self.end.exobiologist);
-        let empire = progression_string_perc(&self.start.empire, &self.end.empire);
-        let federation = progression_string_perc(&self.start.federation, &self.end.federation);
-        let cqc = progression_string_perc(&self.start.cqc, &self.end.cqc);
+    pub fn ranks(&self) -> String {
+        self.samples()
+            .map(|(r, first, last)| {
+                let names = r.kind.names();
+                format!(
+                    "{}: {}",
+                    r.kind,
+                    progression_string_map(first.level, last.level, names)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 
-        write!(
-            f,
-            "Combat: {combat}\nTrade: {trade}\nExplore: {explore}\nSoldier: {soldier}\nExobiology: {exobiologist}\nEmpire: {empire}\nFederation: {federation}\nCQC: {cqc}",
-        )
+    pub fn progress(&self) -> String {
+        self.samples()
+            .map(|(r, first, last)| {
+                let next = r
+                    .time_to_next()
+                    .map(|d| format!(" (next in {})", format_duration(&d)))
+                    .unwrap_or_default();
+                format!(
+                    "{}: {}{next}",
+                    r.kind,
+                    progression_string_perc(&first.percent, &last.percent)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 }
 
@@ -585,7 +487,7 @@ pub fn read_logs() -> Result<Mutex<Vec<EDLogLine>>, MyError> {
     Ok(db)
 }
 
-fn filter_progress(db: Mutex<Vec<EDLogLine>>) -> Result<Vec<EDLogLine>, MyError> {
+fn filter_progress(db: &Mutex<Vec<EDLogLine>>) -> Result<Vec<EDLogLine>, MyError> {
     let lines = db.lock().unwrap();
     let progress = lines
         .par_iter()
@@ -594,12 +496,16 @@ fn filter_progress(db: Mutex<Vec<EDLogLine>>) -> Result<Vec<EDLogLine>, MyError>
                 line.event(),
                 EDLogEvent::Progress(_)
                     | EDLogEvent::Rank(_)
+                    | EDLogEvent::Promotion(_)
                     | EDLogEvent::Reputation(_)
                     | EDLogEvent::Powerplay(_)
                     | EDLogEvent::Statistics(_)
                     | EDLogEvent::CarrierFinance(_)
                     | EDLogEvent::CarrierStats(_)
                     | EDLogEvent::LoadGame(_)
+                    | EDLogEvent::EngineerProgress(_)
+                    | EDLogEvent::EngineerContribution(_)
+                    | EDLogEvent::EngineerCraft(_)
             )
         })
         .cloned()
@@ -608,6 +514,17 @@ fn filter_progress(db: Mutex<Vec<EDLogLine>>) -> Result<Vec<EDLogLine>, MyError>
     Ok(progress)
 }
 
+fn filter_powerplay(db: &Mutex<Vec<EDLogLine>>) -> Vec<EDLogLine> {
+    let lines = db.lock().unwrap();
+    let mut powerplay: Vec<EDLogLine> = lines
+        .par_iter()
+        .filter(|line| POWERPLAY_EVENTS.contains(&EDLogEventDiscriminants::from(line.event())))
+        .cloned()
+        .collect();
+    powerplay.par_sort_by(|a, b| a.timestamp().partial_cmp(b.timestamp()).unwrap());
+    powerplay
+}
+
 pub fn get_data_in_period(
     start: DateTime<Utc>,
     end: DateTime<Utc>,
@@ -663,11 +580,14 @@ fn show_progress(mut progress_items: Vec<EDLogLine>) {
         let credits = CreditsProgress::new(data)
             .map(|p| p.to_string())
             .unwrap_or_else(|| "n/a".into());
-        let progress = RankProgressionChange::new(data)
-            .map(|p| p.to_string())
+        let rank_progress = RankProgress::new(data);
+        let progress = rank_progress
+            .as_ref()
+            .map(|p| p.progress())
             .unwrap_or_else(|| "n/a".into());
-        let rank = RankProgress::new(data)
-            .map(|p| p.to_string())
+        let rank = rank_progress
+            .as_ref()
+            .map(|p| p.ranks())
             .unwrap_or_else(|| "n/a".into());
         let power_play = PowerPlay::new(data)
             .map(|p| p.to_string())
@@ -687,13 +607,100 @@ fn show_progress(mut progress_items: Vec<EDLogLine>) {
         table.add_row(row);
     }
     table.printstd();
+
+    show_engineers(&progress_items);
+}
+
+fn show_engineers(progress_items: &[EDLogLine]) {
+    let tracker = EngineerTracker::new(progress_items);
+
+    let mut table = Table::new();
+    table.add_row(row![
+        "Engineer",
+        "Workshop",
+        "State",
+        "Rank",
+        "Unlock",
+        "Rolls",
+        "Rolls to rank 5"
+    ]);
+    for status in tracker.iter() {
+        let workshop = status.workshop();
+        table.add_row(row![
+            status.engineer,
+            format!("{}\n{}", workshop.base, workshop.star_system),
+            status
+                .progress
+                .map(|p| format!("{p:?}"))
+                .unwrap_or_else(|| "n/a".into()),
+            status
+                .rank
+                .map(|r| format!("{r} ({}%)", status.rank_progress.unwrap_or(0)))
+                .unwrap_or_else(|| "n/a".into()),
+            status
+                .unlock_progress()
+                .map(|p| format!("{:.0}%", p * 100.0))
+                .unwrap_or_else(|| "n/a".into()),
+            status.rolls,
+            status
+                .rolls_to_max_rank()
+                .map(|r| r.to_string())
+                .unwrap_or_else(|| "n/a".into()),
+        ]);
+    }
+    table.printstd();
+}
+
+fn show_powerplay_cycles(progress_items: &[EDLogLine]) {
+    let tracker = PowerplayTracker::new(progress_items);
+
+    let mut table = Table::new();
+    table.add_row(row![
+        "Cycle",
+        "Merits",
+        "Activities",
+        "Top system",
+        "Salary"
+    ]);
+    for cycle in tracker.cycles().iter().rev().take(8) {
+        let activities = cycle
+            .by_activity
+            .iter()
+            .map(|(activity, merits)| format!("{activity}: {merits}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let top_system = cycle
+            .by_system
+            .iter()
+            .max_by_key(|(_, merits)| **merits)
+            .map(|(system, merits)| format!("{system} ({merits})"))
+            .unwrap_or_else(|| "n/a".into());
+        table.add_row(row![
+            cycle.start.format("%Y-%m-%d"),
+            cycle.merits,
+            activities,
+            top_system,
+            cycle.salary.to_human_readable_string(),
+        ]);
+    }
+    table.printstd();
+
+    let now = Utc::now();
+    if let Some(time) = tracker.time_to_next_rank(now - Duration::days(28), now) {
+        println!(
+            "Next power play rank in {} at the rate of the last 28 days",
+            format_duration(&time)
+        );
+    }
 }
 
 pub fn main() {
     println!("Reading log files");
     let db = read_logs().unwrap();
     println!("Filtering progress lines");
-    let progress_items = filter_progress(db).unwrap();
+    let progress_items = filter_progress(&db).unwrap();
+    let powerplay_items = filter_powerplay(&db);
     println!("Show progress");
     show_progress(progress_items);
+    show_powerplay_cycles(&powerplay_items);
 }
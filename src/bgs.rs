@@ -0,0 +1,432 @@
+use crate::{
+    EDString,
+    common_types::{Credits, Faction, FactionState},
+    log_line::{EDLogEvent, EDLogLine},
+    mission::VoucherType,
+};
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+use strum::Display;
+
+/// state of a faction in a system as seen on arrival
+#[derive(Clone, Debug)]
+pub struct FactionSnapshot {
+    pub timestamp: DateTime<Utc>,
+    pub influence: f64,
+    pub state: FactionState,
+    pub active_states: Vec<FactionState>,
+    pub pending_states: Vec<(FactionState, u64)>,
+    pub recovering_states: Vec<(FactionState, u64)>,
+    pub my_reputation: Option<f64>,
+}
+
+impl FactionSnapshot {
+    fn new(timestamp: DateTime<Utc>, faction: &Faction) -> FactionSnapshot {
+        FactionSnapshot {
+            timestamp,
+            influence: faction.influence,
+            state: faction.faction_state,
+            active_states: faction
+                .active_states
+                .iter()
+                .flatten()
+                .map(|s| s.state)
+                .collect(),
+            pending_states: faction
+                .pending_states
+                .iter()
+                .flatten()
+                .map(|s| (s.state, s.trend))
+                .collect(),
+            recovering_states: faction
+                .recovering_states
+                .iter()
+                .flatten()
+                .map(|s| (s.state, s.trend))
+                .collect(),
+            my_reputation: faction.my_reputation,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FactionHistory {
+    pub name: EDString,
+    pub system_address: u64,
+    /// oldest first
+    pub snapshots: Vec<FactionSnapshot>,
+}
+
+impl FactionHistory {
+    pub fn latest(&self) -> Option<&FactionSnapshot> {
+        self.snapshots.last()
+    }
+
+    pub fn influence_trend(&self) -> Vec<(DateTime<Utc>, f64)> {
+        self.snapshots
+            .iter()
+            .map(|s| (s.timestamp, s.influence))
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Display)]
+pub enum ContributionKind {
+    Mission,
+    Bounty,
+    #[strum(to_string = "Combat Bond")]
+    CombatBond,
+    Trade,
+    Exploration,
+    Exobiology,
+}
+
+#[derive(Clone, Debug)]
+pub struct BgsContribution {
+    pub timestamp: DateTime<Utc>,
+    pub faction: EDString,
+    pub system_address: Option<u64>,
+    pub kind: ContributionKind,
+    /// vouchers redeemed, trade profit or data sold, the mission reward for the mission giver
+    pub credits: Credits,
+    /// number of `+` in the mission influence effect
+    pub influence: Option<u64>,
+}
+
+/// Influence and states of factions per system, with our own contributions
+#[derive(Clone, Debug, Default)]
+pub struct BgsTracker {
+    factions: HashMap<(u64, EDString), FactionHistory>,
+    pub system_names: HashMap<u64, EDString>,
+    pub contributions: Vec<BgsContribution>,
+    current_system: Option<u64>,
+    station_faction: Option<EDString>,
+}
+
+impl BgsTracker {
+    /// build the tracker from log lines sorted by timestamp
+    pub fn new(lines: &[EDLogLine]) -> BgsTracker {
+        let mut tracker = BgsTracker::default();
+        for line in lines {
+            tracker.add_line(line);
+        }
+        tracker
+    }
+
+    fn add_factions(
+        &mut self,
+        timestamp: DateTime<Utc>,
+        system_address: u64,
+        factions: &[Faction],
+    ) {
+        for faction in factions {
+            self.factions
+                .entry((system_address, faction.name.clone()))
+                .or_insert_with(|| FactionHistory {
+                    name: faction.name.clone(),
+                    system_address,
+                    snapshots: Vec::new(),
+                })
+                .snapshots
+                .push(FactionSnapshot::new(timestamp, faction));
+        }
+    }
+
+    // contribution to the controlling faction of the station we are docked at
+    fn add_station_contribution(
+        &mut self,
+        timestamp: DateTime<Utc>,
+        kind: ContributionKind,
+        credits: Credits,
+    ) {
+        if let Some(faction) = &self.station_faction {
+            self.contributions.push(BgsContribution {
+                timestamp,
+                faction: faction.clone(),
+                system_address: self.current_system,
+                kind,
+                credits,
+                influence: None,
+            });
+        }
+    }
+
+    pub fn add_line(&mut self, line: &EDLogLine) {
+        let timestamp = *line.timestamp();
+        match line.event() {
+            EDLogEvent::FSDJump(jump) => {
+                self.current_system = Some(jump.system_address);
+                self.station_faction = None;
+                self.system_names
+                    .insert(jump.system_address, jump.star_system.clone());
+                if let Some(factions) = &jump.factions {
+                    self.add_factions(timestamp, jump.system_address, factions);
+                }
+            }
+            EDLogEvent::Location(location) => {
+                self.current_system = location.system_address;
+                // logging in while docked doesn't write a Docked event
+                self.station_faction = location
+                    .station_information
+                    .as_ref()
+                    .filter(|_| location.docked)
+                    .map(|info| info.station_faction.name.clone());
+                if let Some(address) = location.system_address {
+                    self.system_names
+                        .insert(address, location.star_system.clone());
+                    if let Some(factions) = &location.factions {
+                        self.add_factions(timestamp, address, factions);
+                    }
+                }
+            }
+            EDLogEvent::Docked(docked) => {
+                self.current_system = Some(docked.system_address);
+                self.station_faction = Some(docked.station_faction.name.clone());
+            }
+            EDLogEvent::Undocked(_) => self.station_faction = None,
+            EDLogEvent::MissionCompleted(mission) => {
+                // the reward is paid once, by the faction that gave the mission
+                let mut reward = mission.reward.filter(|r| r.0 > 0);
+                for effect in mission.faction_effects.iter().flatten() {
+                    for influence in &effect.influence {
+                        let credits = reward.take_if(|_| effect.faction == mission.faction);
+                        self.contributions.push(BgsContribution {
+                            timestamp,
+                            faction: effect.faction.clone(),
+                            system_address: Some(influence.system_address),
+                            kind: ContributionKind::Mission,
+                            credits: credits.unwrap_or_default(),
+                            influence: Some(
+                                influence.influence.as_str().matches('+').count() as u64
+                            ),
+                        });
+                    }
+                }
+                if let Some(credits) = reward {
+                    self.contributions.push(BgsContribution {
+                        timestamp,
+                        faction: mission.faction.clone(),
+                        system_address: self.current_system,
+                        kind: ContributionKind::Mission,
+                        credits,
+                        influence: None,
+                    });
+                }
+            }
+            EDLogEvent::RedeemVoucher(voucher) => {
+                let kind = match voucher.voucher_type {
+                    VoucherType::Bounty => ContributionKind::Bounty,
+                    VoucherType::CombatBond => ContributionKind::CombatBond,
+                    _ => return,
+                };
+                for faction in voucher.factions.iter().flatten() {
+                    // interstellar factors pay out vouchers without a faction
+                    if faction.faction.as_str().is_empty() {
+                        continue;
+                    }
+                    self.contributions.push(BgsContribution {
+                        timestamp,
+                        faction: faction.faction.clone(),
+                        system_address: self.current_system,
+                        kind,
+                        credits: faction.amount,
+                        influence: None,
+                    });
+                }
+                if let Some(faction) = &voucher.faction {
+                    self.contributions.push(BgsContribution {
+                        timestamp,
+                        faction: faction.clone(),
+                        system_address: self.current_system,
+                        kind,
+                        credits: voucher.amount,
+                        influence: None,
+                    });
+                }
+            }
+            EDLogEvent::MarketSell(sell) => {
+                let profit = sell.total_sale - sell.avg_price_paid * sell.count;
+                if profit.0 > 0 {
+                    self.add_station_contribution(timestamp, ContributionKind::Trade, profit);
+                }
+            }
+            EDLogEvent::SellExplorationData(sell) => {
+                let total = sell.total_earnings.unwrap_or(sell.base_value + sell.bonus);
+                self.add_station_contribution(timestamp, ContributionKind::Exploration, total);
+            }
+            EDLogEvent::MultiSellExplorationData(sell) => {
+                self.add_station_contribution(
+                    timestamp,
+                    ContributionKind::Exploration,
+                    sell.total_earnings,
+                );
+            }
+            EDLogEvent::SellOrganicData(sell) => {
+                let total = sell
+                    .bio_data
+                    .iter()
+                    .map(|data| data.value + data.bonus)
+                    .sum();
+                self.add_station_contribution(timestamp, ContributionKind::Exobiology, total);
+            }
+            _ => {}
+        }
+    }
+
+    pub fn faction(&self, system_address: u64, name: &str) -> Option<&FactionHistory> {
+        self.factions.get(&(system_address, EDString::from(name)))
+    }
+
+    /// factions last seen in the system, highest influence first
+    pub fn factions_in_system(&self, system_address: u64) -> Vec<&FactionHistory> {
+        let mut factions: Vec<&FactionHistory> = self
+            .factions
+            .values()
+            .filter(|f| f.system_address == system_address)
+            .collect();
+        let influence = |f: &FactionHistory| f.latest().map(|s| s.influence).unwrap_or(0.0);
+        factions.sort_by(|a, b| influence(b).total_cmp(&influence(a)));
+        factions
+    }
+
+    /// all systems a faction has been seen in
+    pub fn faction_presence(&self, name: &str) -> Vec<&FactionHistory> {
+        self.factions
+            .values()
+            .filter(|f| f.name.as_str() == name)
+            .collect()
+    }
+
+    pub fn contributions_for<'a>(
+        &'a self,
+        faction: &'a str,
+    ) -> impl Iterator<Item = &'a BgsContribution> {
+        self.contributions
+            .iter()
+            .filter(move |c| c.faction.as_str() == faction)
+    }
+
+    /// credits contributed to a faction per system and kind of activity
+    pub fn contribution_summary(
+        &self,
+        faction: &str,
+    ) -> HashMap<Option<u64>, BTreeMap<ContributionKind, Credits>> {
+        let mut result: HashMap<Option<u64>, BTreeMap<ContributionKind, Credits>> = HashMap::new();
+        for contribution in self.contributions_for(faction) {
+            *result
+                .entry(contribution.system_address)
+                .or_default()
+                .entry(contribution.kind)
+                .or_default() += contribution.credits;
+        }
+        result
+    }
+}
+
+#[test]
+fn test_bgs_tracker() {
+    let json = [
+        r#"{ "timestamp":"2025-12-15T09:00:00Z", "event":"FSDJump", "StarSystem":"Sothis", "SystemAddress":9463020987689, "StarPos":[-352.78125,10.50000,-346.34375], "SystemAllegiance":"Independent", "SystemEconomy":"$economy_Military;", "SystemEconomy_Localised":"Military", "SystemGovernment":"$government_Corporate;", "SystemGovernment_Localised":"Corporate", "SystemSecurity":"$SYSTEM_SECURITY_low;", "SystemSecurity_Localised":"Low Security", "Population":33893, "JumpDist":13.790, "FuelUsed":0.371038, "FuelLevel":5.628962,
+            "Factions":[ { "Name":"CdE Corporation", "FactionState":"None", "Government":"Corporate", "Influence":0.400000, "Allegiance":"Independent", "Happiness":"$Faction_HappinessBand2;", "Happiness_Localised":"Happy", "MyReputation":15.000000, "PendingStates":[ { "State":"Boom", "Trend":0 } ] },
+                         { "Name":"Sothis Crimson Bridge Corp.", "FactionState":"None", "Government":"Corporate", "Influence":0.600000, "Allegiance":"Independent", "Happiness":"$Faction_HappinessBand2;", "Happiness_Localised":"Happy", "MyReputation":0.000000 } ] }"#,
+        r#"{ "timestamp":"2025-12-15T09:36:49Z", "event":"MissionCompleted", "Faction":"CdE Corporation", "Name":"Mission_Sightseeing_name", "LocalisedName":"Christina Wood Seeks Sightseeing Adventure", "MissionID":12341234, "Commodity":"$ConsumerTechnology_Name;", "Commodity_Localised":"Consumer Technology", "Count":1, "DestinationSystem":"Sothis", "Reward":1818345,
+            "FactionEffects":[ {"Faction":"Sothis Crimson Bridge Corp.", "Effects":[], "Influence":[ { "SystemAddress":9463020987689, "Trend":"UpGood", "Influence":"+" } ], "ReputationTrend":"UpGood", "Reputation":"+"},
+                               {"Faction":"CdE Corporation", "Effects":[], "Influence":[ { "SystemAddress":9463020987689, "Trend":"UpGood", "Influence":"++" }, { "SystemAddress":10477373803, "Trend":"UpGood", "Influence":"++" } ], "ReputationTrend":"UpGood", "Reputation":"++"} ]}"#,
+        r#"{ "timestamp":"2025-12-15T09:40:00Z", "event":"RedeemVoucher", "Type":"bounty", "Amount":150000, "Factions":[ { "Faction":"CdE Corporation", "Amount":150000 } ] }"#,
+        r#"{ "timestamp":"2025-12-16T09:00:00Z", "event":"FSDJump", "StarSystem":"Sothis", "SystemAddress":9463020987689, "StarPos":[-352.78125,10.50000,-346.34375], "SystemAllegiance":"Independent", "SystemEconomy":"$economy_Military;", "SystemEconomy_Localised":"Military", "SystemGovernment":"$government_Corporate;", "SystemGovernment_Localised":"Corporate", "SystemSecurity":"$SYSTEM_SECURITY_low;", "SystemSecurity_Localised":"Low Security", "Population":33893, "JumpDist":13.790, "FuelUsed":0.371038, "FuelLevel":5.628962,
+            "Factions":[ { "Name":"CdE Corporation", "FactionState":"Boom", "Government":"Corporate", "Influence":0.650000, "Allegiance":"Independent", "Happiness":"$Faction_HappinessBand2;", "Happiness_Localised":"Happy", "MyReputation":25.000000, "ActiveStates":[ { "State":"Boom" } ] },
+                         { "Name":"Sothis Crimson Bridge Corp.", "FactionState":"None", "Government":"Corporate", "Influence":0.350000, "Allegiance":"Independent", "Happiness":"$Faction_HappinessBand2;", "Happiness_Localised":"Happy", "MyReputation":0.000000 } ] }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let bgs = BgsTracker::new(&lines);
+    let cde = bgs.faction(9463020987689, "CdE Corporation").unwrap();
+    assert_eq!(
+        vec![0.4, 0.65],
+        cde.influence_trend()
+            .into_iter()
+            .map(|(_, i)| i)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![FactionState::Boom],
+        cde.latest().unwrap().active_states
+    );
+    assert_eq!(
+        "CdE Corporation",
+        bgs.factions_in_system(9463020987689)[0].name.as_str()
+    );
+
+    let summary = bgs.contribution_summary("CdE Corporation");
+    let sothis = &summary[&Some(9463020987689)];
+    assert_eq!(Credits(1818345), sothis[&ContributionKind::Mission]);
+    assert_eq!(Credits(150000), sothis[&ContributionKind::Bounty]);
+    // influence in another system, but the reward is only counted once
+    assert_eq!(
+        Credits(0),
+        summary[&Some(10477373803)][&ContributionKind::Mission]
+    );
+    let target = bgs.contribution_summary("Sothis Crimson Bridge Corp.");
+    assert_eq!(
+        Credits(0),
+        target[&Some(9463020987689)][&ContributionKind::Mission]
+    );
+    assert_eq!(
+        Some(2),
+        bgs.contributions_for("CdE Corporation")
+            .next()
+            .unwrap()
+            .influence
+    );
+}
+
+#[test]
+fn test_bgs_docked_at_login() {
+    let json = [
+        r#"{ "timestamp":"2025-04-01T18:00:00Z", "event":"Location", "DistFromStarLS":12.5, "Docked":true, "StationName":"Exogene Sciences", "StationType":"AsteroidBase", "MarketID":129038712, "StationFaction":{ "Name":"October Consortium" }, "StationGovernment":"$government_Corporate;", "StationGovernment_Localised":"Corporate", "StationServices":[ "dock", "autodock", "commodities", "refuel" ], "StationEconomy":"$economy_Service;", "StationEconomy_Localised":"Service", "StationEconomies":[ { "Name":"$economy_Service;", "Name_Localised":"Service", "Proportion":1.000000 } ], "Taxi":false, "Multicrew":false, "StarSystem":"Yukoukha", "SystemAddress":22945325796176, "StarPos":[-134.56250,-34.03125,-4.75000], "SystemAllegiance":"", "SystemEconomy":"$economy_None;", "SystemEconomy_Localised":"None", "SystemGovernment":"$government_None;", "SystemGovernment_Localised":"None", "SystemSecurity":"$GAlAXY_MAP_INFO_state_anarchy;", "SystemSecurity_Localised":"Anarchy", "Population":0, "Body":"Yukoukha A 1", "BodyID":3, "BodyType":"Planet" }"#,
+        r#"{ "timestamp":"2025-04-01T18:05:00Z", "event":"MarketSell", "MarketID":129038712, "Type":"gold", "Count":10, "SellPrice":50000, "TotalSale":500000, "AvgPricePaid":45000 }"#,
+        r#"{ "timestamp":"2025-04-01T18:06:00Z", "event":"SellOrganicData", "MarketID":129038712, "BioData":[ { "Genus":"$Codex_Ent_Bacterial_Genus_Name;", "Genus_Localised":"Bacterium", "Species":"$Codex_Ent_Bacterial_04_Name;", "Species_Localised":"Bacterium Acies", "Variant":"$Codex_Ent_Bacterial_04_Tin_Name;", "Variant_Localised":"Bacterium Acies - Teal", "Value":1000000, "Bonus":0 } ] }"#,
+        r#"{ "timestamp":"2025-04-01T18:10:00Z", "event":"Undocked", "StationName":"Exogene Sciences", "StationType":"AsteroidBase", "MarketID":129038712, "Taxi":false, "Multicrew":false }"#,
+        r#"{ "timestamp":"2025-04-01T18:20:00Z", "event":"MarketSell", "MarketID":129038712, "Type":"gold", "Count":10, "SellPrice":50000, "TotalSale":500000, "AvgPricePaid":45000 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let bgs = BgsTracker::new(&lines);
+    let summary = bgs.contribution_summary("October Consortium");
+    let yukoukha = &summary[&Some(22945325796176)];
+    assert_eq!(Credits(50000), yukoukha[&ContributionKind::Trade]);
+    assert_eq!(Credits(1000000), yukoukha[&ContributionKind::Exobiology]);
+    assert_eq!(2, bgs.contributions.len());
+}
+
+#[test]
+fn test_bgs_tracker_empty() {
+    let bgs = BgsTracker::new(&[]);
+    assert!(bgs.faction(9463020987689, "CdE Corporation").is_none());
+    assert!(bgs.factions_in_system(9463020987689).is_empty());
+    assert!(bgs.faction_presence("CdE Corporation").is_empty());
+    assert!(bgs.contribution_summary("CdE Corporation").is_empty());
+}
+
+#[test]
+fn test_bgs_contributions_without_station() {
+    let json = [
+        // sold before any Docked or Location event, the station faction is unknown
+        r#"{ "timestamp":"2025-04-02T18:10:00Z", "event":"MarketSell", "MarketID":3228342528, "Type":"gold", "Count":10, "SellPrice":50000, "TotalSale":500000, "AvgPricePaid":45000 }"#,
+        r#"{ "timestamp":"2025-04-02T18:20:00Z", "event":"Docked", "StationName":"McDevitt Laboratory", "StationType":"Outpost", "Taxi":false, "Multicrew":false, "StarSystem":"Kholul", "SystemAddress":2415659059547, "MarketID":3228342528, "StationFaction":{ "Name":"SI Terraforming" }, "StationGovernment":"$government_Corporate;", "StationGovernment_Localised":"Corporate", "StationAllegiance":"Independent", "StationServices":[ "dock", "autodock", "commodities", "facilitator", "materialtrader" ], "StationEconomy":"$economy_Industrial;", "StationEconomy_Localised":"Industrial", "StationEconomies":[ { "Name":"$economy_Industrial;", "Name_Localised":"Industrial", "Proportion":1.0 } ], "DistFromStarLS":320.5, "LandingPads":{ "Small":2, "Medium":4, "Large":0 } }"#,
+        // sold at a loss
+        r#"{ "timestamp":"2025-04-02T18:25:00Z", "event":"MarketSell", "MarketID":3228342528, "Type":"gold", "Count":10, "SellPrice":40000, "TotalSale":400000, "AvgPricePaid":45000 }"#,
+        // interstellar factors
+        r#"{ "timestamp":"2025-04-02T18:30:00Z", "event":"RedeemVoucher", "Type":"bounty", "Amount":75000, "Factions":[ { "Faction":"", "Amount":100000 } ], "BrokerPercentage":25.0 }"#,
+        r#"{ "timestamp":"2025-04-02T18:35:00Z", "event":"RedeemVoucher", "Type":"CombatBond", "Amount":52500, "Faction":"SI Terraforming" }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let bgs = BgsTracker::new(&lines);
+    assert_eq!(1, bgs.contributions.len());
+    let summary = bgs.contribution_summary("SI Terraforming");
+    let kholul = &summary[&Some(2415659059547)];
+    assert_eq!(Credits(52500), kholul[&ContributionKind::CombatBond]);
+    assert!(!kholul.contains_key(&ContributionKind::Trade));
+    assert!(bgs.contribution_summary("").is_empty());
+}
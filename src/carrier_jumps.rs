@@ -0,0 +1,410 @@
+use crate::{
+    EDString,
+    fleet_carrier::CarrierType,
+    galaxy::{KnownSystems, distance},
+    log_line::{EDLogEvent, EDLogLine},
+};
+use chrono::{DateTime, Duration, Utc};
+
+pub const CARRIER_MAX_JUMP_RANGE: f64 = 500.0;
+pub const CARRIER_FUEL_CAPACITY: u64 = 1000;
+pub const CARRIER_CAPACITY: u64 = 25000;
+/// time between arriving and being able to request the next jump
+pub const CARRIER_JUMP_COOLDOWN: Duration = Duration::minutes(5);
+/// time between a jump request and the departure
+pub const CARRIER_JUMP_COUNTDOWN: Duration = Duration::minutes(15);
+
+/// Tritium burned for a single jump, `used_capacity` is the occupied space of
+/// the carrier (crew, cargo and packs) in tons.
+pub fn carrier_jump_fuel(distance: f64, used_capacity: u64) -> u64 {
+    (5.0 + distance * (CARRIER_CAPACITY + used_capacity) as f64 / 200_000.0).round() as u64
+}
+
+#[derive(Clone, Debug)]
+pub struct CarrierJumpRecord {
+    pub carrier_id: u64,
+    pub requested: DateTime<Utc>,
+    pub departure: Option<DateTime<Utc>>,
+    pub arrived: Option<DateTime<Utc>>,
+    pub origin_system: Option<EDString>,
+    pub origin_address: Option<u64>,
+    pub destination_system: EDString,
+    pub destination_address: u64,
+    pub body: Option<EDString>,
+    /// measured from the tritium depot before and after the jump
+    pub fuel_burned: Option<u64>,
+}
+
+impl CarrierJumpRecord {
+    /// earliest time the next jump can be requested
+    pub fn cooldown_until(&self) -> Option<DateTime<Utc>> {
+        Some(self.arrived.or(self.departure)? + CARRIER_JUMP_COOLDOWN)
+    }
+
+    pub fn countdown(&self) -> Option<Duration> {
+        Some(self.departure? - self.requested)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CarrierTripPlan {
+    pub distance: f64,
+    pub jumps: u64,
+    pub tritium: u64,
+    /// countdown and cooldown of all jumps
+    pub duration: Duration,
+}
+
+impl CarrierTripPlan {
+    /// Plan a trip in maximum range jumps. The tritium carried as cargo is part
+    /// of `used_capacity`, so the estimate is slightly high towards the end of the trip.
+    pub fn new(distance: f64, used_capacity: u64) -> CarrierTripPlan {
+        let jumps = (distance / CARRIER_MAX_JUMP_RANGE).ceil() as u64;
+        let full_jumps = (distance / CARRIER_MAX_JUMP_RANGE).floor() as u64;
+        let remainder = distance - full_jumps as f64 * CARRIER_MAX_JUMP_RANGE;
+
+        let mut tritium = full_jumps * carrier_jump_fuel(CARRIER_MAX_JUMP_RANGE, used_capacity);
+        if jumps > full_jumps {
+            tritium += carrier_jump_fuel(remainder, used_capacity);
+        }
+
+        CarrierTripPlan {
+            distance,
+            jumps,
+            tritium,
+            duration: (CARRIER_JUMP_COUNTDOWN + CARRIER_JUMP_COOLDOWN) * jumps as i32,
+        }
+    }
+
+    /// tritium needed in the cargo hold on top of a full depot
+    pub fn tritium_in_cargo(&self, fuel_level: u64) -> u64 {
+        self.tritium
+            .saturating_sub(fuel_level.min(CARRIER_FUEL_CAPACITY))
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct CarrierTravelLog {
+    pub carrier_id: Option<u64>,
+    pub jumps: Vec<CarrierJumpRecord>,
+    pub pending: Option<CarrierJumpRecord>,
+    pub current_system: Option<EDString>,
+    pub current_address: Option<u64>,
+    pub fuel_level: Option<u64>,
+    pub used_capacity: Option<u64>,
+    pub tritium_purchase_order: Option<u32>,
+    pub tritium_sale_order: Option<u32>,
+    pub systems: KnownSystems,
+    jumps_since_fuel_level: usize,
+    // carrier events seen before the first CarrierStats told us which carrier is ours
+    unresolved: Vec<EDLogLine>,
+}
+
+impl CarrierTravelLog {
+    /// build the travel log of the commander's own carrier from log lines sorted by timestamp
+    pub fn new(lines: &[EDLogLine]) -> CarrierTravelLog {
+        let mut log = CarrierTravelLog::default();
+        for line in lines {
+            log.add_line(line);
+        }
+        log
+    }
+
+    // our carrier is the one we saw the stats of first, other events may be about squadron carriers
+    fn is_own_carrier(&self, carrier_id: u64) -> bool {
+        self.carrier_id == Some(carrier_id)
+    }
+
+    fn complete_pending(&mut self, arrived: Option<DateTime<Utc>>) {
+        if let Some(mut jump) = self.pending.take() {
+            jump.arrived = arrived;
+            self.current_system = Some(jump.destination_system.clone());
+            self.current_address = Some(jump.destination_address);
+            self.jumps.push(jump);
+            self.jumps_since_fuel_level += 1;
+        }
+    }
+
+    // fuel level before and after a change that is not a jump
+    fn observe_fuel(&mut self, before: u64, after: u64) {
+        if let Some(previous) = self.fuel_level
+            && self.jumps_since_fuel_level == 1
+            && previous >= before
+            && let Some(jump) = self.jumps.last_mut()
+        {
+            jump.fuel_burned = Some(previous - before);
+        }
+        self.fuel_level = Some(after);
+        self.jumps_since_fuel_level = 0;
+    }
+
+    pub fn add_line(&mut self, line: &EDLogLine) {
+        self.systems.add_line(line);
+        let carrier_event = matches!(
+            line.event(),
+            EDLogEvent::CarrierJumpRequest(_)
+                | EDLogEvent::CarrierJumpCancelled(_)
+                | EDLogEvent::CarrierJump(_)
+                | EDLogEvent::CarrierLocation(_)
+                | EDLogEvent::CarrierDepositFuel(_)
+                | EDLogEvent::CarrierTradeOrder(_)
+        );
+        if carrier_event && self.carrier_id.is_none() {
+            self.unresolved.push(line.clone());
+        } else {
+            self.add_carrier_line(line);
+        }
+    }
+
+    fn add_carrier_line(&mut self, line: &EDLogLine) {
+        let timestamp = *line.timestamp();
+        match line.event() {
+            EDLogEvent::CarrierStats(stats)
+                if stats.carrier_type != Some(CarrierType::SquadronCarrier) =>
+            {
+                if self.carrier_id.is_none() {
+                    self.carrier_id = Some(stats.carrier_id);
+                    for earlier in std::mem::take(&mut self.unresolved) {
+                        self.add_carrier_line(&earlier);
+                    }
+                }
+                if self.carrier_id != Some(stats.carrier_id) {
+                    return;
+                }
+                let space = &stats.space_usage;
+                self.used_capacity = Some(space.total_capacity - space.free_space);
+                self.observe_fuel(stats.fuel_level, stats.fuel_level);
+            }
+            EDLogEvent::CarrierJumpRequest(request)
+                if request.carrier_type != Some(CarrierType::SquadronCarrier)
+                    && self.is_own_carrier(request.carrier_id) =>
+            {
+                // a new request means the previous jump has been made
+                self.complete_pending(None);
+                self.pending = Some(CarrierJumpRecord {
+                    carrier_id: request.carrier_id,
+                    requested: timestamp,
+                    departure: request.departure_time.as_str().parse().ok(),
+                    arrived: None,
+                    origin_system: self.current_system.clone(),
+                    origin_address: self.current_address,
+                    destination_system: request.system_name.clone(),
+                    destination_address: request.system_address,
+                    body: request.body.clone(),
+                    fuel_burned: None,
+                });
+            }
+            EDLogEvent::CarrierJumpCancelled(cancelled)
+                if self.is_own_carrier(cancelled.carrier_id) =>
+            {
+                self.pending = None;
+            }
+            EDLogEvent::CarrierJump(jump)
+                if jump
+                    .station_information
+                    .as_ref()
+                    .is_some_and(|s| self.is_own_carrier(s.market_id))
+                    && self
+                        .pending
+                        .as_ref()
+                        .is_some_and(|p| p.destination_address == jump.system_address) =>
+            {
+                self.complete_pending(Some(timestamp));
+            }
+            EDLogEvent::CarrierLocation(location)
+                if location.carrier_type != Some(CarrierType::SquadronCarrier)
+                    && self.is_own_carrier(location.carrier_id) =>
+            {
+                if self
+                    .pending
+                    .as_ref()
+                    .is_some_and(|p| p.destination_address == location.system_address)
+                {
+                    self.complete_pending(None);
+                }
+                self.current_system = Some(location.star_system.clone());
+                self.current_address = Some(location.system_address);
+            }
+            EDLogEvent::CarrierDepositFuel(deposit) if self.is_own_carrier(deposit.carrier_id) => {
+                self.observe_fuel(deposit.total.saturating_sub(deposit.amount), deposit.total);
+            }
+            EDLogEvent::CarrierTradeOrder(order)
+                if order.commodity.as_str().eq_ignore_ascii_case("tritium")
+                    && self.is_own_carrier(order.carrier_id) =>
+            {
+                if order.cancel_trade == Some(true) {
+                    self.tritium_purchase_order = None;
+                    self.tritium_sale_order = None;
+                } else {
+                    self.tritium_purchase_order = order.purchase_order;
+                    self.tritium_sale_order = order.sale_order;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// distance of a jump in light years, if both systems have been visited
+    pub fn jump_distance(&self, jump: &CarrierJumpRecord) -> Option<f64> {
+        let from = self.systems.position(jump.origin_address?)?;
+        let to = self.systems.position(jump.destination_address)?;
+        Some(distance(&from, &to))
+    }
+
+    /// tritium burned by a jump, estimated from the distance if it was not measured
+    pub fn jump_fuel(&self, jump: &CarrierJumpRecord) -> Option<u64> {
+        jump.fuel_burned.or_else(|| {
+            Some(carrier_jump_fuel(
+                self.jump_distance(jump)?,
+                self.used_capacity?,
+            ))
+        })
+    }
+
+    pub fn total_distance(&self) -> f64 {
+        self.jumps
+            .iter()
+            .filter_map(|j| self.jump_distance(j))
+            .sum()
+    }
+
+    /// plan a trip from the current carrier location at the current cargo load
+    pub fn plan_trip(&self, to: &[f64; 3]) -> Option<CarrierTripPlan> {
+        let from = self.systems.position(self.current_address?)?;
+        Some(CarrierTripPlan::new(
+            distance(&from, to),
+            self.used_capacity.unwrap_or(0),
+        ))
+    }
+}
+
+#[test]
+fn test_carrier_travel_log() {
+    let json = [
+        r#"{ "timestamp":"2024-10-13T17:51:23Z", "event":"FSDJump", "StarSystem":"Yukoukha", "SystemAddress":22945325796176, "StarPos":[-134.56250,-34.03125,-4.75000], "SystemAllegiance":"Independent", "SystemEconomy":"$economy_Military;", "SystemEconomy_Localised":"Military", "SystemGovernment":"$government_Corporate;", "SystemGovernment_Localised":"Corporate", "SystemSecurity":"$SYSTEM_SECURITY_low;", "SystemSecurity_Localised":"Low Security", "Population":33893, "JumpDist":13.790, "FuelUsed":0.371038, "FuelLevel":5.628962 }"#,
+        r#"{ "timestamp":"2024-10-13T17:55:23Z", "event":"FSDJump", "StarSystem":"Sol", "SystemAddress":10477373803, "StarPos":[0.0,0.0,0.0], "SystemAllegiance":"Federation", "SystemEconomy":"$economy_Refinery;", "SystemEconomy_Localised":"Refinery", "SystemGovernment":"$government_Democracy;", "SystemGovernment_Localised":"Democracy", "SystemSecurity":"$SYSTEM_SECURITY_high;", "SystemSecurity_Localised":"High Security", "Population":22780919531, "JumpDist":13.790, "FuelUsed":0.371038, "FuelLevel":5.628962 }"#,
+        r#"{ "timestamp":"2024-10-13T17:56:00Z", "event":"CarrierLocation", "CarrierType":"SquadronCarrier", "CarrierID":987654321, "StarSystem":"Yukoukha", "SystemAddress":22945325796176, "BodyID":0 }"#,
+        r#"{ "timestamp":"2024-10-13T17:57:00Z", "event":"CarrierStats", "CarrierID":123456789, "CarrierType":"FleetCarrier", "Callsign":"MFC-MFC", "Name":"My First Carrier", "DockingAccess":"all", "AllowNotorious":false, "FuelLevel":900, "JumpRangeCurr":500.0, "JumpRangeMax":500.0, "PendingDecommission":false, "SpaceUsage":{ "TotalCapacity":25000, "Crew":6370, "Cargo":3160, "CargoSpaceReserved":0, "ShipPacks":0, "ModulePacks":0, "FreeSpace":15470 }, "Finance":{ "CarrierBalance":803361, "ReserveBalance":322176, "AvailableBalance":481185 }, "Crew":[], "ShipPacks":[], "ModulePacks":[] }"#,
+        r#"{ "timestamp":"2024-10-13T18:00:00Z", "event":"CarrierLocation", "CarrierType":"FleetCarrier", "CarrierID":123456789, "StarSystem":"Sol", "SystemAddress":10477373803, "BodyID":3 }"#,
+        r#"{ "timestamp":"2024-10-13T18:01:00Z", "event":"CarrierDepositFuel", "CarrierID":123456789, "Amount":100, "Total":1000 }"#,
+        r#"{ "timestamp":"2024-10-13T18:02:00Z", "event":"CarrierJumpRequest", "CarrierType":"FleetCarrier", "CarrierID":123456789, "SystemName":"Yukoukha", "Body":"Yukoukha", "SystemAddress":22945325796176, "BodyID":0, "DepartureTime":"2024-10-13T18:17:00Z" }"#,
+        r#"{ "timestamp":"2024-10-13T18:30:00Z", "event":"CarrierLocation", "CarrierType":"FleetCarrier", "CarrierID":123456789, "StarSystem":"Yukoukha", "SystemAddress":22945325796176, "BodyID":0 }"#,
+        r#"{ "timestamp":"2024-10-13T18:31:00Z", "event":"CarrierDepositFuel", "CarrierID":123456789, "Amount":40, "Total":1000 }"#,
+        r#"{ "timestamp":"2024-10-13T18:32:00Z", "event":"CarrierJumpRequest", "CarrierType":"FleetCarrier", "CarrierID":123456789, "SystemName":"Sol", "SystemAddress":10477373803, "BodyID":0, "DepartureTime":"2024-10-13T18:47:00Z" }"#,
+        r#"{ "timestamp":"2024-10-13T18:33:00Z", "event":"CarrierJumpCancelled", "CarrierType":"FleetCarrier", "CarrierID":123456789 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let log = CarrierTravelLog::new(&lines);
+    assert_eq!(Some(123456789), log.carrier_id);
+    assert_eq!(1, log.jumps.len());
+    assert!(log.pending.is_none());
+
+    let jump = &log.jumps[0];
+    assert_eq!("Sol", jump.origin_system.unwrap().as_str());
+    assert_eq!(Some(Duration::minutes(15)), jump.countdown());
+    assert_eq!(Some(40), jump.fuel_burned);
+    assert!((log.jump_distance(jump).unwrap() - 138.88).abs() < 0.01);
+    assert_eq!("Yukoukha", log.current_system.unwrap().as_str());
+
+    let plan = CarrierTripPlan::new(1200.0, 6370);
+    assert_eq!(3, plan.jumps);
+    // 2 jumps of 500 ly at 83 tons and one of 200 ly at 36 tons
+    assert_eq!(202, plan.tritium);
+    assert_eq!(0, plan.tritium_in_cargo(1000));
+}
+
+#[test]
+fn test_carrier_events_before_stats() {
+    let json = [
+        r#"{ "timestamp":"2024-10-13T17:56:00Z", "event":"CarrierLocation", "CarrierType":"SquadronCarrier", "CarrierID":987654321, "StarSystem":"Yukoukha", "SystemAddress":22945325796176, "BodyID":0 }"#,
+        r#"{ "timestamp":"2024-10-13T18:00:00Z", "event":"CarrierLocation", "CarrierType":"FleetCarrier", "CarrierID":123456789, "StarSystem":"Sol", "SystemAddress":10477373803, "BodyID":3 }"#,
+        r#"{ "timestamp":"2024-10-13T18:02:00Z", "event":"CarrierJumpRequest", "CarrierType":"FleetCarrier", "CarrierID":123456789, "SystemName":"Yukoukha", "Body":"Yukoukha", "SystemAddress":22945325796176, "BodyID":0, "DepartureTime":"2024-10-13T18:17:00Z" }"#,
+        r#"{ "timestamp":"2024-10-13T18:30:00Z", "event":"CarrierLocation", "CarrierType":"FleetCarrier", "CarrierID":123456789, "StarSystem":"Yukoukha", "SystemAddress":22945325796176, "BodyID":0 }"#,
+        r#"{ "timestamp":"2024-10-13T18:40:00Z", "event":"CarrierStats", "CarrierID":123456789, "CarrierType":"FleetCarrier", "Callsign":"MFC-MFC", "Name":"My First Carrier", "DockingAccess":"all", "AllowNotorious":false, "FuelLevel":900, "JumpRangeCurr":500.0, "JumpRangeMax":500.0, "PendingDecommission":false, "SpaceUsage":{ "TotalCapacity":25000, "Crew":6370, "Cargo":3160, "CargoSpaceReserved":0, "ShipPacks":0, "ModulePacks":0, "FreeSpace":15470 }, "Finance":{ "CarrierBalance":803361, "ReserveBalance":322176, "AvailableBalance":481185 }, "Crew":[], "ShipPacks":[], "ModulePacks":[] }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    // the jump is only known to be ours once CarrierStats has been seen
+    let log = CarrierTravelLog::new(&lines);
+    assert_eq!(Some(123456789), log.carrier_id);
+    assert_eq!(1, log.jumps.len());
+    assert_eq!("Sol", log.jumps[0].origin_system.unwrap().as_str());
+    assert_eq!("Yukoukha", log.current_system.unwrap().as_str());
+    assert_eq!(Some(900), log.fuel_level);
+
+    let mut partial = CarrierTravelLog::default();
+    lines[..4].iter().for_each(|line| partial.add_line(line));
+    assert!(partial.jumps.is_empty());
+    assert!(partial.current_system.is_none());
+}
+
+#[test]
+fn test_carrier_travel_log_without_stats() {
+    let log = CarrierTravelLog::new(&[]);
+    assert!(log.carrier_id.is_none());
+    assert!(log.plan_trip(&crate::galaxy::SOL).is_none());
+    assert_eq!(0.0, log.total_distance());
+
+    let json = [
+        // stats of a squadron carrier don't make it ours
+        r#"{ "timestamp":"2024-10-13T17:57:00Z", "event":"CarrierStats", "CarrierID":987654321, "CarrierType":"SquadronCarrier", "Callsign":"SQD-001", "Name":"Squadron Carrier", "DockingAccess":"all", "AllowNotorious":false, "FuelLevel":500, "JumpRangeCurr":500.0, "JumpRangeMax":500.0, "PendingDecommission":false, "SpaceUsage":{ "TotalCapacity":25000, "Crew":6370, "Cargo":0, "CargoSpaceReserved":0, "ShipPacks":0, "ModulePacks":0, "FreeSpace":18630 }, "Finance":{ "CarrierBalance":803361, "ReserveBalance":322176, "AvailableBalance":481185 }, "Crew":[], "ShipPacks":[], "ModulePacks":[] }"#,
+        r#"{ "timestamp":"2024-10-13T18:02:00Z", "event":"CarrierJumpRequest", "CarrierType":"FleetCarrier", "CarrierID":123456789, "SystemName":"Yukoukha", "Body":"Yukoukha", "SystemAddress":22945325796176, "BodyID":0, "DepartureTime":"2024-10-13T18:17:00Z" }"#,
+        r#"{ "timestamp":"2024-10-13T18:30:00Z", "event":"CarrierLocation", "CarrierType":"FleetCarrier", "CarrierID":123456789, "StarSystem":"Yukoukha", "SystemAddress":22945325796176, "BodyID":0 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let log = CarrierTravelLog::new(&lines);
+    assert!(log.carrier_id.is_none());
+    assert!(log.jumps.is_empty());
+    assert!(log.pending.is_none());
+    assert!(log.fuel_level.is_none());
+}
+
+#[test]
+fn test_carrier_jump_without_arrival() {
+    let json = [
+        r#"{ "timestamp":"2024-10-13T17:57:00Z", "event":"CarrierStats", "CarrierID":123456789, "CarrierType":"FleetCarrier", "Callsign":"MFC-MFC", "Name":"My First Carrier", "DockingAccess":"all", "AllowNotorious":false, "FuelLevel":900, "JumpRangeCurr":500.0, "JumpRangeMax":500.0, "PendingDecommission":false, "SpaceUsage":{ "TotalCapacity":25000, "Crew":6370, "Cargo":3160, "CargoSpaceReserved":0, "ShipPacks":0, "ModulePacks":0, "FreeSpace":15470 }, "Finance":{ "CarrierBalance":803361, "ReserveBalance":322176, "AvailableBalance":481185 }, "Crew":[], "ShipPacks":[], "ModulePacks":[] }"#,
+        r#"{ "timestamp":"2024-10-13T18:02:00Z", "event":"CarrierJumpRequest", "CarrierType":"FleetCarrier", "CarrierID":123456789, "SystemName":"Yukoukha", "Body":"Yukoukha", "SystemAddress":22945325796176, "BodyID":0, "DepartureTime":"2024-10-13T18:17:00Z" }"#,
+        // no CarrierLocation for the arrival, the next request shows the jump was made
+        r#"{ "timestamp":"2024-10-13T18:32:00Z", "event":"CarrierJumpRequest", "CarrierType":"FleetCarrier", "CarrierID":123456789, "SystemName":"Sol", "SystemAddress":10477373803, "BodyID":0, "DepartureTime":"2024-10-13T18:47:00Z" }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let log = CarrierTravelLog::new(&lines);
+    assert_eq!(1, log.jumps.len());
+    let jump = &log.jumps[0];
+    assert!(jump.origin_system.is_none());
+    assert!(jump.arrived.is_none());
+    // cooldown counts from the departure when the arrival is unknown
+    assert_eq!(
+        jump.departure.map(|d| d + CARRIER_JUMP_COOLDOWN),
+        jump.cooldown_until()
+    );
+    // neither system position is known
+    assert!(log.jump_distance(jump).is_none());
+    assert!(log.jump_fuel(jump).is_none());
+    assert_eq!(
+        "Yukoukha",
+        log.pending
+            .as_ref()
+            .unwrap()
+            .origin_system
+            .unwrap()
+            .as_str()
+    );
+}
+
+#[test]
+fn test_carrier_trip_plan_edges() {
+    let plan = CarrierTripPlan::new(0.0, 0);
+    assert_eq!(0, plan.jumps);
+    assert_eq!(0, plan.tritium);
+    assert_eq!(Duration::zero(), plan.duration);
+
+    let plan = CarrierTripPlan::new(CARRIER_MAX_JUMP_RANGE, 0);
+    assert_eq!(1, plan.jumps);
+    assert_eq!(carrier_jump_fuel(CARRIER_MAX_JUMP_RANGE, 0), plan.tritium);
+    // the depot never holds more than its capacity
+    assert_eq!(0, plan.tritium_in_cargo(5000));
+}
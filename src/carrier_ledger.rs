@@ -0,0 +1,524 @@
+use crate::{
+    EDString,
+    common_types::Credits,
+    fleet_carrier::{CrewMember, CrewRole, CrewServiceOperation, SpaceUsage},
+    log_line::{EDLogEvent, EDLogLine},
+};
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+
+/// weekly upkeep of the carrier itself, without any optional services
+pub const CORE_UPKEEP: Credits = Credits(5_000_000);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CarrierServiceState {
+    Active,
+    Paused,
+}
+
+/// Weekly upkeep of an optional service as (active, paused).
+/// Core services (captain, commodities, tritium depot) are part of [`CORE_UPKEEP`].
+pub fn service_upkeep(role: CrewRole) -> Option<(Credits, Credits)> {
+    let (active, paused) = match role {
+        CrewRole::Refuel | CrewRole::Repair | CrewRole::Rearm => (1_500_000, 750_000),
+        CrewRole::Shipyard => (6_500_000, 1_800_000),
+        CrewRole::Outfitting => (5_000_000, 1_500_000),
+        CrewRole::BlackMarket => (2_000_000, 1_250_000),
+        CrewRole::VoucherRedemption => (1_850_000, 850_000),
+        CrewRole::Exploration => (1_850_000, 700_000),
+        CrewRole::VistaGenomics => (1_500_000, 700_000),
+        CrewRole::Bartender => (1_750_000, 1_250_000),
+        CrewRole::PioneerSupplies => (5_000_000, 1_250_000),
+        CrewRole::Captain | CrewRole::Commodities | CrewRole::CarrierFuel => return None,
+    };
+    Some((Credits(active), Credits(paused)))
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CarrierTaxRates {
+    pub rearm: Option<u64>,
+    pub refuel: Option<u64>,
+    pub repair: Option<u64>,
+    pub shipyard: Option<u64>,
+    pub outfitting: Option<u64>,
+    pub pioneer_supplies: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CarrierLedgerEntryKind {
+    Deposit,
+    Withdraw,
+    ModulePackPurchase,
+    ModulePackRefund,
+    /// tariff on services we paid for while docked at the carrier
+    Tariff,
+    /// commodities the carrier market sold to us, income for the carrier
+    CarrierSoldToUs,
+    /// commodities the carrier market bought from us, a cost for the carrier
+    CarrierBoughtFromUs,
+    /// balance increase not explained by the journal, like trades and tariffs of other commanders
+    Other,
+    /// balance decrease not explained by the journal, upkeep and market purchases
+    Expenses,
+}
+
+#[derive(Clone, Debug)]
+pub struct CarrierLedgerEntry {
+    pub timestamp: DateTime<Utc>,
+    pub kind: CarrierLedgerEntryKind,
+    /// change of the carrier balance, negative for costs
+    pub amount: Credits,
+}
+
+#[derive(Clone, Debug)]
+pub struct CarrierTradeOrder {
+    pub commodity: EDString,
+    pub sale_order: Option<u32>,
+    pub purchase_order: Option<u32>,
+    pub price: Option<Credits>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CarrierLedger {
+    pub carrier_id: u64,
+    pub callsign: Option<EDString>,
+    pub name: Option<EDString>,
+    pub balance: Credits,
+    pub reserve_balance: Credits,
+    pub available_balance: Credits,
+    pub reserve_percent: Option<f64>,
+    pub tax_rates: CarrierTaxRates,
+    pub space_usage: Option<SpaceUsage>,
+    pub pending_decommission: bool,
+    pub services: BTreeMap<CrewRole, CarrierServiceState>,
+    pub trade_orders: HashMap<EDString, CarrierTradeOrder>,
+    pub entries: Vec<CarrierLedgerEntry>,
+    pub last_update: DateTime<Utc>,
+    // balance as reported by the last finance snapshot plus the known changes since
+    expected_balance: Option<Credits>,
+}
+
+impl CarrierLedger {
+    fn new(carrier_id: u64, timestamp: DateTime<Utc>) -> CarrierLedger {
+        CarrierLedger {
+            carrier_id,
+            callsign: None,
+            name: None,
+            balance: Credits(0),
+            reserve_balance: Credits(0),
+            available_balance: Credits(0),
+            reserve_percent: None,
+            tax_rates: CarrierTaxRates::default(),
+            space_usage: None,
+            pending_decommission: false,
+            services: BTreeMap::new(),
+            trade_orders: HashMap::new(),
+            entries: Vec::new(),
+            last_update: timestamp,
+            expected_balance: None,
+        }
+    }
+
+    fn add_entry(
+        &mut self,
+        timestamp: DateTime<Utc>,
+        kind: CarrierLedgerEntryKind,
+        amount: Credits,
+    ) {
+        self.entries.push(CarrierLedgerEntry {
+            timestamp,
+            kind,
+            amount,
+        });
+        if let Some(expected) = self.expected_balance.as_mut() {
+            *expected += amount;
+        }
+        self.balance += amount;
+    }
+
+    // a balance reported by the game, any difference to the expected balance is other income or expenses
+    fn observe_balance(&mut self, timestamp: DateTime<Utc>, balance: Credits) {
+        if let Some(expected) = self.expected_balance {
+            let difference = balance - expected;
+            if difference.0 > 0 {
+                self.add_entry(timestamp, CarrierLedgerEntryKind::Other, difference);
+            } else if difference.0 < 0 {
+                self.add_entry(timestamp, CarrierLedgerEntryKind::Expenses, difference);
+            }
+        }
+        self.expected_balance = Some(balance);
+        self.balance = balance;
+        self.last_update = timestamp;
+    }
+
+    fn update_crew(&mut self, crew: &[CrewMember]) {
+        self.services.clear();
+        for member in crew {
+            if !member.activated {
+                continue;
+            }
+            let enabled = member
+                .enabled
+                .or(member.activated_props.as_ref().map(|p| p.enabled))
+                .unwrap_or(true);
+            let state = if enabled {
+                CarrierServiceState::Active
+            } else {
+                CarrierServiceState::Paused
+            };
+            self.services.insert(member.crew_role, state);
+        }
+    }
+
+    /// weekly upkeep of the installed services, paused services at their reduced rate
+    pub fn upkeep_by_service(&self) -> Vec<(CrewRole, CarrierServiceState, Credits)> {
+        self.services
+            .iter()
+            .filter_map(|(role, state)| {
+                let (active, paused) = service_upkeep(*role)?;
+                let cost = match state {
+                    CarrierServiceState::Active => active,
+                    CarrierServiceState::Paused => paused,
+                };
+                Some((*role, *state, cost))
+            })
+            .collect()
+    }
+
+    pub fn weekly_upkeep(&self) -> Credits {
+        CORE_UPKEEP
+            + self
+                .upkeep_by_service()
+                .into_iter()
+                .map(|(_, _, cost)| cost)
+                .sum()
+    }
+
+    // part of a service cost that is the carrier's tariff, `rate` is a percentage on top of the base cost
+    fn add_tariff(&mut self, timestamp: DateTime<Utc>, cost: Credits, rate: Option<u64>) {
+        let Some(rate) = rate.filter(|r| *r > 0) else {
+            return;
+        };
+        let tariff = Credits(cost.0 * rate as i64 / (100 + rate as i64));
+        self.add_entry(timestamp, CarrierLedgerEntryKind::Tariff, tariff);
+    }
+
+    pub fn total(&self, kind: CarrierLedgerEntryKind) -> Credits {
+        self.entries
+            .iter()
+            .filter(|e| e.kind == kind)
+            .map(|e| e.amount)
+            .sum()
+    }
+
+    /// tariffs on services we paid for at the carrier
+    pub fn tariff_income(&self) -> Credits {
+        self.total(CarrierLedgerEntryKind::Tariff)
+    }
+
+    /// our own sales to and purchases from the carrier market
+    pub fn market_income(&self) -> Credits {
+        self.total(CarrierLedgerEntryKind::CarrierSoldToUs)
+            + self.total(CarrierLedgerEntryKind::CarrierBoughtFromUs)
+    }
+
+    /// balance increase the journal doesn't explain
+    pub fn other_income(&self) -> Credits {
+        self.total(CarrierLedgerEntryKind::Other)
+    }
+
+    /// credits committed to open purchase orders on the carrier market
+    pub fn committed_to_purchase_orders(&self) -> Credits {
+        self.trade_orders
+            .values()
+            .filter_map(|o| Some(o.price? * o.purchase_order? as u64))
+            .sum()
+    }
+
+    /// Number of full weeks of upkeep the current balance can pay for, without
+    /// any further income. The carrier is decommissioned once it can no longer pay.
+    pub fn weeks_until_decommission(&self) -> u64 {
+        let upkeep = self.weekly_upkeep();
+        if self.balance.0 <= 0 {
+            return 0;
+        }
+        (self.balance.0 / upkeep.0) as u64
+    }
+
+    /// date the balance runs out, assuming upkeep is paid every week from `now`
+    pub fn decommission_projection(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        now + chrono::Duration::weeks(self.weeks_until_decommission() as i64 + 1)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct CarrierLedgers {
+    carriers: BTreeMap<u64, CarrierLedger>,
+    // carrier we are docked at
+    docked: Option<u64>,
+}
+
+impl CarrierLedgers {
+    /// build the ledgers from log lines sorted by timestamp
+    pub fn new(lines: &[EDLogLine]) -> CarrierLedgers {
+        let mut ledgers = CarrierLedgers::default();
+        for line in lines {
+            ledgers.add_line(line);
+        }
+        ledgers
+    }
+
+    fn ledger(&mut self, carrier_id: u64, timestamp: DateTime<Utc>) -> &mut CarrierLedger {
+        self.carriers
+            .entry(carrier_id)
+            .or_insert_with(|| CarrierLedger::new(carrier_id, timestamp))
+    }
+
+    fn docked_ledger(&mut self) -> Option<&mut CarrierLedger> {
+        self.carriers.get_mut(&self.docked?)
+    }
+
+    pub fn add_line(&mut self, line: &EDLogLine) {
+        let timestamp = *line.timestamp();
+        match line.event() {
+            EDLogEvent::Docked(docked) => {
+                self.docked = docked
+                    .station_identification
+                    .market_id
+                    .filter(|id| self.carriers.contains_key(id));
+            }
+            EDLogEvent::Undocked(_) => self.docked = None,
+            EDLogEvent::MarketBuy(buy) if self.docked == Some(buy.market_id) => {
+                if let Some(ledger) = self.docked_ledger() {
+                    ledger.add_entry(
+                        timestamp,
+                        CarrierLedgerEntryKind::CarrierSoldToUs,
+                        buy.total_cost,
+                    );
+                }
+            }
+            EDLogEvent::MarketSell(sell)
+                if sell.market_id.is_some() && self.docked == sell.market_id =>
+            {
+                if let Some(ledger) = self.docked_ledger() {
+                    ledger.add_entry(
+                        timestamp,
+                        CarrierLedgerEntryKind::CarrierBoughtFromUs,
+                        -sell.total_sale,
+                    );
+                }
+            }
+            EDLogEvent::RefuelAll(refuel) => {
+                if let Some(ledger) = self.docked_ledger() {
+                    let rate = ledger.tax_rates.refuel;
+                    ledger.add_tariff(timestamp, refuel.cost, rate);
+                }
+            }
+            EDLogEvent::Repair(repair) => {
+                if let Some(ledger) = self.docked_ledger() {
+                    let rate = ledger.tax_rates.repair;
+                    ledger.add_tariff(timestamp, repair.cost, rate);
+                }
+            }
+            EDLogEvent::RepairAll(repair) => {
+                if let Some(ledger) = self.docked_ledger() {
+                    let rate = ledger.tax_rates.repair;
+                    ledger.add_tariff(timestamp, repair.cost, rate);
+                }
+            }
+            EDLogEvent::BuyAmmo(ammo) => {
+                if let Some(ledger) = self.docked_ledger() {
+                    let rate = ledger.tax_rates.rearm;
+                    ledger.add_tariff(timestamp, ammo.cost, rate);
+                }
+            }
+            EDLogEvent::CarrierStats(stats) => {
+                let ledger = self.ledger(stats.carrier_id, timestamp);
+                ledger.callsign = Some(stats.callsign.clone());
+                ledger.name = Some(stats.name.clone());
+                ledger.pending_decommission = stats.pending_decommission;
+                ledger.space_usage = Some(stats.space_usage.clone());
+                ledger.reserve_balance = stats.finance.reserve_balance;
+                ledger.available_balance = stats.finance.available_balance;
+                ledger.reserve_percent = stats.finance.reserve_percent;
+                ledger.tax_rates = CarrierTaxRates {
+                    rearm: stats.finance.tax_rate_rearm,
+                    refuel: stats.finance.tax_rate_refuel,
+                    repair: stats.finance.tax_rate_repair,
+                    shipyard: stats.finance.tax_rate_shipyard,
+                    outfitting: stats.finance.tax_rate_outfitting,
+                    pioneer_supplies: stats.finance.tax_rate_pioneer_supplies,
+                };
+                ledger.update_crew(&stats.crew);
+                ledger.observe_balance(timestamp, stats.finance.carrier_balance);
+            }
+            EDLogEvent::CarrierFinance(finance) => {
+                let ledger = self.ledger(finance.carrier_id, timestamp);
+                ledger.reserve_balance = finance.reserve_balance;
+                ledger.available_balance = finance.available_balance;
+                ledger.reserve_percent = Some(finance.reserve_percent as f64);
+                let rate = |r: Option<u8>| r.map(|r| r as u64);
+                ledger.tax_rates = CarrierTaxRates {
+                    rearm: rate(finance.tax_rate_rearm),
+                    refuel: rate(finance.tax_rate_refuel),
+                    repair: rate(finance.tax_rate_repair),
+                    shipyard: rate(finance.tax_rate_shipyard),
+                    outfitting: rate(finance.tax_rate_outfitting),
+                    pioneer_supplies: rate(finance.tax_rate_pioneersupplies),
+                };
+                ledger.observe_balance(timestamp, finance.carrier_balance);
+            }
+            EDLogEvent::CarrierBankTransfer(transfer) => {
+                let ledger = self.ledger(transfer.carrier_id, timestamp);
+                if let Some(deposit) = transfer.deposit {
+                    ledger.add_entry(timestamp, CarrierLedgerEntryKind::Deposit, deposit);
+                }
+                if let Some(withdraw) = transfer.withdraw {
+                    ledger.add_entry(timestamp, CarrierLedgerEntryKind::Withdraw, -withdraw);
+                }
+                ledger.observe_balance(timestamp, transfer.carrier_balance);
+            }
+            EDLogEvent::CarrierModulePack(pack) => {
+                let ledger = self.ledger(pack.carrier_id, timestamp);
+                if let Some(cost) = pack.cost {
+                    ledger.add_entry(timestamp, CarrierLedgerEntryKind::ModulePackPurchase, -cost);
+                }
+                if let Some(refund) = pack.refund {
+                    ledger.add_entry(timestamp, CarrierLedgerEntryKind::ModulePackRefund, refund);
+                }
+                ledger.last_update = timestamp;
+            }
+            EDLogEvent::CarrierCrewServices(crew) => {
+                let ledger = self.ledger(crew.carrier_id, timestamp);
+                match crew.operation {
+                    CrewServiceOperation::Activate | CrewServiceOperation::Resume => {
+                        ledger
+                            .services
+                            .insert(crew.crew_role, CarrierServiceState::Active);
+                    }
+                    CrewServiceOperation::Pause => {
+                        ledger
+                            .services
+                            .insert(crew.crew_role, CarrierServiceState::Paused);
+                    }
+                    CrewServiceOperation::Deactivate => {
+                        ledger.services.remove(&crew.crew_role);
+                    }
+                    CrewServiceOperation::Replace => {}
+                }
+                ledger.last_update = timestamp;
+            }
+            EDLogEvent::CarrierTradeOrder(order) => {
+                let ledger = self.ledger(order.carrier_id, timestamp);
+                if order.cancel_trade == Some(true) {
+                    ledger.trade_orders.remove(&order.commodity);
+                } else {
+                    ledger.trade_orders.insert(
+                        order.commodity.clone(),
+                        CarrierTradeOrder {
+                            commodity: order.commodity.clone(),
+                            sale_order: order.sale_order,
+                            purchase_order: order.purchase_order,
+                            price: order.price,
+                        },
+                    );
+                }
+                ledger.last_update = timestamp;
+            }
+            _ => {}
+        }
+    }
+
+    pub fn get(&self, carrier_id: u64) -> Option<&CarrierLedger> {
+        self.carriers.get(&carrier_id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &CarrierLedger> {
+        self.carriers.values()
+    }
+}
+
+#[test]
+fn test_carrier_ledger() {
+    let json = [
+        r#"{ "timestamp":"2025-08-21T10:38:00Z", "event":"CarrierFinance", "CarrierID":123456789, "CarrierType":"FleetCarrier", "CarrierBalance":100000000, "ReserveBalance":50000000, "AvailableBalance":50000000, "ReservePercent":50, "TaxRate_rearm":25, "TaxRate_refuel":25, "TaxRate_repair":25 }"#,
+        r#"{ "timestamp":"2025-08-21T10:38:45Z", "event":"CarrierBankTransfer", "CarrierID":123456789, "CarrierType":"FleetCarrier", "Deposit":10000000, "PlayerBalance":18879865108, "CarrierBalance":110000000 }"#,
+        r#"{ "timestamp":"2025-08-21T10:40:00Z", "event":"CarrierCrewServices", "CarrierID":123456789, "CarrierType":"FleetCarrier", "CrewRole":"Refuel", "Operation":"Activate", "CrewName":"Akemi Cunningham" }"#,
+        r#"{ "timestamp":"2025-08-21T10:41:00Z", "event":"CarrierCrewServices", "CarrierID":123456789, "CarrierType":"FleetCarrier", "CrewRole":"Bartender", "Operation":"Activate", "CrewName":"Aleeah Bogdani" }"#,
+        r#"{ "timestamp":"2025-08-21T10:42:00Z", "event":"CarrierCrewServices", "CarrierID":123456789, "CarrierType":"FleetCarrier", "CrewRole":"Bartender", "Operation":"Pause", "CrewName":"Aleeah Bogdani" }"#,
+        r#"{ "timestamp":"2025-08-21T10:43:00Z", "event":"CarrierTradeOrder", "CarrierID":123456789, "BlackMarket":false, "Commodity":"tritium", "PurchaseOrder":1000, "Price":50000 }"#,
+        r#"{ "timestamp":"2025-08-21T11:00:00Z", "event":"Docked", "StationName":"X7J-BQG", "StationType":"FleetCarrier", "Taxi":false, "Multicrew":false, "StarSystem":"Sol", "SystemAddress":10477373803, "MarketID":123456789, "StationFaction":{ "Name":"FleetCarrier" }, "StationGovernment":"$government_Carrier;", "StationGovernment_Localised":"Private Ownership", "StationServices":[ "dock", "autodock", "commodities", "refuel" ], "StationEconomy":"$economy_Carrier;", "StationEconomy_Localised":"Private Enterprise", "StationEconomies":[ { "Name":"$economy_Carrier;", "Name_Localised":"Private Enterprise", "Proportion":1.000000 } ], "DistFromStarLS":490.0, "LandingPads":{ "Small":4, "Medium":4, "Large":8 } }"#,
+        r#"{ "timestamp":"2025-08-21T11:01:00Z", "event":"RefuelAll", "Cost":1250, "Amount":25.0 }"#,
+        r#"{ "timestamp":"2025-08-21T11:02:00Z", "event":"MarketBuy", "MarketID":123456789, "Type":"tritium", "Count":20, "BuyPrice":50000, "TotalCost":1000000 }"#,
+        r#"{ "timestamp":"2025-08-21T11:03:00Z", "event":"Undocked", "StationName":"X7J-BQG", "StationType":"FleetCarrier", "MarketID":123456789, "Taxi":false, "Multicrew":false }"#,
+        r#"{ "timestamp":"2025-08-21T11:30:00Z", "event":"RefuelAll", "Cost":60, "Amount":1.187222 }"#,
+        r#"{ "timestamp":"2025-08-22T10:38:49Z", "event":"CarrierFinance", "CarrierID":123456789, "CarrierType":"FleetCarrier", "CarrierBalance":113500250, "ReserveBalance":50000000, "AvailableBalance":62500000, "ReservePercent":50, "TaxRate_rearm":25, "TaxRate_refuel":25, "TaxRate_repair":25 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let ledgers = CarrierLedgers::new(&lines);
+    let ledger = ledgers.get(123456789).unwrap();
+
+    assert_eq!(Credits(113500250), ledger.balance);
+    assert_eq!(Some(25), ledger.tax_rates.rearm);
+    // 1000 base and 250 tariff for refuelling at the carrier, not the refuel elsewhere
+    assert_eq!(Credits(250), ledger.tariff_income());
+    assert_eq!(Credits(1000000), ledger.market_income());
+    // we bought from the carrier market, that is income for the carrier
+    assert_eq!(
+        Credits(1000000),
+        ledger.total(CarrierLedgerEntryKind::CarrierSoldToUs)
+    );
+    assert_eq!(Credits(2500000), ledger.other_income());
+    assert_eq!(Credits(50000000), ledger.committed_to_purchase_orders());
+    // core 5M, refuel active 1.5M, bartender paused 1.25M
+    assert_eq!(Credits(7750000), ledger.weekly_upkeep());
+    assert_eq!(14, ledger.weeks_until_decommission());
+}
+
+#[test]
+fn test_carrier_ledger_unknown_carrier() {
+    assert_eq!(0, CarrierLedgers::new(&[]).iter().count());
+
+    let json = [
+        // docked before any finance event told us this is our carrier
+        r#"{ "timestamp":"2025-08-21T11:00:00Z", "event":"Docked", "StationName":"X7J-BQG", "StationType":"FleetCarrier", "Taxi":false, "Multicrew":false, "StarSystem":"Sol", "SystemAddress":10477373803, "MarketID":123456789, "StationFaction":{ "Name":"FleetCarrier" }, "StationGovernment":"$government_Carrier;", "StationGovernment_Localised":"Private Ownership", "StationServices":[ "dock", "autodock", "commodities", "refuel" ], "StationEconomy":"$economy_Carrier;", "StationEconomy_Localised":"Private Enterprise", "StationEconomies":[ { "Name":"$economy_Carrier;", "Name_Localised":"Private Enterprise", "Proportion":1.000000 } ], "DistFromStarLS":490.0, "LandingPads":{ "Small":4, "Medium":4, "Large":8 } }"#,
+        r#"{ "timestamp":"2025-08-21T11:01:00Z", "event":"RefuelAll", "Cost":1250, "Amount":25.0 }"#,
+        r#"{ "timestamp":"2025-08-21T11:02:00Z", "event":"MarketBuy", "MarketID":123456789, "Type":"tritium", "Count":20, "BuyPrice":50000, "TotalCost":1000000 }"#,
+        r#"{ "timestamp":"2025-08-21T11:38:00Z", "event":"CarrierFinance", "CarrierID":123456789, "CarrierType":"FleetCarrier", "CarrierBalance":100000000, "ReserveBalance":50000000, "AvailableBalance":50000000, "ReservePercent":50, "TaxRate_rearm":25, "TaxRate_refuel":25, "TaxRate_repair":25 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let ledgers = CarrierLedgers::new(&lines);
+    let ledger = ledgers.get(123456789).unwrap();
+    assert!(ledger.entries.is_empty());
+    assert_eq!(Credits(100000000), ledger.balance);
+    // the first balance is the starting point, not income
+    assert_eq!(Credits(0), ledger.other_income());
+}
+
+#[test]
+fn test_carrier_ledger_expenses() {
+    let json = [
+        r#"{ "timestamp":"2025-08-21T10:38:00Z", "event":"CarrierFinance", "CarrierID":123456789, "CarrierType":"FleetCarrier", "CarrierBalance":10000000, "ReserveBalance":0, "AvailableBalance":10000000, "ReservePercent":0 }"#,
+        r#"{ "timestamp":"2025-08-21T10:43:00Z", "event":"CarrierTradeOrder", "CarrierID":123456789, "BlackMarket":false, "Commodity":"tritium", "PurchaseOrder":1000, "Price":50000 }"#,
+        r#"{ "timestamp":"2025-08-21T10:44:00Z", "event":"CarrierTradeOrder", "CarrierID":123456789, "BlackMarket":false, "Commodity":"tritium", "CancelTrade":true }"#,
+        r#"{ "timestamp":"2025-08-21T11:00:00Z", "event":"Docked", "StationName":"X7J-BQG", "StationType":"FleetCarrier", "Taxi":false, "Multicrew":false, "StarSystem":"Sol", "SystemAddress":10477373803, "MarketID":123456789, "StationFaction":{ "Name":"FleetCarrier" }, "StationGovernment":"$government_Carrier;", "StationGovernment_Localised":"Private Ownership", "StationServices":[ "dock", "autodock", "commodities", "refuel" ], "StationEconomy":"$economy_Carrier;", "StationEconomy_Localised":"Private Enterprise", "StationEconomies":[ { "Name":"$economy_Carrier;", "Name_Localised":"Private Enterprise", "Proportion":1.000000 } ], "DistFromStarLS":490.0, "LandingPads":{ "Small":4, "Medium":4, "Large":8 } }"#,
+        r#"{ "timestamp":"2025-08-21T11:05:00Z", "event":"MarketSell", "MarketID":123456789, "Type":"tritium", "Count":20, "SellPrice":50000, "TotalSale":1000000, "AvgPricePaid":45000 }"#,
+        // upkeep taken, more than the balance
+        r#"{ "timestamp":"2025-08-28T10:38:49Z", "event":"CarrierFinance", "CarrierID":123456789, "CarrierType":"FleetCarrier", "CarrierBalance":-1000000, "ReserveBalance":0, "AvailableBalance":0, "ReservePercent":0 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let ledgers = CarrierLedgers::new(&lines);
+    let ledger = ledgers.get(123456789).unwrap();
+    assert!(ledger.trade_orders.is_empty());
+    assert_eq!(Credits(0), ledger.committed_to_purchase_orders());
+    // the carrier paid for what we sold it
+    assert_eq!(Credits(-1000000), ledger.market_income());
+    assert_eq!(
+        Credits(-10000000),
+        ledger.total(CarrierLedgerEntryKind::Expenses)
+    );
+    assert_eq!(0, ledger.weeks_until_decommission());
+}
@@ -1,10 +1,21 @@
 use crate::EDString;
 use ed_parse_log_files_macros::{Extractable, testcase};
 use serde::{Deserialize, Serialize};
-use strum::Display;
+use strum::{Display, EnumIter, IntoEnumIterator};
 
 #[derive(
-    Serialize, Deserialize, Clone, Debug, Copy, Display, Hash, Eq, PartialEq, Ord, PartialOrd,
+    Serialize,
+    Deserialize,
+    Clone,
+    Debug,
+    Copy,
+    Display,
+    Hash,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    EnumIter,
 )]
 pub enum CodexNames {
     //
@@ -1397,8 +1408,479 @@ pub enum CodexCategory {
     Xenological,
 }
 
+impl CodexNames {
+    pub fn sub_category(&self) -> CodexSubCategory {
+        use CodexNames::*;
+
+        match self {
+            SupermassiveBlackHole
+            | BlackHole
+            | NeutronStar
+            | AeBeTypeStar
+            | ATypeStar
+            | ATypeGiant
+            | BTypes
+            | BTypeGiant
+            | CTypeGiant
+            | CNTypeGiant
+            | CJTypeGiant
+            | DTypeStar
+            | DATypeStar
+            | DABTypeStar
+            | DAZTypeStar
+            | DBTypeStar
+            | DBVTypeStar
+            | DCTypeStar
+            | DCVTypeStar
+            | DQTypeStar
+            | FTypeGiant
+            | FTypeStar
+            | GTypeStar
+            | GTypeGiant
+            | KTypeStar
+            | KTypeGiant
+            | LTypeStar
+            | MTypeGiant
+            | MTypeStar
+            | MSTypeGiant
+            | OTypeStars
+            | STypeGiant
+            | TTauriStar
+            | TTypeStar
+            | WTypeStar
+            | WOTypeStar
+            | YTypeStar => CodexSubCategory::Stars,
+            Terraformable
+            | TerraformableRockyNoAtmos
+            | TerraformableHighMetalContentNoAtmos
+            | TerraformableHighMetalContent
+            | TerraformableWaterWorlds
+            | EarthLikePlanet
+            | StandardAmmoniaWorlds
+            | StandardIceNoAtmos
+            | StandardRockyIce
+            | StandardTerIce
+            | StandardRockyIceNoAtmos
+            | StandardTerRockyIce
+            | StandardRockyNoAtmos
+            | StandardTerRocky
+            | StandardTerHighMetalContent
+            | StandardTerMetalRichContent
+            | StandardMetalRichNoAtmos
+            | StandardHighMetalContentNoAtmos
+            | StandardTerMetalRich
+            | StandardWaterWorlds => CodexSubCategory::TerrestrialPlanets,
+            StandardGiantWithAmmoniaLife
+            | StandardGiantWithWaterLife
+            | StandardHeliumRich
+            | StandardSudarskyClassI
+            | StandardSudarskyClassII
+            | StandardSudarskyClassIII
+            | StandardSudarskyClassIV
+            | StandardSudarskyClassV
+            | StandardWaterGiant => CodexSubCategory::GasGiants,
+            SilicateVapourGasVent
+            | SilicateMagmaLavaSpout
+            | CarbonDioxideIceGeyser
+            | WaterIceGeyser
+            | WaterIceFumarole
+            | NitrogenIceGeyser
+            | SulphurDioxideGasVent
+            | CarbonDioxideIceFumarole
+            | MethaneIceFumarole
+            | NitrogenIceFumarole
+            | MethaneIceGeyser
+            | AmmoniaIceGeyser
+            | IronMagmaLavaSpout
+            | AmmoniaIceFumarole
+            | FumaroleSilicateVapour
+            | FumaroleSulphurDioxide => CodexSubCategory::GeologyAndAnomalies,
+            GuardianBeacon | GuardianCodex | GuardianDataTerminal => {
+                CodexSubCategory::GuardianObjects
+            }
+            ThargoidBarnacleBarbs | CommonThargoidBarnacle | ThargoidInterceptorBasilisk => {
+                CodexSubCategory::ThargoidObjects
+            }
+            RubicundumLagrangeCloud
+            | RoseumLagrangeCloud
+            | CaeruleumLagrangeCloud
+            | ProtoLagrangeCloud
+            | CroceumLagrangeCloud
+            | FlavumMetallicCrystals
+            | PrasinumMetallicCrystals
+            | PurpureumMetallicCrystals
+            | RubeumMetallicCrystals
+            | SolidMineralSpheres
+            | CrystallineShards => CodexSubCategory::GeologyAndAnomalies,
+            VirideUmbrellaMollusc
+            | LuteolumReelMollusc
+            | StolonTree
+            | AleoidaSpicaLime
+            | AleoidaCoronamusLime
+            | AleoidaGravisGreen
+            | AleoidaCoronamusGreen
+            | AleoidaSpicaEmerald
+            | AleoidaArcusTeal
+            | AleoidaArcusTurquoise
+            | AleoidaSpicaGreen
+            | AleoidaSpicaTeal
+            | AleoidaSpicaTurquoise
+            | AleoidaLaminiaeTurquoise
+            | AleoidaLaminiaeLime
+            | AleoidaArcusGreen
+            | AleoidaCoronamusTeal
+            | AleoidaCoronamusTurquoise
+            | AleoidaLaminiaeGreen
+            | AleoidaGravisTurquoise
+            | AleoidaGravisTeal
+            | BacteriumAurasusIndigo
+            | BacteriumAurasusSage
+            | BacteriumAlcyoneumIndigo
+            | BacteriumVoluCyan
+            | BacteriumBullarisYellow
+            | BacteriumOmentumLime
+            | BacteriumOmentumBlue
+            | BacteriumVerrataBlue
+            | BacteriumCerbrusRed
+            | BacteriumAurasusRed
+            | BacteriumAlcyoneumRed
+            | BacteriumTelaMagenta
+            | BacteriumScopulumMulberry
+            | BacteriumInformemAquamarine
+            | BacteriumInformemLime
+            | BacteriumVoluCobalt
+            | BacteriumBullarisGold
+            | BacteriumCerbrusYellow
+            | BacteriumAciesMagenta
+            | BacteriumOmentumAquamarine
+            | BacteriumAlcyoneumEmerald
+            | BacteriumAurasusEmerald
+            | BacteriumAurasusGreen
+            | BacteriumAciesCobalt
+            | BacteriumVesiculaOrange
+            | BacteriumVesiculaCyan
+            | BacteriumAlcyoneumSage
+            | BacteriumAlcyoneumTeal
+            | BacteriumTelaGold
+            | BacteriumInformemRed
+            | BacteriumVoluRed
+            | BacteriumInformemGold
+            | BacteriumCerbrusEmerald
+            | BacteriumTelaYellow
+            | BacteriumAurasusYellow
+            | BacteriumAurasusTeal
+            | BacteriumAurasusLime
+            | BacteriumAciesWhite
+            | BacteriumAlcyoneumYellow
+            | BacteriumAciesCyan
+            | BacteriumAciesLime
+            | BacteriumAciesAquamarine
+            | BacteriumVesiculaRed
+            | BacteriumVesiculaGold
+            | BacteriumVesiculaMulberry
+            | BacteriumVesiculaLime
+            | BacteriumAlcyoneumLime
+            | BacteriumAlcyoneumGreen
+            | BacteriumTelaCobalt
+            | BacteriumInformemYellow
+            | BacteriumInformemCobalt
+            | BacteriumBullarisCobalt
+            | BacteriumBullarisAquamarine
+            | BacteriumBullarisLime
+            | BacteriumBullarisRed
+            | BacteriumOmentumRed
+            | BacteriumCerbrusIndigo
+            | BacteriumCerbrusGreen
+            | BacteriumTelaOrange
+            | BacteriumTelaGreen
+            | BacteriumCerbrusLime
+            | BacteriumCerbrusTeal
+            | BacteriumCerbrusSage
+            | AureumBrainTree
+            | RoseumBrainTree
+            | GypseeumBrainTree
+            | LividumBrainTree
+            | CactoidaPeperatisSage
+            | CactoidaPeperatisYellow
+            | CactoidaPeperatisGreen
+            | CactoidaPeperatisOrange
+            | CactoidaPullulantaGreen
+            | CactoidaCortexumTeal
+            | CactoidaCortexumYellow
+            | CactoidaCortexumMauve
+            | CactoidaLapisGreen
+            | CactoidaLapisYellow
+            | CactoidaLapisTeal
+            | CactoidaLapisMauve
+            | CactoidaLapisOrange
+            | CactoidaVermisTeal
+            | CactoidaVermisMauve
+            | CactoidaPullulantaYellow
+            | CactoidaPeperatisTeal
+            | CactoidaPeperatisMauve
+            | CactoidaPeperatisAmethyst
+            | ConchaAureolasTeal
+            | ConchaAureolasRed
+            | ConchaAureolasGrey
+            | ConchaRenibusMulberry
+            | ConchaLabiataGrey
+            | ConchaRenibusRed
+            | ConchaRenibusAquamarine
+            | ConchaRenibusWhite
+            | ConchaAureolasTurquoise
+            | ConchaAureolasOrange
+            | ConchaRenibusPeach
+            | ConchaRenibusBlue
+            | ConchaLabiataTeal
+            | ConchaLabiataTurquoise
+            | ConchaLabiataRed
+            | ClypeusLacrimamGrey
+            | ClypeusMargaritusOrange
+            | ClypeusMargaritusMauve
+            | ClypeusMargaritusTurquoise
+            | ClypeusMargaritusGrey
+            | ClypeusLacrimamTurquoise
+            | ClypeusLacrimamAmethyst
+            | ClypeusSpeculumiAmethyst
+            | ClypeusSpeculumiMauve
+            | ClypeusMargaritusTeal
+            | ClypeusMargaritusAmethyst
+            | ElectricaePlumaBlue
+            | ElectricaeRadialemCyan
+            | ElectricaePlumaMulberry
+            | ElectricaePlumaCyan
+            | ElectricaeRadialemBlue
+            | ElectricaeRadialemAquamarine
+            | ElectricaeRadialemMagenta
+            | FonticuluaCampestrisSage
+            | FonticuluaSegmentatusEmerald
+            | FonticuluaUpupamAmethyst
+            | FonticuluaUpupamOrange
+            | FonticuluaDigitosMauve
+            | FonticuluaLapidaTeal
+            | FonticuluaLapidaMauve
+            | FonticuluaLapidaRed
+            | FonticuluaCampestrisMauve
+            | FonticuluaCampestrisGreen
+            | FonticuluaDigitosEmerald
+            | FonticuluaSegmentatusAmethyst
+            | FonticuluaCampestrisYellow
+            | FonticuluaCampestrisEmerald
+            | FonticuluaUpupamTeal
+            | FonticuluaUpupamEmerald
+            | FonticuluaUpupamYellow
+            | FonticuluaCampestrisTeal
+            | FonticuluaCampestrisAmethyst
+            | FonticuluaCampestrisOrange
+            | FonticuluaLapidaEmerald
+            | FonticuluaLapidaAmethyst
+            | FonticuluaDigitosTeal
+            | FonticuluaDigitosAmethyst
+            | FumerolaCarbosisOrange
+            | FumerolaCarbosisYellow
+            | FumerolaExtremusPeach
+            | FumerolaNitrisRed
+            | FumerolaExtremusWhite
+            | FumerolaNitrisPeach
+            | FumerolaCarbosisCobalt
+            | FumerolaAquatisCobalt
+            | FungoidaBullarumRed
+            | FungoidaGelataGreen
+            | FungoidaSetisisWhite
+            | FungoidaBullarumOrange
+            | FungoidaBullarumMulberry
+            | FungoidaBullarumMagenta
+            | FungoidaBullarumGold
+            | FungoidaGelataLime
+            | FungoidaGelataOrange
+            | FungoidaGelataMulberry
+            | FungoidaStabitisWhite
+            | FungoidaGelataCyan
+            | FungoidaSetisisPeach
+            | FungoidaSetisisLime
+            | FungoidaSetisisGold
+            | FungoidaSetisisYellow
+            | FungoidaStabitisMagenta
+            | FungoidaSetisisOrange
+            | FungoidaStabitisBlue
+            | FungoidaStabitisGreen
+            | FungoidaStabitisOrange
+            | FungoidaStabitisPeach
+            | FungoidaGelataRed
+            | FrutexaMetallicumRed
+            | FrutexaFlammasisGrey
+            | FrutexaFlammasisGreen
+            | FrutexaMetallicumEmerald
+            | FrutexaFlabellumEmerald
+            | FrutexaMetallicumGrey
+            | FrutexaFlabellumTeal
+            | FrutexaAcusEmerald
+            | FrutexaMetallicumGreen
+            | FrutexaMetallicumTeal
+            | FrutexaFlammasisEmerald
+            | FrutexaFlabellumGreen
+            | FrutexaAcusGreen
+            | FrutexaSponsaeEmerald
+            | OsseusFractusLime
+            | OsseusDiscusAquamarine
+            | OsseusPellebantusIndigo
+            | OsseusDiscusWhite
+            | OsseusPumiceWhite
+            | OsseusPumiceGreen
+            | OsseusPumiceGold
+            | OsseusFractusEmerald
+            | OsseusFractusTurquoise
+            | OsseusFractusGrey
+            | OsseusDiscusBlue
+            | OsseusDiscusRed
+            | OsseusDiscusLime
+            | OsseusDiscusPeach
+            | OsseusSpiralisLime
+            | OsseusSpiralisEmerald
+            | OsseusSpiralisGrey
+            | OsseusSpiralisIndigo
+            | OsseusSpiralisTurquoise
+            | OsseusFractusIndigo
+            | OsseusPumiceLime
+            | OsseusPumiceYellow
+            | OsseusCornibusLime
+            | OsseusCornibusTurquoise
+            | OsseusPellebantusTurquoise
+            | OsseusPellebantusGrey
+            | ReceptaUmbruxMaroon
+            | ReceptaDeltahedronixOrange
+            | ReceptaUmbruxRed
+            | ReceptaConditivusLime
+            | ReceptaUmbruxMauve
+            | ReceptaUmbruxSage
+            | ReceptaConditivusAquamarine
+            | ReceptaConditivusGreen
+            | RadicoidaUnica
+            | RubeumBioluminescentAnemone
+            | StratumLaminamusLime
+            | StratumLimaxusLime
+            | StratumLimaxusGreen
+            | StratumFrigusEmerald
+            | StratumAraneamusEmerald
+            | StratumFrigusGreen
+            | StratumTectonicasGrey
+            | StratumExcutitusEmerald
+            | StratumExcutitusLime
+            | StratumPaleasEmerald
+            | StratumPaleasLime
+            | StratumPaleasTurquoise
+            | StratumPaleasGreen
+            | StratumCucumisisEmerald
+            | StratumCucumisisLime
+            | StratumTectonicasEmerald
+            | StratumTectonicasLime
+            | StratumTectonicasTurquoise
+            | StratumLaminamusEmerald
+            | StratumTectonicasGreen
+            | StratumLimaxusEmerald
+            | TubusSororibusMauve
+            | TubusSororibusTeal
+            | TubusRosariumIndigo
+            | TubusCavasGrey
+            | TubusCavasMaroon
+            | TubusRosariumRed
+            | TubusConiferGrey
+            | TubusConiferRed
+            | TubusConiferIndigo
+            | TubusConiferTeal
+            | TubusSororibusMaroon
+            | TubusCavasIndigo
+            | TubusCavasRed
+            | TubusRosariumGrey
+            | TubusRosariumMaroon
+            | TubusRosariumTurquoise
+            | TubusCompagibusGrey
+            | TubusCompagibusRed
+            | TubusCompagibusMaroon
+            | TubusCompagibusIndigo
+            | TussockAlbataEmerald
+            | TussockPropagitoTeal
+            | TussockVentusaGreen
+            | TussockSerratiLime
+            | TussockPropagitoSage
+            | TussockCapillumGreen
+            | TussockCatenaTeal
+            | TussockPropagitoEmerald
+            | TussockCapillumLime
+            | TussockCatenaYellow
+            | TussockPennataYellow
+            | TussockCultroEmerald
+            | TussockCatenaEmerald
+            | TussockCatenaGreen
+            | TussockSerratiYellow
+            | TussockStigmasisYellow
+            | TussockVirgamEmerald
+            | TussockCapillumYellow
+            | TussockDivisaTeal
+            | TussockPennatisGreen
+            | TussockVirgamGreen
+            | TussockVirgamSage
+            | TussockVentusaYellow
+            | TussockIgnisLime
+            | TussockCultroLime
+            | TussockCultroGreen
+            | TussockCultroSage
+            | TussockCatenaLime
+            | TussockCatenaSage
+            | TussockPennatisSage
+            | TussockAlbataYellow
+            | TussockAlbataLime
+            | TussockAlbataGreen
+            | TussockPropagitoGreen
+            | TussockDivisaYellow
+            | TussockDivisaGreen
+            | TussockDivisaEmerald
+            | TussockCaputusLime
+            | TussockTriticumLime
+            | TussockVirgamLime
+            | TussockTriticumYellow
+            | TussockCultroYellow
+            | TussockCaputusYellow
+            | TussockIgnisEmerald
+            | TussockCaputusGreen
+            | TussockIgnisGreen
+            | TussockSerratiGreen
+            | TussockVentusaLime
+            | TussockIgnisYellow
+            | TussockPropagitoYellow
+            | TussockPropagitoLime
+            | TussockCapillumEmerald => CodexSubCategory::OrganicStructures,
+        }
+    }
+
+    pub fn category(&self) -> CodexCategory {
+        match self.sub_category() {
+            CodexSubCategory::Stars
+            | CodexSubCategory::TerrestrialPlanets
+            | CodexSubCategory::GasGiants => CodexCategory::StellarBodies,
+            CodexSubCategory::OrganicStructures | CodexSubCategory::GeologyAndAnomalies => {
+                CodexCategory::Biology
+            }
+            CodexSubCategory::ThargoidObjects | CodexSubCategory::GuardianObjects => {
+                CodexCategory::Xenological
+            }
+        }
+    }
+}
+
 #[derive(
-    Serialize, Deserialize, Clone, Debug, Copy, Display, Eq, PartialEq, Hash, Ord, PartialOrd,
+    Serialize,
+    Deserialize,
+    Clone,
+    Debug,
+    Copy,
+    Display,
+    Eq,
+    PartialEq,
+    Hash,
+    Ord,
+    PartialOrd,
+    EnumIter,
 )]
 pub enum CodexRegion {
     #[serde(rename = "$Codex_RegionName_1;")]
@@ -1529,6 +2011,13 @@ pub enum CodexRegion {
     TheVoid,
 }
 
+impl CodexRegion {
+    /// region from its number in `$Codex_RegionName_<id>;`
+    pub fn from_id(id: u8) -> Option<CodexRegion> {
+        CodexRegion::iter().nth(usize::from(id).checked_sub(1)?)
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 #[testcase({ "timestamp":"2022-08-26T19:09:04Z", "event":"CodexEntry", "EntryID":1100502, "Name":"$Codex_Ent_G_TypeGiant_Name;", 
@@ -0,0 +1,250 @@
+use crate::{
+    EDString,
+    codex::{CodexCategory, CodexNames, CodexRegion, CodexSubCategory},
+    galaxy::{KnownSystems, distance},
+    log_line::{EDLogEvent, EDLogLine},
+};
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use strum::IntoEnumIterator;
+
+#[derive(Clone, Debug)]
+pub struct CodexSighting {
+    pub timestamp: DateTime<Utc>,
+    pub region: CodexRegion,
+    pub system: EDString,
+    pub system_address: u64,
+    pub body_id: Option<u64>,
+    pub is_new_entry: bool,
+    pub voucher_amount: Option<u32>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Completion {
+    pub logged: usize,
+    pub total: usize,
+}
+
+impl Completion {
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.logged as f64 / self.total as f64
+    }
+}
+
+/// Codex entries we have logged, per region
+#[derive(Clone, Debug, Default)]
+pub struct CodexTracker {
+    // first time an entry was logged in a region
+    logged: BTreeMap<(CodexRegion, CodexNames), CodexSighting>,
+    sightings: BTreeMap<CodexNames, Vec<CodexSighting>>,
+    pub systems: KnownSystems,
+}
+
+impl CodexTracker {
+    /// build the tracker from log lines sorted by timestamp
+    pub fn new(lines: &[EDLogLine]) -> CodexTracker {
+        let mut tracker = CodexTracker::default();
+        for line in lines {
+            tracker.add_line(line);
+        }
+        tracker
+    }
+
+    pub fn add_line(&mut self, line: &EDLogLine) {
+        self.systems.add_line(line);
+        let EDLogEvent::CodexEntry(entry) = line.event() else {
+            return;
+        };
+        let sighting = CodexSighting {
+            timestamp: *line.timestamp(),
+            region: entry.region,
+            system: entry.system.clone(),
+            system_address: entry.system_address,
+            body_id: entry.body_id,
+            is_new_entry: entry.is_new_entry.unwrap_or(false),
+            voucher_amount: entry.voucher_amount,
+        };
+        self.logged
+            .entry((entry.region, entry.name))
+            .or_insert_with(|| sighting.clone());
+        self.sightings.entry(entry.name).or_default().push(sighting);
+    }
+
+    pub fn logged(&self, region: CodexRegion, name: CodexNames) -> Option<&CodexSighting> {
+        self.logged.get(&(region, name))
+    }
+
+    /// all sightings of an entry, in any region
+    pub fn sightings(&self, name: CodexNames) -> &[CodexSighting] {
+        self.sightings.get(&name).map_or(&[], |s| s.as_slice())
+    }
+
+    pub fn missing(&self, region: CodexRegion) -> Vec<CodexNames> {
+        CodexNames::iter()
+            .filter(|name| !self.logged.contains_key(&(region, *name)))
+            .collect()
+    }
+
+    pub fn completion(&self, region: CodexRegion) -> Completion {
+        Completion {
+            logged: self.logged.keys().filter(|(r, _)| *r == region).count(),
+            total: CodexNames::iter().count(),
+        }
+    }
+
+    pub fn completion_by_category(
+        &self,
+        region: CodexRegion,
+    ) -> BTreeMap<CodexCategory, Completion> {
+        let mut result: BTreeMap<CodexCategory, Completion> = BTreeMap::new();
+        for name in CodexNames::iter() {
+            let completion = result.entry(name.category()).or_default();
+            completion.total += 1;
+            completion.logged += self.logged.contains_key(&(region, name)) as usize;
+        }
+        result
+    }
+
+    pub fn completion_by_sub_category(
+        &self,
+        region: CodexRegion,
+    ) -> BTreeMap<CodexSubCategory, Completion> {
+        let mut result: BTreeMap<CodexSubCategory, Completion> = BTreeMap::new();
+        for name in CodexNames::iter() {
+            let completion = result.entry(name.sub_category()).or_default();
+            completion.total += 1;
+            completion.logged += self.logged.contains_key(&(region, name)) as usize;
+        }
+        result
+    }
+
+    /// for each entry missing in `region`, the nearest system where we logged it before, nearest first
+    pub fn nearest_sightings(
+        &self,
+        region: CodexRegion,
+        from: &[f64; 3],
+    ) -> Vec<(CodexNames, &CodexSighting, f64)> {
+        let mut result: Vec<_> = self
+            .missing(region)
+            .into_iter()
+            .filter_map(|name| {
+                self.sightings(name)
+                    .iter()
+                    .filter_map(|s| {
+                        let position = self.systems.position(s.system_address)?;
+                        Some((name, s, distance(from, &position)))
+                    })
+                    .min_by(|a, b| a.2.total_cmp(&b.2))
+            })
+            .collect();
+        result.sort_by(|a, b| a.2.total_cmp(&b.2));
+        result
+    }
+}
+
+#[test]
+fn test_codex_tracker() {
+    let json = [
+        r#"{ "timestamp":"2022-08-26T19:00:00Z", "event":"FSDJump", "StarSystem":"Kholul", "SystemAddress":2415659059547, "StarPos":[-46.93750,-7.84375,-152.18750], "SystemAllegiance":"", "SystemEconomy":"$economy_None;", "SystemEconomy_Localised":"None", "SystemGovernment":"$government_None;", "SystemGovernment_Localised":"None", "SystemSecurity":"$GAlAXY_MAP_INFO_state_anarchy;", "SystemSecurity_Localised":"Anarchy", "Population":0, "JumpDist":13.790, "FuelUsed":0.371038, "FuelLevel":5.628962 }"#,
+        r#"{ "timestamp":"2022-08-26T19:09:04Z", "event":"CodexEntry", "EntryID":1100502, "Name":"$Codex_Ent_G_TypeGiant_Name;", "Name_Localised":"G Type Giant", "SubCategory":"$Codex_SubCategory_Stars;", "SubCategory_Localised":"Stars", "Category":"$Codex_Category_StellarBodies;", "Category_Localised":"Astronomical Bodies", "Region":"$Codex_RegionName_18;", "Region_Localised":"Inner Orion Spur", "System":"Kholul", "SystemAddress":2415659059547, "IsNewEntry":true }"#,
+        r#"{ "timestamp":"2026-01-26T15:45:23Z", "event":"CodexEntry", "EntryID":1101001, "Name":"$Codex_Ent_TTS_Type_Name;", "Name_Localised":"T Tauri Star", "SubCategory":"$Codex_SubCategory_Stars;", "SubCategory_Localised":"Stars", "Category":"$Codex_Category_StellarBodies;", "Category_Localised":"Astronomical Bodies", "Region":"$Codex_RegionName_10;", "Region_Localised":"Norma Expanse", "System":"Skauduae QM-T c17-4", "SystemAddress":1185175214746, "BodyID":0, "IsNewEntry":true }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let tracker = CodexTracker::new(&lines);
+    assert_eq!(
+        "Kholul",
+        tracker
+            .logged(CodexRegion::InnerOrionSpur, CodexNames::GTypeGiant)
+            .unwrap()
+            .system
+            .as_str()
+    );
+    let completion = tracker.completion(CodexRegion::InnerOrionSpur);
+    assert_eq!(1, completion.logged);
+    assert_eq!(
+        CodexNames::iter().count() - 1,
+        tracker.missing(CodexRegion::InnerOrionSpur).len()
+    );
+    assert_eq!(
+        1,
+        tracker.completion_by_sub_category(CodexRegion::InnerOrionSpur)[&CodexSubCategory::Stars]
+            .logged
+    );
+    assert_eq!(
+        0,
+        tracker.completion_by_category(CodexRegion::InnerOrionSpur)[&CodexCategory::Biology].logged
+    );
+
+    // the G type giant was seen in a system we know the position of, the T Tauri star was not
+    let nearest = tracker.nearest_sightings(CodexRegion::NormaExpanse, &[0.0, 0.0, 0.0]);
+    assert_eq!(1, nearest.len());
+    assert_eq!(CodexNames::GTypeGiant, nearest[0].0);
+    assert!((nearest[0].2 - 159.454).abs() < 0.001);
+}
+
+#[test]
+fn test_codex_anomalies_are_not_organic() {
+    for name in [
+        CodexNames::RoseumLagrangeCloud,
+        CodexNames::ProtoLagrangeCloud,
+        CodexNames::FlavumMetallicCrystals,
+        CodexNames::SolidMineralSpheres,
+        CodexNames::CrystallineShards,
+    ] {
+        assert_eq!(CodexSubCategory::GeologyAndAnomalies, name.sub_category());
+    }
+    assert_eq!(
+        CodexSubCategory::OrganicStructures,
+        CodexNames::StolonTree.sub_category()
+    );
+    assert_eq!(
+        CodexSubCategory::OrganicStructures,
+        CodexNames::TussockCapillumEmerald.sub_category()
+    );
+}
+
+#[test]
+fn test_codex_tracker_empty() {
+    let tracker = CodexTracker::new(&[]);
+    let completion = tracker.completion(CodexRegion::InnerOrionSpur);
+    assert_eq!(0, completion.logged);
+    assert_eq!(0.0, completion.fraction());
+    assert_eq!(0.0, Completion::default().fraction());
+    assert!(tracker.sightings(CodexNames::GTypeGiant).is_empty());
+    assert!(
+        tracker
+            .nearest_sightings(CodexRegion::InnerOrionSpur, &[0.0, 0.0, 0.0])
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_codex_tracker_repeat_sightings() {
+    let json = [
+        r#"{ "timestamp":"2022-08-26T19:09:04Z", "event":"CodexEntry", "EntryID":1100502, "Name":"$Codex_Ent_G_TypeGiant_Name;", "Name_Localised":"G Type Giant", "SubCategory":"$Codex_SubCategory_Stars;", "SubCategory_Localised":"Stars", "Category":"$Codex_Category_StellarBodies;", "Category_Localised":"Astronomical Bodies", "Region":"$Codex_RegionName_18;", "Region_Localised":"Inner Orion Spur", "System":"Kholul", "SystemAddress":2415659059547, "IsNewEntry":true }"#,
+        r#"{ "timestamp":"2022-09-01T19:09:04Z", "event":"CodexEntry", "EntryID":1100502, "Name":"$Codex_Ent_G_TypeGiant_Name;", "Name_Localised":"G Type Giant", "SubCategory":"$Codex_SubCategory_Stars;", "SubCategory_Localised":"Stars", "Category":"$Codex_Category_StellarBodies;", "Category_Localised":"Astronomical Bodies", "Region":"$Codex_RegionName_18;", "Region_Localised":"Inner Orion Spur", "System":"Sol", "SystemAddress":10477373803 }"#,
+        // the jumps come after the entries, positions are looked up when asked
+        r#"{ "timestamp":"2022-09-02T19:00:00Z", "event":"FSDJump", "StarSystem":"Kholul", "SystemAddress":2415659059547, "StarPos":[-46.93750,-7.84375,-152.18750], "SystemAllegiance":"", "SystemEconomy":"$economy_None;", "SystemEconomy_Localised":"None", "SystemGovernment":"$government_None;", "SystemGovernment_Localised":"None", "SystemSecurity":"$GAlAXY_MAP_INFO_state_anarchy;", "SystemSecurity_Localised":"Anarchy", "Population":0, "JumpDist":13.790, "FuelUsed":0.371038, "FuelLevel":5.628962 }"#,
+        r#"{ "timestamp":"2022-09-02T19:55:23Z", "event":"FSDJump", "StarSystem":"Sol", "SystemAddress":10477373803, "StarPos":[0.0,0.0,0.0], "SystemAllegiance":"Federation", "SystemEconomy":"$economy_Refinery;", "SystemEconomy_Localised":"Refinery", "SystemGovernment":"$government_Democracy;", "SystemGovernment_Localised":"Democracy", "SystemSecurity":"$SYSTEM_SECURITY_high;", "SystemSecurity_Localised":"High Security", "Population":22780919531, "JumpDist":13.790, "FuelUsed":0.371038, "FuelLevel":5.628962 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let tracker = CodexTracker::new(&lines);
+    // the first sighting in a region is the one logged
+    let logged = tracker
+        .logged(CodexRegion::InnerOrionSpur, CodexNames::GTypeGiant)
+        .unwrap();
+    assert_eq!("Kholul", logged.system.as_str());
+    assert!(logged.is_new_entry);
+    assert_eq!(2, tracker.sightings(CodexNames::GTypeGiant).len());
+    assert_eq!(1, tracker.completion(CodexRegion::InnerOrionSpur).logged);
+
+    let nearest = tracker.nearest_sightings(CodexRegion::NormaExpanse, &crate::galaxy::SOL);
+    assert_eq!(1, nearest.len());
+    assert_eq!("Sol", nearest[0].1.system.as_str());
+    assert_eq!(0.0, nearest[0].2);
+}
@@ -0,0 +1,315 @@
+use crate::{
+    EDString,
+    common_types::Credits,
+    log_line::{EDLogEvent, EDLogLine},
+    market::{MarketItemType, RequiredResource},
+};
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+
+/// state of a construction depot when docked at it
+#[derive(Clone, Debug)]
+pub struct DepotSnapshot {
+    pub timestamp: DateTime<Utc>,
+    pub progress: f64,
+    pub complete: bool,
+    pub failed: bool,
+    pub resources: Vec<RequiredResource>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CommodityShare {
+    pub required: u64,
+    pub provided: u64,
+    /// delivered by us, the rest of `provided` came from others
+    pub ours: u64,
+}
+
+impl CommodityShare {
+    pub fn remaining(&self) -> u64 {
+        self.required.saturating_sub(self.provided)
+    }
+
+    pub fn others(&self) -> u64 {
+        self.provided.saturating_sub(self.ours)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ConstructionDepot {
+    pub market_id: u64,
+    pub station_name: Option<EDString>,
+    pub star_system: Option<EDString>,
+    /// oldest first
+    pub snapshots: Vec<DepotSnapshot>,
+    pub contributed: BTreeMap<MarketItemType, u64>,
+}
+
+impl ConstructionDepot {
+    pub fn latest(&self) -> Option<&DepotSnapshot> {
+        self.snapshots.last()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.latest().is_some_and(|s| s.complete)
+    }
+
+    pub fn shares(&self) -> BTreeMap<MarketItemType, CommodityShare> {
+        let mut result = BTreeMap::new();
+        for resource in self.latest().iter().flat_map(|s| &s.resources) {
+            result.insert(
+                resource.name,
+                CommodityShare {
+                    required: resource.required_amount,
+                    provided: resource.provided_amount,
+                    ours: self.contributed.get(&resource.name).copied().unwrap_or(0),
+                },
+            );
+        }
+        result
+    }
+
+    /// tons still to deliver by commodity
+    pub fn remaining(&self) -> BTreeMap<MarketItemType, u64> {
+        self.shares()
+            .into_iter()
+            .filter(|(_, share)| share.remaining() > 0)
+            .map(|(commodity, share)| (commodity, share.remaining()))
+            .collect()
+    }
+
+    pub fn remaining_tonnage(&self) -> u64 {
+        self.remaining().values().sum()
+    }
+
+    /// fraction of the delivered tonnage that came from us
+    pub fn our_share(&self) -> f64 {
+        let shares = self.shares();
+        let provided: u64 = shares.values().map(|s| s.provided).sum();
+        if provided == 0 {
+            return 0.0;
+        }
+        shares.values().map(|s| s.ours.min(s.provided)).sum::<u64>() as f64 / provided as f64
+    }
+
+    /// trips needed to deliver what remains with `capacity` tons of cargo space
+    pub fn trips(&self, capacity: u64) -> Option<u64> {
+        (capacity > 0).then(|| self.remaining_tonnage().div_ceil(capacity))
+    }
+}
+
+/// last market seen with a commodity in stock
+#[derive(Clone, Debug)]
+pub struct MarketSighting {
+    pub timestamp: DateTime<Utc>,
+    pub market_id: u64,
+    pub station_name: EDString,
+    pub star_system: EDString,
+    pub stock: u64,
+    pub buy_price: Credits,
+}
+
+/// Colonisation construction depots with haul planning
+#[derive(Clone, Debug, Default)]
+pub struct ColonisationTracker {
+    depots: BTreeMap<u64, ConstructionDepot>,
+    sightings: HashMap<MarketItemType, MarketSighting>,
+    docked: Option<(u64, EDString, EDString)>,
+    /// cargo capacity of the current ship
+    pub cargo_capacity: Option<u64>,
+    /// free space of our fleet carrier
+    pub carrier_free_space: Option<u64>,
+}
+
+impl ColonisationTracker {
+    /// build the tracker from log lines sorted by timestamp
+    pub fn new(lines: &[EDLogLine]) -> ColonisationTracker {
+        let mut tracker = ColonisationTracker::default();
+        for line in lines {
+            tracker.add_line(line);
+        }
+        tracker
+    }
+
+    fn depot(&mut self, market_id: u64) -> &mut ConstructionDepot {
+        let docked = self.docked.as_ref().filter(|(id, _, _)| *id == market_id);
+        self.depots
+            .entry(market_id)
+            .or_insert_with(|| ConstructionDepot {
+                market_id,
+                station_name: docked.map(|(_, station, _)| station.clone()),
+                star_system: docked.map(|(_, _, system)| system.clone()),
+                snapshots: Vec::new(),
+                contributed: BTreeMap::new(),
+            })
+    }
+
+    pub fn add_line(&mut self, line: &EDLogLine) {
+        let timestamp = *line.timestamp();
+        match line.event() {
+            EDLogEvent::Docked(docked) => {
+                self.docked = docked.station_identification.market_id.map(|id| {
+                    (
+                        id,
+                        docked.station_identification.station_name.clone(),
+                        docked.star_system.clone(),
+                    )
+                });
+            }
+            EDLogEvent::Undocked(_) => self.docked = None,
+            EDLogEvent::Loadout(loadout) => {
+                self.cargo_capacity = loadout.loadout_stats.as_ref().map(|s| s.cargo_capacity);
+            }
+            EDLogEvent::CarrierStats(stats) => {
+                self.carrier_free_space = Some(stats.space_usage.free_space);
+            }
+            EDLogEvent::ColonisationConstructionDepot(depot) => {
+                self.depot(depot.market_id).snapshots.push(DepotSnapshot {
+                    timestamp,
+                    progress: depot.construction_progress,
+                    complete: depot.construction_complete,
+                    failed: depot.construction_failed,
+                    resources: depot.resources_required.clone(),
+                });
+            }
+            EDLogEvent::ColonisationContribution(contribution) => {
+                let depot = self.depot(contribution.market_id);
+                for resource in &contribution.contributions {
+                    *depot.contributed.entry(resource.name).or_default() += resource.amount;
+                }
+            }
+            EDLogEvent::Market(market) => {
+                for item in market.items.iter().flatten() {
+                    if item.stock == 0 || item.buy_price.0 <= 0 {
+                        continue;
+                    }
+                    self.sightings.insert(
+                        item.market_item_name,
+                        MarketSighting {
+                            timestamp,
+                            market_id: market.market_id,
+                            station_name: market.station_name.clone(),
+                            star_system: market.star_system.clone(),
+                            stock: item.stock,
+                            buy_price: item.buy_price,
+                        },
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn get(&self, market_id: u64) -> Option<&ConstructionDepot> {
+        self.depots.get(&market_id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ConstructionDepot> {
+        self.depots.values()
+    }
+
+    pub fn in_progress(&self) -> impl Iterator<Item = &ConstructionDepot> {
+        self.depots
+            .values()
+            .filter(|d| d.latest().is_some_and(|s| !s.complete && !s.failed))
+    }
+
+    /// trips with the current ship and with the fleet carrier
+    pub fn trips(&self, market_id: u64) -> (Option<u64>, Option<u64>) {
+        let Some(depot) = self.get(market_id) else {
+            return (None, None);
+        };
+        (
+            self.cargo_capacity.and_then(|c| depot.trips(c)),
+            self.carrier_free_space.and_then(|c| depot.trips(c)),
+        )
+    }
+
+    pub fn last_seen_for_sale(&self, commodity: MarketItemType) -> Option<&MarketSighting> {
+        self.sightings.get(&commodity)
+    }
+
+    /// where each missing commodity of a depot was last seen for sale
+    pub fn where_to_buy(
+        &self,
+        market_id: u64,
+    ) -> BTreeMap<MarketItemType, Option<&MarketSighting>> {
+        self.get(market_id)
+            .map(|depot| depot.remaining())
+            .unwrap_or_default()
+            .into_keys()
+            .map(|commodity| (commodity, self.last_seen_for_sale(commodity)))
+            .collect()
+    }
+}
+
+#[test]
+fn test_colonisation_tracker() {
+    let json = [
+        r#"{ "timestamp":"2025-04-10T09:00:00Z", "event":"Market", "MarketID":128776559, "StationName":"Robardin Rock", "StationType":"AsteroidBase", "StarSystem":"Carcosa", "Items":[ { "id":128049202, "Name":"$steel_name;", "Name_Localised":"Steel", "Category":"$MARKET_category_metals;", "Category_Localised":"Metals", "BuyPrice":4500, "SellPrice":4400, "MeanPrice":5000, "StockBracket":3, "DemandBracket":0, "Stock":12000, "Demand":0, "Consumer":false, "Producer":true, "Rare":false } ] }"#,
+        r#"{ "timestamp":"2025-04-10T09:30:00Z", "event":"Loadout", "Ship":"type9", "ShipID":12, "ShipName":"", "ShipIdent":"", "HullValue":72000000, "ModulesValue":50000000, "HullHealth":1.0, "UnladenMass":1000.0, "CargoCapacity":720, "MaxJumpRange":15.0, "FuelCapacity":{ "Main":64.0, "Reserve":0.77 }, "Rebuy":6100000, "Modules":[] }"#,
+        r#"{ "timestamp":"2025-04-10T10:00:00Z", "event":"ColonisationConstructionDepot", "MarketID":3956667138, "ConstructionProgress":0.25, "ConstructionComplete":false, "ConstructionFailed":false, "ResourcesRequired":[ { "Name":"$aluminium_name;", "Name_Localised":"Aluminium", "RequiredAmount":510, "ProvidedAmount":510, "Payment":3239 }, { "Name":"$steel_name;", "Name_Localised":"Steel", "RequiredAmount":6000, "ProvidedAmount":1000, "Payment":5057 } ] }"#,
+        r#"{ "timestamp":"2025-04-10T10:01:00Z", "event":"ColonisationContribution", "MarketID":3956667138, "Contributions":[ { "Name":"$steel_name;", "Name_Localised":"Steel", "Amount":720 } ] }"#,
+        r#"{ "timestamp":"2025-04-10T10:02:00Z", "event":"ColonisationConstructionDepot", "MarketID":3956667138, "ConstructionProgress":0.3, "ConstructionComplete":false, "ConstructionFailed":false, "ResourcesRequired":[ { "Name":"$aluminium_name;", "Name_Localised":"Aluminium", "RequiredAmount":510, "ProvidedAmount":510, "Payment":3239 }, { "Name":"$steel_name;", "Name_Localised":"Steel", "RequiredAmount":6000, "ProvidedAmount":1720, "Payment":5057 } ] }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let tracker = ColonisationTracker::new(&lines);
+    let depot = tracker.get(3956667138).unwrap();
+    assert_eq!(2, depot.snapshots.len());
+    assert_eq!(4280, depot.remaining_tonnage());
+    let steel = depot.shares()[&MarketItemType::Steel];
+    assert_eq!(720, steel.ours);
+    assert_eq!(1000, steel.others());
+    assert!((depot.our_share() - 720.0 / 2230.0).abs() < 1e-9);
+
+    assert_eq!((Some(6), None), tracker.trips(3956667138));
+    let where_to_buy = tracker.where_to_buy(3956667138);
+    assert_eq!(1, where_to_buy.len());
+    assert_eq!(
+        "Robardin Rock",
+        where_to_buy[&MarketItemType::Steel]
+            .unwrap()
+            .station_name
+            .as_str()
+    );
+}
+
+#[test]
+fn test_colonisation_tracker_empty() {
+    let tracker = ColonisationTracker::new(&[]);
+    assert!(tracker.get(3956667138).is_none());
+    assert_eq!(0, tracker.in_progress().count());
+    assert_eq!((None, None), tracker.trips(3956667138));
+    assert!(tracker.where_to_buy(3956667138).is_empty());
+    assert!(tracker.last_seen_for_sale(MarketItemType::Steel).is_none());
+}
+
+#[test]
+fn test_colonisation_contribution_before_depot() {
+    let json = [
+        r#"{ "timestamp":"2025-04-10T10:01:00Z", "event":"ColonisationContribution", "MarketID":3956667138, "Contributions":[ { "Name":"$steel_name;", "Name_Localised":"Steel", "Amount":1000 } ] }"#,
+    ];
+    let mut lines = crate::utils::parse_lines(&json);
+
+    let tracker = ColonisationTracker::new(&lines);
+    let depot = tracker.get(3956667138).unwrap();
+    assert!(depot.latest().is_none());
+    assert!(depot.shares().is_empty());
+    assert_eq!(0.0, depot.our_share());
+    assert_eq!(Some(0), depot.trips(720));
+    assert_eq!(None, depot.trips(0));
+    assert_eq!(0, tracker.in_progress().count());
+
+    // we are credited with more than the depot has received
+    lines.extend(crate::utils::parse_lines(&[
+        r#"{ "timestamp":"2025-04-10T10:02:00Z", "event":"ColonisationConstructionDepot", "MarketID":3956667138, "ConstructionProgress":1.0, "ConstructionComplete":true, "ConstructionFailed":false, "ResourcesRequired":[ { "Name":"$steel_name;", "Name_Localised":"Steel", "RequiredAmount":720, "ProvidedAmount":720, "Payment":5057 } ] }"#,
+    ]));
+    let tracker = ColonisationTracker::new(&lines);
+    let depot = tracker.get(3956667138).unwrap();
+    assert!(depot.is_complete());
+    assert_eq!(0, depot.shares()[&MarketItemType::Steel].others());
+    assert_eq!(1.0, depot.our_share());
+    assert!(depot.remaining().is_empty());
+    assert_eq!(0, tracker.in_progress().count());
+}
@@ -0,0 +1,391 @@
+use crate::{
+    EDString,
+    common_types::Credits,
+    log_line::{EDLogEvent, EDLogLine},
+    ship_type::ShipType,
+};
+use chrono::{DateTime, Duration, Utc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+};
+
+/// combat events further apart than this start a new engagement
+pub const ENGAGEMENT_GAP: Duration = Duration::minutes(5);
+
+/// system and signal source or site where an engagement took place
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct HuntingGround {
+    pub star_system: Option<EDString>,
+    pub site: Option<EDString>,
+}
+
+/// what we killed, ships by type so internal and localised names count as one
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum KillTarget {
+    Ship(ShipType),
+    CapitalShip,
+    /// skimmers, on foot enemies and ships we have no type for
+    Other(String),
+    Unknown,
+}
+
+impl KillTarget {
+    fn from_name(name: EDString, localised: Option<EDString>) -> KillTarget {
+        match ShipType::from_name(name.as_str()) {
+            Some(ship) => KillTarget::Ship(ship),
+            None => KillTarget::Other(localised.unwrap_or(name).to_string()),
+        }
+    }
+}
+
+impl Display for KillTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KillTarget::Ship(ship) => write!(f, "{ship}"),
+            KillTarget::CapitalShip => write!(f, "Capital Ship"),
+            KillTarget::Other(name) => write!(f, "{name}"),
+            KillTarget::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Engagement {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub ground: HuntingGround,
+    pub kills: BTreeMap<KillTarget, u64>,
+    pub pvp_kills: Vec<EDString>,
+    pub bounties: Credits,
+    pub bonds: Credits,
+    /// lowest hull health seen, 1.0 is undamaged
+    pub min_hull_health: Option<f64>,
+    pub shields_lost: u64,
+    pub fighters_lost: u64,
+    pub interdicted: bool,
+    pub died: bool,
+    pub times_to_kill: Vec<Duration>,
+}
+
+impl Engagement {
+    fn new(start: DateTime<Utc>, ground: HuntingGround) -> Engagement {
+        Engagement {
+            start,
+            end: start,
+            ground,
+            kills: BTreeMap::new(),
+            pvp_kills: Vec::new(),
+            bounties: Credits(0),
+            bonds: Credits(0),
+            min_hull_health: None,
+            shields_lost: 0,
+            fighters_lost: 0,
+            interdicted: false,
+            died: false,
+            times_to_kill: Vec::new(),
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+
+    pub fn kill_count(&self) -> u64 {
+        self.kills.values().sum::<u64>() + self.pvp_kills.len() as u64
+    }
+
+    pub fn earned(&self) -> Credits {
+        self.bounties + self.bonds
+    }
+
+    pub fn hull_lost(&self) -> f64 {
+        self.min_hull_health.map(|h| 1.0 - h).unwrap_or(0.0)
+    }
+
+    pub fn average_time_to_kill(&self) -> Option<Duration> {
+        if self.times_to_kill.is_empty() {
+            return None;
+        }
+        Some(self.times_to_kill.iter().copied().sum::<Duration>() / self.times_to_kill.len() as i32)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct HuntingGroundSummary {
+    pub engagements: u64,
+    pub kills: u64,
+    pub earned: Credits,
+    pub deaths: u64,
+    pub duration: Duration,
+}
+
+impl HuntingGroundSummary {
+    pub fn earned_per_hour(&self) -> Option<Credits> {
+        let hours = self.duration.num_seconds() as f64 / 3600.0;
+        if hours <= 0.0 {
+            return None;
+        }
+        Some(Credits((self.earned.0 as f64 / hours) as i64))
+    }
+}
+
+/// Combat activity segmented into engagements
+#[derive(Clone, Debug, Default)]
+pub struct CombatLog {
+    engagements: Vec<Engagement>,
+    current: Option<Engagement>,
+    ground: HuntingGround,
+    // the locked target and when it was first locked
+    target: Option<(KillTarget, DateTime<Utc>)>,
+}
+
+impl CombatLog {
+    /// build the combat log from log lines sorted by timestamp
+    pub fn new(lines: &[EDLogLine]) -> CombatLog {
+        let mut log = CombatLog::default();
+        for line in lines {
+            log.add_line(line);
+        }
+        log
+    }
+
+    fn close(&mut self) {
+        if let Some(engagement) = self.current.take() {
+            self.engagements.push(engagement);
+        }
+        self.target = None;
+    }
+
+    fn engagement(&mut self, timestamp: DateTime<Utc>) -> &mut Engagement {
+        if self
+            .current
+            .as_ref()
+            .is_some_and(|e| timestamp - e.end > ENGAGEMENT_GAP)
+        {
+            self.close();
+        }
+        let ground = &self.ground;
+        let engagement = self
+            .current
+            .get_or_insert_with(|| Engagement::new(timestamp, ground.clone()));
+        engagement.end = timestamp;
+        engagement
+    }
+
+    fn kill(&mut self, timestamp: DateTime<Utc>, ship: Option<KillTarget>) {
+        let target = self.target.take();
+        let engagement = self.engagement(timestamp);
+        if let Some((_, since)) = &target {
+            engagement.times_to_kill.push(timestamp - *since);
+        }
+        let ship = ship
+            .or(target.map(|(ship, _)| ship))
+            .unwrap_or(KillTarget::Unknown);
+        *engagement.kills.entry(ship).or_default() += 1;
+    }
+
+    pub fn add_line(&mut self, line: &EDLogLine) {
+        let timestamp = *line.timestamp();
+        match line.event() {
+            EDLogEvent::FSDJump(jump) => {
+                self.close();
+                self.ground = HuntingGround {
+                    star_system: Some(jump.star_system.clone()),
+                    site: None,
+                };
+            }
+            EDLogEvent::Location(location) => {
+                self.close();
+                self.ground = HuntingGround {
+                    star_system: Some(location.star_system.clone()),
+                    site: None,
+                };
+            }
+            EDLogEvent::SupercruiseEntry(_) | EDLogEvent::Docked(_) => {
+                self.close();
+                self.ground.site = None;
+            }
+            EDLogEvent::USSDrop(drop) => {
+                self.ground.site = Some(drop.uss_type_localised.clone());
+            }
+            EDLogEvent::SupercruiseDestinationDrop(drop) => {
+                self.ground.site = Some(
+                    drop.dest_type_localised
+                        .clone()
+                        .unwrap_or_else(|| drop.dest_type.clone()),
+                );
+            }
+            EDLogEvent::ShipTargeted(targeted) => {
+                if !targeted.target_locked {
+                    return;
+                }
+                let Some(ship) = targeted.ship.map(KillTarget::Ship).or(targeted
+                    .ship_localised
+                    .as_ref()
+                    .map(|s| KillTarget::Other(s.to_string())))
+                else {
+                    return;
+                };
+                if self.target.as_ref().map(|(s, _)| s) != Some(&ship) {
+                    self.target = Some((ship, timestamp));
+                }
+                self.engagement(timestamp);
+            }
+            EDLogEvent::UnderAttack(_) => {
+                self.engagement(timestamp);
+            }
+            EDLogEvent::HullDamage(damage)
+                if damage.player_pilot && damage.fighter != Some(true) =>
+            {
+                let engagement = self.engagement(timestamp);
+                engagement.min_hull_health = Some(
+                    engagement
+                        .min_hull_health
+                        .map_or(damage.health, |h| h.min(damage.health)),
+                );
+            }
+            EDLogEvent::ShieldState(state) if !state.shields_up => {
+                self.engagement(timestamp).shields_lost += 1;
+            }
+            EDLogEvent::Interdicted(_) => {
+                self.engagement(timestamp).interdicted = true;
+            }
+            EDLogEvent::Interdiction(_) => {
+                self.engagement(timestamp);
+            }
+            EDLogEvent::FighterDestroyed(_) => {
+                self.engagement(timestamp).fighters_lost += 1;
+            }
+            EDLogEvent::Bounty(bounty) => {
+                let reward = bounty
+                    .total_reward
+                    .unwrap_or_else(|| bounty.rewards.iter().flatten().map(|r| r.reward).sum());
+                let ship =
+                    KillTarget::from_name(bounty.target.clone(), bounty.target_localised.clone());
+                self.kill(timestamp, Some(ship));
+                self.engagement(timestamp).bounties += reward;
+            }
+            EDLogEvent::FactionKillBond(bond) => {
+                self.kill(timestamp, None);
+                self.engagement(timestamp).bonds += bond.reward;
+            }
+            EDLogEvent::CapitalShipBond(bond) => {
+                self.kill(timestamp, Some(KillTarget::CapitalShip));
+                self.engagement(timestamp).bonds += bond.reward;
+            }
+            EDLogEvent::PVPKill(kill) => {
+                self.target = None;
+                self.engagement(timestamp)
+                    .pvp_kills
+                    .push(kill.victim.clone());
+            }
+            EDLogEvent::Died(_) => {
+                self.engagement(timestamp).died = true;
+                self.close();
+            }
+            _ => {}
+        }
+    }
+
+    /// all engagements, including the one still in progress
+    pub fn engagements(&self) -> impl Iterator<Item = &Engagement> {
+        self.engagements.iter().chain(self.current.iter())
+    }
+
+    pub fn by_hunting_ground(&self) -> HashMap<HuntingGround, HuntingGroundSummary> {
+        let mut result: HashMap<HuntingGround, HuntingGroundSummary> = HashMap::new();
+        for engagement in self.engagements() {
+            let summary = result.entry(engagement.ground.clone()).or_default();
+            summary.engagements += 1;
+            summary.kills += engagement.kill_count();
+            summary.earned += engagement.earned();
+            summary.deaths += engagement.died as u64;
+            summary.duration += engagement.duration();
+        }
+        result
+    }
+}
+
+#[test]
+fn test_combat_log() {
+    let json = [
+        r#"{ "timestamp":"2024-10-13T17:55:23Z", "event":"FSDJump", "StarSystem":"Sol", "SystemAddress":10477373803, "StarPos":[0.0,0.0,0.0], "SystemAllegiance":"Federation", "SystemEconomy":"$economy_Refinery;", "SystemEconomy_Localised":"Refinery", "SystemGovernment":"$government_Democracy;", "SystemGovernment_Localised":"Democracy", "SystemSecurity":"$SYSTEM_SECURITY_high;", "SystemSecurity_Localised":"High Security", "Population":22780919531, "JumpDist":13.790, "FuelUsed":0.371038, "FuelLevel":5.628962 }"#,
+        r#"{ "timestamp":"2024-10-13T18:00:00Z", "event":"SupercruiseDestinationDrop", "Type":"$MULTIPLAYER_SCENARIO78_TITLE;", "Type_Localised":"Resource Extraction Site [High]", "Threat":0 }"#,
+        r#"{ "timestamp":"2024-10-13T18:01:00Z", "event":"ShipTargeted", "TargetLocked":true, "Ship":"vulture", "Ship_Localised":"Vulture", "ScanStage":0 }"#,
+        r#"{ "timestamp":"2024-10-13T18:01:10Z", "event":"ShieldState", "ShieldsUp":false }"#,
+        r#"{ "timestamp":"2024-10-13T18:01:20Z", "event":"HullDamage", "Health":0.800000, "PlayerPilot":true, "Fighter":false }"#,
+        r#"{ "timestamp":"2024-10-13T18:02:00Z", "event":"Bounty", "Rewards":[ { "Faction":"Mother Gaia", "Reward":120000 } ], "Target":"vulture", "Target_Localised":"Vulture", "TotalReward":120000, "VictimFaction":"Sol Pirates" }"#,
+        r#"{ "timestamp":"2024-10-13T18:03:00Z", "event":"ShipTargeted", "TargetLocked":true, "Ship":"python", "ScanStage":0 }"#,
+        r#"{ "timestamp":"2024-10-13T18:04:00Z", "event":"Bounty", "Rewards":[ { "Faction":"Mother Gaia", "Reward":300000 } ], "Target":"python", "TotalReward":300000, "VictimFaction":"Sol Pirates" }"#,
+        r#"{ "timestamp":"2024-10-13T18:30:00Z", "event":"UnderAttack", "Target":"You" }"#,
+        r#"{ "timestamp":"2024-10-13T18:31:00Z", "event":"Died", "KillerName":"$UNKNOWN;", "KillerName_Localised":"Unknown", "KillerShip":"unknownsaucer", "KillerRank":"Elite" }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let log = CombatLog::new(&lines);
+    let engagements: Vec<&Engagement> = log.engagements().collect();
+    assert_eq!(2, engagements.len());
+
+    let first = engagements[0];
+    assert_eq!(2, first.kill_count());
+    assert_eq!(
+        Some(&1),
+        first.kills.get(&KillTarget::Ship(ShipType::Vulture))
+    );
+    assert_eq!(2, first.kills.len());
+    assert_eq!(Credits(420000), first.earned());
+    assert_eq!(1, first.shields_lost);
+    assert!((first.hull_lost() - 0.2).abs() < 1e-9);
+    assert_eq!(Some(Duration::minutes(1)), first.average_time_to_kill());
+    assert!(!first.died);
+    assert!(engagements[1].died);
+
+    let grounds = log.by_hunting_ground();
+    let res = grounds
+        .get(&HuntingGround {
+            star_system: Some("Sol".into()),
+            site: Some("Resource Extraction Site [High]".into()),
+        })
+        .unwrap();
+    assert_eq!(2, res.engagements);
+    assert_eq!(1, res.deaths);
+}
+
+#[test]
+fn test_combat_log_empty() {
+    let log = CombatLog::new(&[]);
+    assert_eq!(0, log.engagements().count());
+    assert!(log.by_hunting_ground().is_empty());
+    assert_eq!(None, HuntingGroundSummary::default().earned_per_hour());
+}
+
+#[test]
+fn test_combat_log_without_location() {
+    let json = [
+        // no FSDJump or Location yet, the hunting ground is unknown
+        r#"{ "timestamp":"2026-01-05T20:00:00Z", "event":"HullDamage", "Health":0.500000, "PlayerPilot":true, "Fighter":true }"#,
+        r#"{ "timestamp":"2026-01-05T20:01:00Z", "event":"FactionKillBond", "Reward":52500, "AwardingFaction":"CdE Corporation", "VictimFaction":"Sothis Crimson Bridge Corp." }"#,
+        // more than ENGAGEMENT_GAP later
+        r#"{ "timestamp":"2026-01-05T20:07:00Z", "event":"FactionKillBond", "Reward":47500, "AwardingFaction":"CdE Corporation", "VictimFaction":"Sothis Crimson Bridge Corp." }"#,
+        r#"{ "timestamp":"2026-01-05T20:37:00Z", "event":"FactionKillBond", "Reward":50000, "AwardingFaction":"CdE Corporation", "VictimFaction":"Sothis Crimson Bridge Corp." }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let log = CombatLog::new(&lines);
+    let engagements: Vec<&Engagement> = log.engagements().collect();
+    assert_eq!(3, engagements.len());
+
+    let first = engagements[0];
+    assert_eq!(HuntingGround::default(), first.ground);
+    assert_eq!(Some(&1), first.kills.get(&KillTarget::Unknown));
+    // damage to our fighter doesn't start the engagement or count as hull lost
+    assert_eq!(0.0, first.hull_lost());
+    assert_eq!(Duration::zero(), first.duration());
+    assert_eq!(None, first.average_time_to_kill());
+
+    let summary = &log.by_hunting_ground()[&HuntingGround::default()];
+    assert_eq!(3, summary.engagements);
+    assert_eq!(Credits(150000), summary.earned);
+    assert_eq!(None, summary.earned_per_hour());
+}
@@ -4,7 +4,13 @@ use crate::{
     log_line::{EDLogEvent, Extractable},
 };
 use ed_parse_log_files_macros::{Extractable, testcase, testcase_struct};
-use serde::{Deserialize, Serialize};
+use serde::{
+    Deserialize, Serialize,
+    de::{
+        IntoDeserializer,
+        value::{Error as ValueError, StrDeserializer},
+    },
+};
 use std::{
     fmt::Display,
     iter::Sum,
@@ -201,7 +207,7 @@ impl Credits {
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub enum Unknown {}
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[serde(rename_all = "lowercase")]
 pub enum OnFootItem {
     AeroGel,
@@ -432,9 +438,13 @@ pub enum OnFootItem {
 
     #[serde(rename = "nm_seed")]
     NMSeed,
+
+    /// any item name not listed above, so new items don't break parsing
+    #[serde(untagged)]
+    Other(EDString),
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum FactionState {
     None,
     Expansion,
@@ -611,14 +621,16 @@ pub enum SignalType {
     StationBernalSphere,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, Display)]
 pub enum MaterialCategory {
     Manufactured,
     Encoded,
     Raw,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(
+    Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Display,
+)]
 #[serde(rename_all = "lowercase")]
 pub enum TraderType {
     Manufactured,
@@ -683,7 +695,7 @@ pub enum FSSSignalType {
     Codex,
 }
 
-#[derive(Serialize, Deserialize, Clone, Copy, Debug, Display)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Display)]
 pub enum StationType {
     #[serde(rename = "")]
     #[strum(to_string = "None")]
@@ -723,7 +735,7 @@ pub enum ShipScanType {
     Data,
 }
 
-#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Allegiance {
     #[serde(rename = "")]
     None,
@@ -737,7 +749,7 @@ pub enum Allegiance {
     FrontlineSolutions,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum DroneType {
     Drones,
     Collection,
@@ -749,7 +761,7 @@ pub enum DroneType {
     FuelTransfer,
 }
 
-#[derive(Serialize, Deserialize, Clone, Copy, Debug, Display)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Display)]
 pub enum PowerplayState {
     Unoccupied,
     Exploited,
@@ -761,7 +773,7 @@ pub enum PowerplayState {
     Stronghold,
 }
 
-#[derive(Serialize, Deserialize, Copy, Clone, Debug, Display)]
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq, Hash, Display)]
 pub enum Power {
     #[serde(rename = "A. Lavigny-Duval")]
     #[strum(to_string = "A. Lavigny-Duval")]
@@ -804,6 +816,14 @@ pub enum Power {
     ZeminaTorval,
 }
 
+impl Power {
+    /// power from its journal name like "Jerome Archer"
+    pub fn from_name(name: &str) -> Option<Power> {
+        let deserializer: StrDeserializer<ValueError> = name.into_deserializer();
+        Power::deserialize(deserializer).ok()
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct PowerplayConflictProgress {
@@ -882,7 +902,7 @@ pub struct StationInformation {
     pub station_economies: Vec<StationEconomy>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum StationService {
     #[serde(alias = "Dock")]
@@ -953,15 +973,16 @@ pub enum StationService {
     Initiatives,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Display)]
 #[serde(rename_all = "lowercase")]
 pub enum WarType {
     Election,
     War,
+    #[strum(to_string = "Civil War")]
     CivilWar,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Display)]
 #[serde(rename_all = "lowercase")]
 pub enum ConflictStatus {
     #[serde(rename = "")]
@@ -973,18 +994,18 @@ pub enum ConflictStatus {
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct ConflictFaction {
-    name: EDString,
-    stake: EDString,
-    won_days: u64,
+    pub name: EDString,
+    pub stake: EDString,
+    pub won_days: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct Conflict {
-    war_type: WarType,
-    status: ConflictStatus,
-    faction1: ConflictFaction,
-    faction2: ConflictFaction,
+    pub war_type: WarType,
+    pub status: ConflictStatus,
+    pub faction1: ConflictFaction,
+    pub faction2: ConflictFaction,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -1019,10 +1040,10 @@ pub struct ThargoidWar {
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct StationEconomy {
-    name: SystemEconomy,
+    pub name: SystemEconomy,
     #[serde(rename = "Name_Localised")]
-    name_localised: EDString,
-    proportion: f64,
+    pub name_localised: EDString,
+    pub proportion: f64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -1049,21 +1070,21 @@ impl FromStr for FactionName {
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct FactionRecoveringState {
-    state: FactionState,
-    trend: u64,
+    pub state: FactionState,
+    pub trend: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct FactionActiveState {
-    state: FactionState,
+    pub state: FactionState,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct FactionPendingState {
-    state: FactionState,
-    trend: u64,
+    pub state: FactionState,
+    pub trend: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Copy, Display)]
@@ -1446,3 +1467,21 @@ fn test_station_info() {
     ]}"#;
     let _line: StationInformation = serde_json::from_str(json).expect("should parse");
 }
+
+#[test]
+fn test_on_foot_item() {
+    let item: OnFootItem = serde_json::from_str(r#""healthpack""#).expect("should parse");
+    assert_eq!(OnFootItem::HealthPack, item);
+
+    let item: OnFootItem = serde_json::from_str(r#""somenewgadget""#).expect("should parse");
+    assert_eq!(OnFootItem::Other(EDString::from("somenewgadget")), item);
+
+    let item: crate::market::MicroResource = serde_json::from_str(
+        r#"{ "Name":"somenewgadget", "Name_Localised":"Some New Gadget", "Category":"Item", "Count":2 }"#,
+    )
+    .expect("should parse");
+    assert_eq!(
+        OnFootItem::Other(EDString::from("somenewgadget")),
+        item.name
+    );
+}
@@ -1,7 +1,12 @@
-use crate::{EDString, common_types::Credits};
-use chrono::{DateTime, Utc};
+use crate::{
+    EDString,
+    common_types::Credits,
+    log_line::{EDLogEvent, EDLogLine},
+};
+use chrono::{DateTime, Duration, Utc};
 use ed_parse_log_files_macros::{testcase, testcase_struct};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
@@ -72,3 +77,318 @@ pub struct EDLogCommunityGoalDiscard {
 pub struct EDLogCommunityGoal {
     pub current_goals: Vec<CommunityGoal>,
 }
+
+/// snapshots this close to the latest one are used for projections
+pub const PROJECTION_WINDOW: Duration = Duration::hours(48);
+
+/// percentile bands reported for community goals, best first
+pub const PERCENTILE_BANDS: [u64; 5] = [10, 25, 50, 75, 100];
+
+/// tier number from a tier name like `Tier 3`
+pub fn tier_number(name: &str) -> Option<u64> {
+    name.trim().strip_prefix("Tier ")?.parse().ok()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CommunityGoalProjection {
+    pub current_total: u64,
+    pub tier: u64,
+    pub player_percentile_band: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct CommunityGoalHistory {
+    pub cgid: u64,
+    pub joined: Option<DateTime<Utc>>,
+    pub discarded: Option<DateTime<Utc>>,
+    pub reward: Option<(DateTime<Utc>, Credits)>,
+    /// oldest first, unchanged snapshots are skipped
+    pub snapshots: Vec<(DateTime<Utc>, CommunityGoal)>,
+}
+
+impl CommunityGoalHistory {
+    fn new(cgid: u64) -> CommunityGoalHistory {
+        CommunityGoalHistory {
+            cgid,
+            joined: None,
+            discarded: None,
+            reward: None,
+            snapshots: Vec::new(),
+        }
+    }
+
+    pub fn latest(&self) -> Option<&CommunityGoal> {
+        self.snapshots.last().map(|(_, goal)| goal)
+    }
+
+    pub fn total_history(&self) -> Vec<(DateTime<Utc>, u64)> {
+        self.snapshots
+            .iter()
+            .map(|(t, g)| (*t, g.current_total))
+            .collect()
+    }
+
+    pub fn contribution_history(&self) -> Vec<(DateTime<Utc>, u64)> {
+        self.snapshots
+            .iter()
+            .map(|(t, g)| (*t, g.player_contribution))
+            .collect()
+    }
+
+    pub fn percentile_band_history(&self) -> Vec<(DateTime<Utc>, u64)> {
+        self.snapshots
+            .iter()
+            .map(|(t, g)| (*t, g.player_percentile_band))
+            .collect()
+    }
+
+    /// Bounds of the tier size, assuming tiers are evenly spaced: reaching tier `n`
+    /// means the total is at least `n` and less than `n + 1` tier sizes.
+    fn tier_size(&self) -> Option<f64> {
+        let (mut low, mut high) = (0.0_f64, f64::MAX);
+        for (_, goal) in &self.snapshots {
+            let tier = goal
+                .tier_reached
+                .as_ref()
+                .and_then(|t| tier_number(t.as_str()))
+                .unwrap_or(0);
+            let total = goal.current_total as f64;
+            low = low.max(total / (tier + 1) as f64);
+            if tier > 0 {
+                high = high.min(total / tier as f64);
+            }
+        }
+        (high < f64::MAX).then_some((low.min(high) + high) / 2.0)
+    }
+
+    /// Extrapolate total, tier and our percentile band at expiry from the snapshots
+    /// within `PROJECTION_WINDOW` of the latest one, keeping our contribution as it is.
+    pub fn projection(&self) -> Option<CommunityGoalProjection> {
+        let (last_time, last) = self.snapshots.last()?;
+        let (first_time, first) = self
+            .snapshots
+            .iter()
+            .find(|(t, _)| *last_time - *t <= PROJECTION_WINDOW)?;
+        if last.is_complete || first_time == last_time {
+            return Some(CommunityGoalProjection {
+                current_total: last.current_total,
+                tier: last
+                    .tier_reached
+                    .as_ref()
+                    .and_then(|t| tier_number(t.as_str()))
+                    .unwrap_or(0),
+                player_percentile_band: last.player_percentile_band,
+            });
+        }
+
+        let elapsed = (*last_time - *first_time).num_seconds() as f64;
+        let remaining = (last.expiry - *last_time).num_seconds().max(0) as f64;
+        let factor = remaining / elapsed;
+
+        let total_rate = last.current_total.saturating_sub(first.current_total) as f64;
+        let current_total = last.current_total + (total_rate * factor).round() as u64;
+
+        let top_tier = tier_number(last.top_tier.name.as_str()).unwrap_or(u64::MAX);
+        let tier = self
+            .tier_size()
+            .map_or(0, |size| (current_total as f64 / size) as u64)
+            .min(top_tier);
+
+        let band_rate = last.player_percentile_band as f64 - first.player_percentile_band as f64;
+        let band = last.player_percentile_band as f64 + band_rate * factor;
+        let player_percentile_band = PERCENTILE_BANDS
+            .into_iter()
+            .find(|b| *b as f64 >= band)
+            .unwrap_or(100)
+            .max(last.player_percentile_band);
+
+        Some(CommunityGoalProjection {
+            current_total,
+            tier,
+            player_percentile_band,
+        })
+    }
+}
+
+/// Community goals we have seen, merged by CGID
+#[derive(Clone, Debug, Default)]
+pub struct CommunityGoals {
+    goals: BTreeMap<u64, CommunityGoalHistory>,
+}
+
+impl CommunityGoals {
+    /// build the goals from log lines sorted by timestamp
+    pub fn new(lines: &[EDLogLine]) -> CommunityGoals {
+        let mut goals = CommunityGoals::default();
+        for line in lines {
+            goals.add_line(line);
+        }
+        goals
+    }
+
+    fn goal(&mut self, cgid: u64) -> &mut CommunityGoalHistory {
+        self.goals
+            .entry(cgid)
+            .or_insert_with(|| CommunityGoalHistory::new(cgid))
+    }
+
+    pub fn add_line(&mut self, line: &EDLogLine) {
+        let timestamp = *line.timestamp();
+        match line.event() {
+            EDLogEvent::CommunityGoal(goals) => {
+                for goal in &goals.current_goals {
+                    let history = self.goal(goal.cgid);
+                    let unchanged = history.latest().is_some_and(|last| {
+                        last.current_total == goal.current_total
+                            && last.player_contribution == goal.player_contribution
+                            && last.player_percentile_band == goal.player_percentile_band
+                            && last.is_complete == goal.is_complete
+                    });
+                    if !unchanged {
+                        history.snapshots.push((timestamp, goal.clone()));
+                    }
+                }
+            }
+            EDLogEvent::CommunityGoalJoin(join) => {
+                self.goal(join.cgid).joined.get_or_insert(timestamp);
+            }
+            EDLogEvent::CommunityGoalDiscard(discard) => {
+                self.goal(discard.cgid).discarded = Some(timestamp);
+            }
+            EDLogEvent::CommunityGoalReward(reward) => {
+                self.goal(reward.cgid).reward = Some((timestamp, reward.reward));
+            }
+            _ => {}
+        }
+    }
+
+    pub fn get(&self, cgid: u64) -> Option<&CommunityGoalHistory> {
+        self.goals.get(&cgid)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &CommunityGoalHistory> {
+        self.goals.values()
+    }
+
+    /// goals that have not expired at `now`
+    pub fn active(&self, now: DateTime<Utc>) -> impl Iterator<Item = &CommunityGoalHistory> {
+        self.goals
+            .values()
+            .filter(move |g| g.latest().is_some_and(|l| l.expiry > now))
+    }
+}
+
+#[test]
+fn test_community_goals() {
+    let goal = |total: u64, band: u64, tier: &str| {
+        format!(
+            r#"{{ "CGID":834, "Title":"HIP 87621 Exobiology Initiative", "SystemName":"HIP 87621", "MarketName":"Exogene Sciences", "Expiry":"2025-11-27T12:00:00Z", "IsComplete":false, "CurrentTotal":{total}, "PlayerContribution":283, "NumContributors":25373, "TopTier":{{ "Name":"Tier 5", "Bonus":"" }}, "TopRankSize":10, "PlayerInTopRank":false, "TierReached":"{tier}", "PlayerPercentileBand":{band}, "Bonus":235000000 }}"#
+        )
+    };
+    let json = [
+        r#"{ "timestamp":"2025-11-24T10:00:00Z", "event":"CommunityGoalJoin", "CGID":834, "Name":"HIP 87621 Exobiology Initiative", "System":"HIP 87621" }"#.to_string(),
+        format!(
+            r#"{{ "timestamp":"2025-11-24T12:00:00Z", "event":"CommunityGoal", "CurrentGoals":[ {} ] }}"#,
+            goal(2_000_000, 10, "Tier 2")
+        ),
+        format!(
+            r#"{{ "timestamp":"2025-11-24T13:00:00Z", "event":"CommunityGoal", "CurrentGoals":[ {} ] }}"#,
+            goal(2_000_000, 10, "Tier 2")
+        ),
+        format!(
+            r#"{{ "timestamp":"2025-11-25T12:00:00Z", "event":"CommunityGoal", "CurrentGoals":[ {} ] }}"#,
+            goal(3_000_000, 25, "Tier 3")
+        ),
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let goals = CommunityGoals::new(&lines);
+    let history = goals.get(834).unwrap();
+    assert!(history.joined.is_some());
+    assert_eq!(2, history.snapshots.len());
+    assert_eq!(
+        vec![10, 25],
+        history
+            .percentile_band_history()
+            .into_iter()
+            .map(|(_, b)| b)
+            .collect::<Vec<_>>()
+    );
+
+    // two more days at one million a day, and two more band steps
+    let projection = history.projection().unwrap();
+    assert_eq!(5_000_000, projection.current_total);
+    assert_eq!(5, projection.tier);
+    assert_eq!(75, projection.player_percentile_band);
+}
+
+#[test]
+fn test_community_goals_without_snapshot() {
+    let json = [
+        r#"{ "timestamp":"2025-11-28T10:00:00Z", "event":"CommunityGoalReward", "CGID":834, "Name":"HIP 87621 Exobiology Initiative", "System":"HIP 87621", "Reward":5000000 }"#,
+        r#"{ "timestamp":"2025-11-28T10:05:00Z", "event":"CommunityGoalDiscard", "CGID":835, "Name":"Protect Deliveries to Minerva", "System":"Minerva" }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let goals = CommunityGoals::new(&lines);
+    assert_eq!(2, goals.iter().count());
+    let history = goals.get(834).unwrap();
+    assert_eq!(Some(Credits(5000000)), history.reward.map(|(_, r)| r));
+    assert!(history.joined.is_none());
+    assert!(history.latest().is_none());
+    assert!(history.projection().is_none());
+    assert!(goals.get(835).unwrap().discarded.is_some());
+    assert_eq!(
+        0,
+        goals
+            .active("2025-11-20T00:00:00Z".parse().unwrap())
+            .count()
+    );
+}
+
+#[test]
+fn test_community_goal_projection_saturates() {
+    let goal = |timestamp: &str, total: u64, band: u64, tier: &str| {
+        format!(
+            r#"{{ "timestamp":"{timestamp}", "event":"CommunityGoal", "CurrentGoals":[ {{ "CGID":834, "Title":"HIP 87621 Exobiology Initiative", "SystemName":"HIP 87621", "MarketName":"Exogene Sciences", "Expiry":"2025-11-27T12:00:00Z", "IsComplete":false, "CurrentTotal":{total}, "PlayerContribution":283, "NumContributors":25373, "TopTier":{{ "Name":"Tier 5", "Bonus":"" }}, "TopRankSize":10, "PlayerInTopRank":false, "TierReached":"{tier}", "PlayerPercentileBand":{band}, "Bonus":235000000 }} ] }}"#
+        )
+    };
+    let json = [
+        // outside PROJECTION_WINDOW of the latest snapshot
+        goal("2025-11-22T12:00:00Z", 0, 10, "Tier 0"),
+        goal("2025-11-24T12:00:00Z", 1_000_000, 50, "Tier 1"),
+        goal("2025-11-25T12:00:00Z", 4_000_000, 100, "Tier 4"),
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let goals = CommunityGoals::new(&lines);
+    let history = goals.get(834).unwrap();
+    assert_eq!(
+        1,
+        goals
+            .active("2025-11-26T00:00:00Z".parse().unwrap())
+            .count()
+    );
+    assert_eq!(
+        0,
+        goals
+            .active("2025-11-28T00:00:00Z".parse().unwrap())
+            .count()
+    );
+
+    // tier and band can't go past the top tier and the last band
+    let projection = history.projection().unwrap();
+    assert_eq!(10_000_000, projection.current_total);
+    assert_eq!(5, projection.tier);
+    assert_eq!(100, projection.player_percentile_band);
+
+    let single = CommunityGoals::new(&lines[2..]);
+    assert_eq!(
+        Some(CommunityGoalProjection {
+            current_total: 4_000_000,
+            tier: 4,
+            player_percentile_band: 100,
+        }),
+        single.get(834).unwrap().projection()
+    );
+}
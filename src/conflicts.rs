@@ -0,0 +1,418 @@
+use crate::{
+    EDString,
+    common_types::{Conflict, ConflictStatus, Credits, WarType},
+    log_line::{EDLogEvent, EDLogLine},
+    mission::VoucherType,
+};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// days a side needs to win to end a conflict
+pub const CONFLICT_DAYS_TO_WIN: u64 = 4;
+
+/// conflict state as seen on arrival in the system
+#[derive(Clone, Debug)]
+pub struct ConflictSnapshot {
+    pub timestamp: DateTime<Utc>,
+    pub status: ConflictStatus,
+    pub faction1_won_days: u64,
+    pub faction2_won_days: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct ConflictHistory {
+    pub system_address: u64,
+    pub star_system: EDString,
+    pub war_type: WarType,
+    pub faction1: EDString,
+    pub faction1_stake: EDString,
+    pub faction2: EDString,
+    pub faction2_stake: EDString,
+    /// oldest first
+    pub snapshots: Vec<ConflictSnapshot>,
+    /// first visit to the system without this conflict
+    pub ended: Option<DateTime<Utc>>,
+    /// combat bonds earned per side
+    pub bonds_earned: HashMap<EDString, Credits>,
+    /// combat bonds turned in per side
+    pub bonds_redeemed: HashMap<EDString, Credits>,
+}
+
+impl ConflictHistory {
+    fn new(
+        system_address: u64,
+        star_system: EDString,
+        conflict: &Conflict,
+        timestamp: DateTime<Utc>,
+    ) -> ConflictHistory {
+        let mut history = ConflictHistory {
+            system_address,
+            star_system,
+            war_type: conflict.war_type,
+            faction1: conflict.faction1.name.clone(),
+            faction1_stake: conflict.faction1.stake.clone(),
+            faction2: conflict.faction2.name.clone(),
+            faction2_stake: conflict.faction2.stake.clone(),
+            snapshots: Vec::new(),
+            ended: None,
+            bonds_earned: HashMap::new(),
+            bonds_redeemed: HashMap::new(),
+        };
+        history.update(conflict, timestamp);
+        history
+    }
+
+    fn update(&mut self, conflict: &Conflict, timestamp: DateTime<Utc>) {
+        let (faction1, faction2) = if conflict.faction1.name == self.faction1 {
+            (&conflict.faction1, &conflict.faction2)
+        } else {
+            (&conflict.faction2, &conflict.faction1)
+        };
+        self.snapshots.push(ConflictSnapshot {
+            timestamp,
+            status: conflict.status,
+            faction1_won_days: faction1.won_days,
+            faction2_won_days: faction2.won_days,
+        });
+    }
+
+    fn is_between(&self, a: &EDString, b: &EDString) -> bool {
+        (self.faction1 == *a && self.faction2 == *b) || (self.faction1 == *b && self.faction2 == *a)
+    }
+
+    pub fn involves(&self, faction: &str) -> bool {
+        self.faction1.as_str() == faction || self.faction2.as_str() == faction
+    }
+
+    pub fn latest(&self) -> Option<&ConflictSnapshot> {
+        self.snapshots.last()
+    }
+
+    pub fn won_days(&self, faction: &str) -> Option<u64> {
+        let latest = self.latest()?;
+        if self.faction1.as_str() == faction {
+            Some(latest.faction1_won_days)
+        } else if self.faction2.as_str() == faction {
+            Some(latest.faction2_won_days)
+        } else {
+            None
+        }
+    }
+
+    /// times at which the status changed, starting with the first status seen
+    pub fn status_transitions(&self) -> Vec<(DateTime<Utc>, ConflictStatus)> {
+        let mut transitions: Vec<(DateTime<Utc>, ConflictStatus)> = Vec::new();
+        for snapshot in &self.snapshots {
+            if transitions.last().map(|(_, s)| *s) != Some(snapshot.status) {
+                transitions.push((snapshot.timestamp, snapshot.status));
+            }
+        }
+        transitions
+    }
+
+    /// the conflict has ended or one side has won enough days
+    pub fn is_decided(&self) -> bool {
+        self.ended.is_some()
+            || self.latest().is_some_and(|l| {
+                l.faction1_won_days.max(l.faction2_won_days) >= CONFLICT_DAYS_TO_WIN
+            })
+    }
+
+    /// Side with more won days once the conflict is decided, None for a draw or
+    /// a conflict that is still running.
+    pub fn winner(&self) -> Option<EDString> {
+        if !self.is_decided() {
+            return None;
+        }
+        let latest = self.latest()?;
+        match latest.faction1_won_days.cmp(&latest.faction2_won_days) {
+            std::cmp::Ordering::Greater => Some(self.faction1.clone()),
+            std::cmp::Ordering::Less => Some(self.faction2.clone()),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+
+    /// side we earned the most combat bonds for
+    pub fn supported_side(&self) -> Option<EDString> {
+        self.bonds_earned
+            .iter()
+            .max_by_key(|(_, credits)| credits.0)
+            .map(|(faction, _)| faction.clone())
+    }
+
+    /// whether the side we fought for won, None while undecided or if we did not fight
+    pub fn did_our_side_win(&self) -> Option<bool> {
+        let supported = self.supported_side()?;
+        if !self.is_decided() {
+            return None;
+        }
+        Some(self.winner() == Some(supported))
+    }
+}
+
+/// Wars and elections per system followed across visits, with our combat bonds
+#[derive(Clone, Debug, Default)]
+pub struct ConflictTracker {
+    conflicts: Vec<ConflictHistory>,
+    current_system: Option<u64>,
+}
+
+impl ConflictTracker {
+    /// build the tracker from log lines sorted by timestamp
+    pub fn new(lines: &[EDLogLine]) -> ConflictTracker {
+        let mut tracker = ConflictTracker::default();
+        for line in lines {
+            tracker.add_line(line);
+        }
+        tracker
+    }
+
+    fn visit(
+        &mut self,
+        timestamp: DateTime<Utc>,
+        system_address: u64,
+        star_system: EDString,
+        conflicts: &[Conflict],
+    ) {
+        for conflict in conflicts {
+            let open = self.conflicts.iter_mut().find(|c| {
+                c.system_address == system_address
+                    && c.ended.is_none()
+                    && c.is_between(&conflict.faction1.name, &conflict.faction2.name)
+            });
+            match open {
+                Some(history) => history.update(conflict, timestamp),
+                None => self.conflicts.push(ConflictHistory::new(
+                    system_address,
+                    star_system.clone(),
+                    conflict,
+                    timestamp,
+                )),
+            }
+        }
+
+        for history in self
+            .conflicts
+            .iter_mut()
+            .filter(|c| c.system_address == system_address && c.ended.is_none())
+        {
+            if !conflicts
+                .iter()
+                .any(|c| history.is_between(&c.faction1.name, &c.faction2.name))
+            {
+                history.ended = Some(timestamp);
+            }
+        }
+    }
+
+    pub fn add_line(&mut self, line: &EDLogLine) {
+        let timestamp = *line.timestamp();
+        match line.event() {
+            EDLogEvent::FSDJump(jump) => {
+                self.current_system = Some(jump.system_address);
+                // systems without factions carry no conflict information
+                if jump.factions.is_some() {
+                    let conflicts = jump.conflicts.as_deref().unwrap_or_default();
+                    self.visit(
+                        timestamp,
+                        jump.system_address,
+                        jump.star_system.clone(),
+                        conflicts,
+                    );
+                }
+            }
+            EDLogEvent::Location(location) => {
+                self.current_system = location.system_address;
+                if let Some(address) = location.system_address
+                    && location.factions.is_some()
+                {
+                    let conflicts = location.conflicts.as_deref().unwrap_or_default();
+                    self.visit(timestamp, address, location.star_system.clone(), conflicts);
+                }
+            }
+            EDLogEvent::FactionKillBond(bond) => {
+                let current_system = self.current_system;
+                if let Some(history) = self.conflicts.iter_mut().rev().find(|c| {
+                    Some(c.system_address) == current_system
+                        && c.involves(bond.awarding_faction.as_str())
+                }) {
+                    *history
+                        .bonds_earned
+                        .entry(bond.awarding_faction.clone())
+                        .or_default() += bond.reward;
+                }
+            }
+            EDLogEvent::RedeemVoucher(voucher)
+                if voucher.voucher_type == VoucherType::CombatBond =>
+            {
+                let paid = voucher
+                    .factions
+                    .iter()
+                    .flatten()
+                    .map(|f| (f.faction.clone(), f.amount))
+                    .chain(voucher.faction.clone().map(|f| (f, voucher.amount)));
+                for (faction, amount) in paid {
+                    // bonds can be turned in anywhere, credit the most recent conflict of the faction
+                    if let Some(history) = self
+                        .conflicts
+                        .iter_mut()
+                        .rev()
+                        .find(|c| c.involves(faction.as_str()))
+                    {
+                        *history.bonds_redeemed.entry(faction).or_default() += amount;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ConflictHistory> {
+        self.conflicts.iter()
+    }
+
+    pub fn active(&self) -> impl Iterator<Item = &ConflictHistory> {
+        self.conflicts.iter().filter(|c| c.ended.is_none())
+    }
+
+    pub fn in_system(&self, system_address: u64) -> impl Iterator<Item = &ConflictHistory> {
+        self.conflicts
+            .iter()
+            .filter(move |c| c.system_address == system_address)
+    }
+}
+
+#[test]
+fn test_conflict_tracker() {
+    let jump = |timestamp: &str, conflicts: &str| {
+        format!(
+            r#"{{ "timestamp":"{timestamp}", "event":"FSDJump", "StarSystem":"Sothis", "SystemAddress":9463020987689, "StarPos":[-352.78125,10.50000,-346.34375], "SystemAllegiance":"Independent", "SystemEconomy":"$economy_Military;", "SystemEconomy_Localised":"Military", "SystemGovernment":"$government_Corporate;", "SystemGovernment_Localised":"Corporate", "SystemSecurity":"$SYSTEM_SECURITY_low;", "SystemSecurity_Localised":"Low Security", "Population":33893, "JumpDist":13.790, "FuelUsed":0.371038, "FuelLevel":5.628962,
+            "Factions":[ {{ "Name":"CdE Corporation", "FactionState":"War", "Government":"Corporate", "Influence":0.400000, "Allegiance":"Independent", "Happiness":"$Faction_HappinessBand2;", "Happiness_Localised":"Happy", "MyReputation":15.000000 }},
+                         {{ "Name":"Sothis Crimson Bridge Corp.", "FactionState":"War", "Government":"Corporate", "Influence":0.400000, "Allegiance":"Independent", "Happiness":"$Faction_HappinessBand2;", "Happiness_Localised":"Happy", "MyReputation":0.000000 }} ]
+            {conflicts} }}"#
+        )
+    };
+    let war = |status: &str, days1: u64, days2: u64| {
+        format!(
+            r#", "Conflicts":[ {{ "WarType":"war", "Status":"{status}", "Faction1":{{ "Name":"CdE Corporation", "Stake":"Wood Hub", "WonDays":{days1} }}, "Faction2":{{ "Name":"Sothis Crimson Bridge Corp.", "Stake":"", "WonDays":{days2} }} }} ]"#
+        )
+    };
+    let json = [
+        jump("2026-01-05T19:00:00Z", &war("pending", 0, 0)),
+        jump("2026-01-05T20:00:00Z", &war("active", 0, 0)),
+        r#"{ "timestamp":"2026-01-05T20:04:31Z", "event":"FactionKillBond", "Reward":52500, "AwardingFaction":"CdE Corporation", "VictimFaction":"Sothis Crimson Bridge Corp." }"#.to_string(),
+        r#"{ "timestamp":"2026-01-05T21:00:00Z", "event":"RedeemVoucher", "Type":"CombatBond", "Amount":52500, "Faction":"CdE Corporation" }"#.to_string(),
+        jump("2026-01-08T20:00:00Z", &war("active", 3, 1)),
+        jump("2026-01-09T20:00:00Z", &war("active", 4, 1)),
+        jump("2026-01-10T20:00:00Z", ""),
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let tracker = ConflictTracker::new(&lines);
+    assert_eq!(1, tracker.iter().count());
+    assert_eq!(0, tracker.active().count());
+
+    let war = tracker.in_system(9463020987689).next().unwrap();
+    assert_eq!(WarType::War, war.war_type);
+    assert_eq!(Some(4), war.won_days("CdE Corporation"));
+    assert_eq!(
+        vec![ConflictStatus::Pending, ConflictStatus::Active],
+        war.status_transitions()
+            .into_iter()
+            .map(|(_, s)| s)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        Some(&Credits(52500)),
+        war.bonds_earned.get(&EDString::from("CdE Corporation"))
+    );
+    assert_eq!(
+        Some(&Credits(52500)),
+        war.bonds_redeemed.get(&EDString::from("CdE Corporation"))
+    );
+    assert_eq!("CdE Corporation", war.winner().unwrap().as_str());
+    assert_eq!(Some(true), war.did_our_side_win());
+}
+
+#[test]
+fn test_conflict_tracker_empty() {
+    let tracker = ConflictTracker::new(&[]);
+    assert_eq!(0, tracker.iter().count());
+    assert_eq!(0, tracker.active().count());
+    assert_eq!(0, tracker.in_system(9463020987689).count());
+}
+
+#[test]
+fn test_conflict_tracker_draw() {
+    let jump = |timestamp: &str, factions: bool, conflicts: &str| {
+        let factions = if factions {
+            r#", "Factions":[ { "Name":"CdE Corporation", "FactionState":"War", "Government":"Corporate", "Influence":0.400000, "Allegiance":"Independent", "Happiness":"$Faction_HappinessBand2;", "Happiness_Localised":"Happy", "MyReputation":15.000000 },
+                             { "Name":"Sothis Crimson Bridge Corp.", "FactionState":"War", "Government":"Corporate", "Influence":0.400000, "Allegiance":"Independent", "Happiness":"$Faction_HappinessBand2;", "Happiness_Localised":"Happy", "MyReputation":0.000000 } ]"#
+        } else {
+            ""
+        };
+        format!(
+            r#"{{ "timestamp":"{timestamp}", "event":"FSDJump", "StarSystem":"Sothis", "SystemAddress":9463020987689, "StarPos":[-352.78125,10.50000,-346.34375], "SystemAllegiance":"Independent", "SystemEconomy":"$economy_Military;", "SystemEconomy_Localised":"Military", "SystemGovernment":"$government_Corporate;", "SystemGovernment_Localised":"Corporate", "SystemSecurity":"$SYSTEM_SECURITY_low;", "SystemSecurity_Localised":"Low Security", "Population":33893, "JumpDist":13.790, "FuelUsed":0.371038, "FuelLevel":5.628962
+            {factions} {conflicts} }}"#
+        )
+    };
+    // the journal may list the factions of a conflict in either order
+    let war = |first: &str, second: &str, days1: u64, days2: u64| {
+        format!(
+            r#", "Conflicts":[ {{ "WarType":"war", "Status":"active", "Faction1":{{ "Name":"{first}", "Stake":"", "WonDays":{days1} }}, "Faction2":{{ "Name":"{second}", "Stake":"", "WonDays":{days2} }} }} ]"#
+        )
+    };
+    let json = [
+        // bonds earned and redeemed before the conflict was seen are dropped
+        r#"{ "timestamp":"2026-01-05T18:00:00Z", "event":"FactionKillBond", "Reward":10000, "AwardingFaction":"CdE Corporation", "VictimFaction":"Sothis Crimson Bridge Corp." }"#.to_string(),
+        r#"{ "timestamp":"2026-01-05T18:30:00Z", "event":"RedeemVoucher", "Type":"CombatBond", "Amount":10000, "Faction":"CdE Corporation" }"#.to_string(),
+        jump(
+            "2026-01-05T20:00:00Z",
+            true,
+            &war("CdE Corporation", "Sothis Crimson Bridge Corp.", 0, 0),
+        ),
+        r#"{ "timestamp":"2026-01-05T20:04:31Z", "event":"FactionKillBond", "Reward":52500, "AwardingFaction":"Sothis Crimson Bridge Corp.", "VictimFaction":"CdE Corporation" }"#.to_string(),
+        jump(
+            "2026-01-07T20:00:00Z",
+            true,
+            &war("Sothis Crimson Bridge Corp.", "CdE Corporation", 2, 1),
+        ),
+        // no faction information, the conflict is still running
+        jump("2026-01-08T20:00:00Z", false, ""),
+        jump(
+            "2026-01-09T20:00:00Z",
+            true,
+            &war("CdE Corporation", "Sothis Crimson Bridge Corp.", 3, 3),
+        ),
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let tracker = ConflictTracker::new(&lines);
+    let war = tracker.in_system(9463020987689).next().unwrap();
+    assert_eq!(1, tracker.active().count());
+    assert_eq!(3, war.snapshots.len());
+    assert_eq!(1, war.snapshots[1].faction1_won_days);
+    assert_eq!(2, war.snapshots[1].faction2_won_days);
+    assert_eq!(Some(3), war.won_days("CdE Corporation"));
+    assert_eq!(None, war.won_days("Federal Navy"));
+    assert!(war.bonds_redeemed.is_empty());
+    assert_eq!(
+        "Sothis Crimson Bridge Corp.",
+        war.supported_side().unwrap().as_str()
+    );
+    assert!(!war.is_decided());
+    assert_eq!(None, war.winner());
+    assert_eq!(None, war.did_our_side_win());
+
+    let mut lines = lines;
+    lines.extend(crate::utils::parse_lines(&[jump(
+        "2026-01-10T20:00:00Z",
+        true,
+        "",
+    )]));
+    let tracker = ConflictTracker::new(&lines);
+    let war = tracker.in_system(9463020987689).next().unwrap();
+    assert!(war.is_decided());
+    assert_eq!(None, war.winner());
+    assert_eq!(Some(false), war.did_our_side_win());
+}
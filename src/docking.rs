@@ -40,7 +40,7 @@ pub struct EDLogRepairAll {
     pub cost: Credits,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct LandingPads {
     pub small: u64,
@@ -189,31 +189,42 @@ pub struct EDLogOutfitting {
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct ExchangedMaterials {
-    material: EDString,
+    pub material: EDString,
     #[serde(rename = "Material_Localised")]
-    material_localised: Option<EDString>,
-    category: MaterialCategory,
-    quantity: u64,
+    pub material_localised: Option<EDString>,
+    pub category: MaterialCategory,
+    pub quantity: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogMaterialTrade {
     #[serde(rename = "MarketID")]
-    market_id: u64,
-    trader_type: TraderType,
-    paid: ExchangedMaterials,
-    received: ExchangedMaterials,
+    pub market_id: u64,
+    pub trader_type: TraderType,
+    pub paid: ExchangedMaterials,
+    pub received: ExchangedMaterials,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogMaterialCollected {
-    category: MaterialCategory,
-    name: EDString,
+    pub category: MaterialCategory,
+    pub name: EDString,
     #[serde(rename = "Name_Localised")]
-    name_localised: Option<EDString>,
-    count: u64,
+    pub name_localised: Option<EDString>,
+    pub count: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+#[testcase({ "timestamp":"2024-03-10T18:22:38Z", "event":"MaterialDiscarded", "Category":"Raw", "Name":"iron", "Count":10 })]
+pub struct EDLogMaterialDiscarded {
+    pub category: MaterialCategory,
+    pub name: EDString,
+    #[serde(rename = "Name_Localised")]
+    pub name_localised: Option<EDString>,
+    pub count: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
@@ -251,11 +262,11 @@ pub struct BrokerItemUnlocked {
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct BrokerMaterial {
-    name: EDString,
+    pub name: EDString,
     #[serde(rename = "Name_Localised")]
-    name_localised: Option<EDString>,
-    count: u64,
-    category: MaterialCategory,
+    pub name_localised: Option<EDString>,
+    pub count: u64,
+    pub category: MaterialCategory,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
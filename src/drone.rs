@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 #[serde(deny_unknown_fields)]
 pub struct EDLogLaunchDrone {
     #[serde(rename = "Type")]
-    drone_type: DroneType,
+    pub drone_type: DroneType,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
@@ -0,0 +1,247 @@
+use crate::{
+    engineers::{
+        Engineer, EngineerProgress, EngineerProgressState, EngineerUnlockRequirement,
+        EngineerWorkshop,
+    },
+    log_line::{EDLogEvent, EDLogLine},
+};
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+
+pub const MAX_ENGINEER_RANK: u64 = 5;
+
+#[derive(Clone, Copy, Debug)]
+struct RankObservation {
+    rolls: u64,
+    rank: u64,
+    rank_progress: u64,
+}
+
+impl RankObservation {
+    // rank and progress combined in percent, rank 5 is 500%
+    fn total(&self) -> u64 {
+        self.rank * 100 + self.rank_progress
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct EngineerStatus {
+    pub engineer: Engineer,
+    pub engineer_id: Option<u64>,
+    pub progress: Option<EngineerProgressState>,
+    pub rank: Option<u64>,
+    pub rank_progress: Option<u64>,
+    pub last_update: DateTime<Utc>,
+    /// total donated towards the unlock requirement
+    pub contributed: u64,
+    /// number of blueprints rolled at this engineer
+    pub rolls: u64,
+    observations: Vec<RankObservation>,
+}
+
+impl EngineerStatus {
+    fn new(engineer: Engineer, timestamp: DateTime<Utc>) -> EngineerStatus {
+        EngineerStatus {
+            engineer,
+            engineer_id: None,
+            progress: None,
+            rank: None,
+            rank_progress: None,
+            last_update: timestamp,
+            contributed: 0,
+            rolls: 0,
+            observations: Vec::new(),
+        }
+    }
+
+    fn update(&mut self, progress: &EngineerProgress, timestamp: DateTime<Utc>) {
+        self.engineer_id = progress.engineer_id.or(self.engineer_id);
+        self.progress = Some(progress.progress);
+        self.rank = progress.rank.or(self.rank);
+        self.rank_progress = progress.rank_progress.or(self.rank_progress);
+        self.last_update = timestamp;
+
+        if let Some(rank) = progress.rank {
+            self.observations.push(RankObservation {
+                rolls: self.rolls,
+                rank,
+                rank_progress: progress.rank_progress.unwrap_or(0),
+            });
+        }
+    }
+
+    pub fn workshop(&self) -> EngineerWorkshop {
+        self.engineer.workshop()
+    }
+
+    pub fn unlock_requirement(&self) -> Option<EngineerUnlockRequirement> {
+        self.engineer.unlock_requirement()
+    }
+
+    /// fraction of the unlock donation that has been made, 1.0 once unlocked
+    pub fn unlock_progress(&self) -> Option<f64> {
+        if self.progress == Some(EngineerProgressState::Unlocked) {
+            return Some(1.0);
+        }
+        let requirement = self.unlock_requirement()?;
+        Some((self.contributed as f64 / requirement.quantity as f64).min(1.0))
+    }
+
+    /// average rank progress in percent gained per blueprint roll
+    pub fn progress_per_roll(&self) -> Option<f64> {
+        let first = self.observations.first()?;
+        let last = self.observations.last()?;
+        let rolls = last.rolls.checked_sub(first.rolls)?;
+        if rolls == 0 || last.total() <= first.total() {
+            return None;
+        }
+        Some((last.total() - first.total()) as f64 / rolls as f64)
+    }
+
+    /// estimated number of rolls still needed to reach the maximum rank
+    pub fn rolls_to_max_rank(&self) -> Option<u64> {
+        let rank = self.rank?;
+        if rank >= MAX_ENGINEER_RANK {
+            return Some(0);
+        }
+        let current = rank * 100 + self.rank_progress.unwrap_or(0);
+        let remaining = (MAX_ENGINEER_RANK * 100).saturating_sub(current);
+        Some((remaining as f64 / self.progress_per_roll()?).ceil() as u64)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct EngineerTracker {
+    engineers: BTreeMap<Engineer, EngineerStatus>,
+}
+
+impl EngineerTracker {
+    /// build the tracker from log lines sorted by timestamp
+    pub fn new(lines: &[EDLogLine]) -> EngineerTracker {
+        let mut tracker = EngineerTracker::default();
+        for line in lines {
+            tracker.add_line(line);
+        }
+        tracker
+    }
+
+    fn status(&mut self, engineer: Engineer, timestamp: DateTime<Utc>) -> &mut EngineerStatus {
+        self.engineers
+            .entry(engineer)
+            .or_insert_with(|| EngineerStatus::new(engineer, timestamp))
+    }
+
+    pub fn add_line(&mut self, line: &EDLogLine) {
+        let timestamp = *line.timestamp();
+        match line.event() {
+            EDLogEvent::EngineerProgress(progress) => {
+                for p in progress
+                    .engineer
+                    .iter()
+                    .chain(progress.engineers.iter().flatten())
+                {
+                    if let Some(engineer) = p.engineer {
+                        self.status(engineer, timestamp).update(p, timestamp);
+                    }
+                }
+            }
+            EDLogEvent::EngineerContribution(contribution) => {
+                let status = self.status(contribution.engineer, timestamp);
+                status.engineer_id = Some(contribution.engineer_id);
+                status.contributed = contribution.total_quantity;
+                status.last_update = timestamp;
+            }
+            EDLogEvent::EngineerCraft(craft) => {
+                if let Some(engineer) = craft.engineer {
+                    let status = self.status(engineer, timestamp);
+                    status.engineer_id = Some(craft.engineer_id);
+                    status.rolls += 1;
+                    status.last_update = timestamp;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn get(&self, engineer: Engineer) -> Option<&EngineerStatus> {
+        self.engineers.get(&engineer)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &EngineerStatus> {
+        self.engineers.values()
+    }
+
+    pub fn unlocked(&self) -> impl Iterator<Item = &EngineerStatus> {
+        self.engineers
+            .values()
+            .filter(|s| s.progress == Some(EngineerProgressState::Unlocked))
+    }
+}
+
+#[test]
+fn test_engineer_tracker() {
+    let json = [
+        r#"{ "timestamp":"2024-01-20T19:20:00Z", "event":"EngineerProgress", "Engineers":[ { "Engineer":"Colonel Bris Dekker", "EngineerID":300140, "Progress":"Invited" }, { "Engineer":"Didi Vatermann", "EngineerID":300000, "Progress":"Unlocked", "RankProgress":20, "Rank":3 } ] }"#,
+        r#"{ "timestamp":"2024-01-20T19:22:48Z", "event":"EngineerContribution", "Engineer":"Colonel Bris Dekker", "EngineerID":300140, "Type":"Bond", "Quantity":126004, "TotalQuantity":250000 }"#,
+        r#"{ "timestamp":"2024-01-20T19:30:00Z", "event":"EngineerCraft", "Slot":"TinyHardpoint3", "Module":"hpt_shieldbooster_size0_class5", "Ingredients":[], "Engineer":"Didi Vatermann", "EngineerID":300000, "BlueprintID":128673794, "BlueprintName":"ShieldBooster_Resistive", "Level":3, "Quality":0.400000, "Modifiers":[] }"#,
+        r#"{ "timestamp":"2024-01-20T19:31:00Z", "event":"EngineerCraft", "Slot":"TinyHardpoint3", "Module":"hpt_shieldbooster_size0_class5", "Ingredients":[], "Engineer":"Didi Vatermann", "EngineerID":300000, "BlueprintID":128673794, "BlueprintName":"ShieldBooster_Resistive", "Level":3, "Quality":0.600000, "Modifiers":[] }"#,
+        r#"{ "timestamp":"2024-01-20T19:31:01Z", "event":"EngineerProgress", "Engineer":"Didi Vatermann", "EngineerID":300000, "Progress":"Unlocked", "RankProgress":60, "Rank":3 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let tracker = EngineerTracker::new(&lines);
+
+    let dekker = tracker.get(Engineer::ColonelBrisDekker).unwrap();
+    assert_eq!(Some(0.25), dekker.unlock_progress());
+    assert_eq!("Sol", dekker.workshop().star_system);
+
+    let didi = tracker.get(Engineer::DidiVatermann).unwrap();
+    assert_eq!(2, didi.rolls);
+    assert_eq!(Some(20.0), didi.progress_per_roll());
+    // 140% to go at 20% per roll
+    assert_eq!(Some(7), didi.rolls_to_max_rank());
+    assert_eq!(1, tracker.unlocked().count());
+}
+
+#[test]
+fn test_engineer_tracker_without_progress() {
+    let tracker = EngineerTracker::new(&[]);
+    assert_eq!(0, tracker.iter().count());
+    assert_eq!(0, tracker.unlocked().count());
+
+    let json = [
+        // crafted before the first EngineerProgress of the log
+        r#"{ "timestamp":"2024-01-20T19:30:00Z", "event":"EngineerCraft", "Slot":"TinyHardpoint3", "Module":"hpt_shieldbooster_size0_class5", "Ingredients":[], "Engineer":"Didi Vatermann", "EngineerID":300000, "BlueprintID":128673794, "BlueprintName":"ShieldBooster_Resistive", "Level":3, "Quality":0.400000, "Modifiers":[] }"#,
+        r#"{ "timestamp":"2024-01-20T19:22:48Z", "event":"EngineerContribution", "Engineer":"Colonel Bris Dekker", "EngineerID":300140, "Type":"Bond", "Quantity":1200000, "TotalQuantity":1200000 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let tracker = EngineerTracker::new(&lines);
+    let didi = tracker.get(Engineer::DidiVatermann).unwrap();
+    assert_eq!(1, didi.rolls);
+    assert_eq!(None, didi.progress);
+    assert_eq!(None, didi.progress_per_roll());
+    assert_eq!(None, didi.rolls_to_max_rank());
+    assert_eq!(0, tracker.unlocked().count());
+
+    // donated more than needed
+    let dekker = tracker.get(Engineer::ColonelBrisDekker).unwrap();
+    assert_eq!(Some(1.0), dekker.unlock_progress());
+}
+
+#[test]
+fn test_engineer_tracker_max_rank() {
+    let json = [
+        r#"{ "timestamp":"2024-01-20T19:20:00Z", "event":"EngineerProgress", "Engineer":"Didi Vatermann", "EngineerID":300000, "Progress":"Unlocked", "Rank":5 }"#,
+        r#"{ "timestamp":"2024-01-20T19:30:00Z", "event":"EngineerCraft", "Slot":"TinyHardpoint3", "Module":"hpt_shieldbooster_size0_class5", "Ingredients":[], "Engineer":"Didi Vatermann", "EngineerID":300000, "BlueprintID":128673794, "BlueprintName":"ShieldBooster_Resistive", "Level":5, "Quality":0.400000, "Modifiers":[] }"#,
+        r#"{ "timestamp":"2024-01-20T19:31:00Z", "event":"EngineerProgress", "Engineer":"Didi Vatermann", "EngineerID":300000, "Progress":"Unlocked", "Rank":5 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let tracker = EngineerTracker::new(&lines);
+    let didi = tracker.get(Engineer::DidiVatermann).unwrap();
+    // no progress left to make
+    assert_eq!(None, didi.progress_per_roll());
+    assert_eq!(Some(0), didi.rolls_to_max_rank());
+    assert_eq!(Some(1.0), didi.unlock_progress());
+}
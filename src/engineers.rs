@@ -9,7 +9,9 @@ use ed_parse_log_files_macros::{Extractable, testcase, testcase_struct};
 use serde::{Deserialize, Serialize};
 use strum::Display;
 
-#[derive(Serialize, Deserialize, Clone, Debug, Display)]
+#[derive(
+    Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Display,
+)]
 pub enum Engineer {
     #[strum(to_string = "Baltanos")]
     #[serde(rename = "Baltanos")]
@@ -127,7 +129,7 @@ pub enum Engineer {
     ZacariahNemo,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum EngineerProgressState {
     Invited,
     Unlocked,
@@ -138,12 +140,12 @@ pub enum EngineerProgressState {
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 #[testcase_struct({ "Engineer":"Tod 'The Blaster' McQuinn", "EngineerID":300260, "Progress":"Known" })]
 pub struct EngineerProgress {
-    engineer: Option<Engineer>,
+    pub engineer: Option<Engineer>,
     #[serde(rename = "EngineerID")]
-    engineer_id: Option<u64>,
-    progress: EngineerProgressState,
-    rank_progress: Option<u64>,
-    rank: Option<u64>,
+    pub engineer_id: Option<u64>,
+    pub progress: EngineerProgressState,
+    pub rank_progress: Option<u64>,
+    pub rank: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
@@ -159,10 +161,10 @@ pub struct EDLogEngineerProgress {
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EngineerCraftIngredient {
-    name: EDString,
+    pub name: EDString,
     #[serde(rename = "Name_Localised")]
-    name_localised: Option<EDString>,
-    count: u64,
+    pub name_localised: Option<EDString>,
+    pub count: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -204,7 +206,7 @@ pub struct EDLogEngineerCraft {
     pub modifiers: Vec<ModuleEngineeringModifiers>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum EngineerContributionType {
     Bond,
     Bounty,
@@ -240,3 +242,130 @@ pub struct EDLogEngineerContribution {
     pub quantity: u64,
     pub total_quantity: u64,
 }
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EngineerWorkshop {
+    pub star_system: &'static str,
+    pub base: &'static str,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EngineerUnlockRequirement {
+    pub contribution_type: EngineerContributionType,
+    /// journal name of the commodity or material to donate
+    pub item: Option<&'static str>,
+    pub quantity: u64,
+}
+
+const fn donate(
+    contribution_type: EngineerContributionType,
+    item: Option<&'static str>,
+    quantity: u64,
+) -> Option<EngineerUnlockRequirement> {
+    Some(EngineerUnlockRequirement {
+        contribution_type,
+        item,
+        quantity,
+    })
+}
+
+impl Engineer {
+    pub fn workshop(&self) -> EngineerWorkshop {
+        let (star_system, base) = match self {
+            Engineer::Baltanos => ("Deriso", "The Divine Apparatus"),
+            Engineer::BillTurner => ("Alioth", "Turner Metallics Inc"),
+            Engineer::BrooTarquin => ("Muang", "Broo's Legacy"),
+            Engineer::ChloeSedesi => ("Shenve", "Cinder Dock"),
+            Engineer::ColonelBrisDekker => ("Sol", "Dekker's Yard"),
+            Engineer::DidiVatermann => ("Leesti", "Vatermann LLC"),
+            Engineer::DominoGreen => ("Orishis", "The Jackrabbit"),
+            Engineer::TheDweller => ("Wyrd", "Black Hide"),
+            Engineer::ElviraMartuuk => ("Khun", "Long Sight Base"),
+            Engineer::EleanorBresa => ("Desy", "Bresa Modifications"),
+            Engineer::EtienneDorn => ("Los", "Kraken's Retreat"),
+            Engineer::FelicityFarseer => ("Deciat", "Farseer Inc"),
+            Engineer::HeraTani => ("Kuwemaki", "The Jet's Hole"),
+            Engineer::HeroFerrari => ("Siris", "Nevermore Terrace"),
+            Engineer::JudeNavarro => ("Aurai", "Marshall's Drift"),
+            Engineer::JuriIshmaak => ("Giryak", "Pater's Memorial"),
+            Engineer::KitFowler => ("Capoya", "The Last Call"),
+            Engineer::LeiCheung => ("Laksak", "Trader's Rest"),
+            Engineer::LizRyder => ("Eurybia", "Demolition Unlimited"),
+            Engineer::LoriJameson => ("Shinrarta Dezhra", "Jameson Base"),
+            Engineer::MarshaHicks => ("Tir", "The Watchtower"),
+            Engineer::MelBrandon => ("Luchtaine", "The Brig"),
+            Engineer::MarcoQwent => ("Sirius", "Qwent Research Base"),
+            Engineer::OdenGeiger => ("Candiaei", "Ankh's Promise"),
+            Engineer::PetraOlmanova => ("Asura", "Sanctuary"),
+            Engineer::ProfessorPalin => ("Arque", "Abel Laboratory"),
+            Engineer::RamTah => ("Meene", "Phoenix Base"),
+            Engineer::TheSarge => ("Beta-3 Tucani", "The Beach"),
+            Engineer::RosaDayette => ("Kojeara", "Rosa's Shop"),
+            Engineer::SeleneJean => ("Kuk", "Prospector's Rest"),
+            Engineer::TerraVelasquez => ("Shou Xing", "Rascal's Choice"),
+            Engineer::TianaFortune => ("Achenar", "Fortune's Loss"),
+            Engineer::TodTheBlasterMcQuinn => ("Wolf 397", "Trophy Camp"),
+            Engineer::UmaLaszlo => ("Xuane", "Laszlo's Resolve"),
+            Engineer::WellingtonBeck => ("Jolapa", "Beck Facility"),
+            Engineer::YardenBond => ("Bayan", "Salamander Bank"),
+            Engineer::YiShen => ("Einheriar", "Eidolon Hold"),
+            Engineer::ZacariahNemo => ("Yoru", "Nemo Cyber Party Base"),
+        };
+        EngineerWorkshop { star_system, base }
+    }
+
+    /// The donation needed to unlock the engineer. Engineers that are unlocked
+    /// by referral or by completing tasks have no donation requirement.
+    pub fn unlock_requirement(&self) -> Option<EngineerUnlockRequirement> {
+        use EngineerContributionType::*;
+
+        match self {
+            Engineer::BillTurner => donate(Commodity, Some("bromellite"), 50),
+            Engineer::BrooTarquin => donate(Commodity, Some("fujintea"), 50),
+            Engineer::ChloeSedesi => donate(Materials, Some("unknownenergysource"), 25),
+            Engineer::ColonelBrisDekker => donate(Bond, None, 1_000_000),
+            Engineer::DidiVatermann => donate(Commodity, Some("lavianbrandy"), 50),
+            Engineer::TheDweller => donate(Credits, None, 500_000),
+            Engineer::ElviraMartuuk => donate(Commodity, Some("soontillrelics"), 3),
+            Engineer::EtienneDorn => donate(Commodity, Some("occupiedcryopod"), 25),
+            Engineer::FelicityFarseer => donate(Commodity, Some("metaalloys"), 1),
+            Engineer::HeraTani => donate(Commodity, Some("kamitracigars"), 50),
+            Engineer::JuriIshmaak => donate(Bond, None, 100_000),
+            Engineer::LeiCheung => donate(Commodity, Some("gold"), 200),
+            Engineer::LizRyder => donate(Commodity, Some("landmines"), 200),
+            Engineer::LoriJameson => donate(Commodity, Some("konggaale"), 25),
+            Engineer::MarcoQwent => donate(Commodity, Some("modularterminals"), 25),
+            Engineer::MarshaHicks => donate(Commodity, Some("osmium"), 10),
+            Engineer::MelBrandon => donate(Bounty, None, 100_000),
+            Engineer::PetraOlmanova => donate(Commodity, Some("progenitorcells"), 200),
+            Engineer::ProfessorPalin => donate(Materials, Some("unknownenergysource"), 25),
+            Engineer::RamTah => donate(Materials, Some("scandatabanks"), 50),
+            Engineer::TheSarge => donate(Materials, Some("shieldpatternanalysis"), 50),
+            Engineer::SeleneJean => donate(Commodity, Some("painite"), 10),
+            Engineer::TianaFortune => donate(Materials, Some("decodedemissiondata"), 50),
+            Engineer::TodTheBlasterMcQuinn => donate(Bounty, None, 100_000),
+            Engineer::ZacariahNemo => donate(Commodity, Some("xihecompanions"), 25),
+            _ => None,
+        }
+    }
+
+    /// Odyssey engineers modify suits and weapons and have no ranks
+    pub fn has_ranks(&self) -> bool {
+        !matches!(
+            self,
+            Engineer::Baltanos
+                | Engineer::DominoGreen
+                | Engineer::EleanorBresa
+                | Engineer::HeroFerrari
+                | Engineer::JudeNavarro
+                | Engineer::KitFowler
+                | Engineer::OdenGeiger
+                | Engineer::RosaDayette
+                | Engineer::TerraVelasquez
+                | Engineer::UmaLaszlo
+                | Engineer::WellingtonBeck
+                | Engineer::YardenBond
+                | Engineer::YiShen
+        )
+    }
+}
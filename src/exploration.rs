@@ -4,9 +4,16 @@ use crate::{
         Credits, FSSSignalType, LuminosityType, MaterialCategory, ScanType, ShipScanType,
         SignalType, StarClass, Unknown,
     },
+    market::MarketItemType,
 };
 use ed_parse_log_files_macros::{Extractable, testcase, testcase_struct};
-use serde::{Deserialize, Serialize};
+use serde::{
+    Deserialize, Serialize,
+    de::{
+        IntoDeserializer,
+        value::{Error as ValueError, StrDeserializer},
+    },
+};
 use strum::Display;
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -97,21 +104,28 @@ pub struct ScannedStarDetails {
     pub luminosity: LuminosityType,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, Display)]
 pub enum RingClass {
     #[serde(rename = "eRingClass_Rocky")]
     Rocky,
+    #[serde(rename = "eRingClass_Metalic")]
+    Metallic,
+    #[serde(rename = "eRingClass_MetalRich")]
+    #[strum(to_string = "Metal Rich")]
+    MetalRich,
+    #[serde(rename = "eRingClass_Icy")]
+    Icy,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct Ring {
-    name: EDString,
-    ring_class: RingClass,
+    pub name: EDString,
+    pub ring_class: RingClass,
     #[serde(rename = "MassMT")]
-    mass_mt: f64,
-    inner_rad: f64,
-    outer_rad: f64,
+    pub mass_mt: f64,
+    pub inner_rad: f64,
+    pub outer_rad: f64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Copy)]
@@ -242,7 +256,7 @@ pub struct ScannedCommonDetails {
     axial_tilt: f64,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, Display)]
 pub enum ReserveLevel {
     #[serde(rename = "MajorResources")]
     Major,
@@ -342,38 +356,67 @@ pub struct EDLogScanOrganic {
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct ProspectedMaterial {
-    name: EDString,
+    pub name: EDString,
     #[serde(rename = "Name_Localised")]
-    name_localised: Option<EDString>,
-    proportion: f64,
+    pub name_localised: Option<EDString>,
+    pub proportion: f64,
+}
+
+// prospector names are the commodity names in PascalCase, like "LowTemperatureDiamond"
+fn commodity_from_name(name: &str) -> Option<MarketItemType> {
+    let name = name.to_lowercase();
+    let deserializer: StrDeserializer<ValueError> = name.as_str().into_deserializer();
+    MarketItemType::deserialize(deserializer).ok()
+}
+
+impl ProspectedMaterial {
+    pub fn commodity(&self) -> Option<MarketItemType> {
+        commodity_from_name(self.name.as_str())
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogProspectedAsteroid {
-    materials: Vec<ProspectedMaterial>,
-    motherlode_material: Option<EDString>,
+    pub materials: Vec<ProspectedMaterial>,
+    pub motherlode_material: Option<EDString>,
     #[serde(rename = "MotherlodeMaterial_Localised")]
-    motherlode_material_localised: Option<EDString>,
-    content: EDString,
+    pub motherlode_material_localised: Option<EDString>,
+    pub content: EDString,
     #[serde(rename = "Content_Localised")]
-    content_localised: EDString,
-    remaining: f64,
+    pub content_localised: EDString,
+    pub remaining: f64,
+}
+
+impl EDLogProspectedAsteroid {
+    pub fn motherlode(&self) -> Option<MarketItemType> {
+        self.motherlode_material
+            .as_ref()
+            .and_then(|m| commodity_from_name(m.as_str()))
+    }
+
+    /// proportion in percent of a commodity in the asteroid, 0 if absent
+    pub fn proportion(&self, commodity: MarketItemType) -> f64 {
+        self.materials
+            .iter()
+            .find(|m| m.commodity() == Some(commodity))
+            .map_or(0.0, |m| m.proportion)
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogAsteroidCracked {
-    body: EDString,
+    pub body: EDString,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogMiningRefined {
     #[serde(rename = "Type")]
-    material_type: EDString,
+    pub material_type: MarketItemType,
     #[serde(rename = "Type_Localised")]
-    material_type_localised: EDString,
+    pub material_type_localised: EDString,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
@@ -482,7 +525,7 @@ pub struct EDLogFSSAllBodiesFound {
     pub count: u64,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum BodySignalType {
     #[serde(rename = "$SAA_SignalType_Biological;")]
     Biological,
@@ -515,6 +558,28 @@ pub enum BodySignalType {
     Tritium,
 }
 
+impl BodySignalType {
+    /// the commodity of a ring hotspot
+    pub fn commodity(&self) -> Option<MarketItemType> {
+        match self {
+            BodySignalType::Alexandrite => Some(MarketItemType::Alexandrite),
+            BodySignalType::Benitoite => Some(MarketItemType::Benitoite),
+            BodySignalType::Bromellite => Some(MarketItemType::Bromellite),
+            BodySignalType::Grandidierite => Some(MarketItemType::Grandidierite),
+            BodySignalType::LowTemperatureDiamond => Some(MarketItemType::LowTempDiamonds),
+            BodySignalType::Monazite => Some(MarketItemType::Monazite),
+            BodySignalType::Musgravite => Some(MarketItemType::Musgravite),
+            BodySignalType::Opal => Some(MarketItemType::VoidOpal),
+            BodySignalType::Painite => Some(MarketItemType::Painite),
+            BodySignalType::Platinum => Some(MarketItemType::Platinum),
+            BodySignalType::Rhodplumsite => Some(MarketItemType::Rhodplumsite),
+            BodySignalType::Serendibite => Some(MarketItemType::Serendibite),
+            BodySignalType::Tritium => Some(MarketItemType::Tritium),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct BodySignal {
@@ -696,3 +761,19 @@ fn test_signal_type_optional() {
         assert!(header.signal_type.is_none());
     }
 }
+
+#[test]
+fn test_prospected_unknown_material() {
+    let json = r#"{ "timestamp":"2025-03-01T10:15:05Z", "event":"ProspectedAsteroid", "Materials":[ { "Name":"Unobtainium", "Proportion":40.0 }, { "Name":"Bromellite", "Proportion":12.5 } ], "MotherlodeMaterial":"Unobtainium", "Content":"$AsteroidMaterialContent_Low;", "Content_Localised":"Material Content: Low", "Remaining":0.0 }"#;
+
+    let line: crate::log_line::EDLogLine = serde_json::from_str(json).expect("Should parse");
+
+    if let crate::log_line::EDLogEvent::ProspectedAsteroid(asteroid) = line.event() {
+        assert!(asteroid.materials[0].commodity().is_none());
+        assert!(asteroid.motherlode().is_none());
+        assert_eq!(12.5, asteroid.proportion(MarketItemType::Bromellite));
+        assert_eq!(0.0, asteroid.proportion(MarketItemType::Platinum));
+    } else {
+        panic!("Expected ProspectedAsteroid");
+    }
+}
@@ -8,6 +8,7 @@ use crate::{
 };
 use ed_parse_log_files_macros::{Extractable, testcase};
 use serde::{Deserialize, Serialize};
+use strum::Display;
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
@@ -38,15 +39,15 @@ pub struct EDLogCarrierFinance {
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogCarrierTradeOrder {
     #[serde(rename = "CarrierID")]
-    carrier_id: u64,
-    black_market: bool,
-    commodity: EDString, // check on MarketItemType versus OnFootItem
+    pub carrier_id: u64,
+    pub black_market: bool,
+    pub commodity: EDString, // check on MarketItemType versus OnFootItem
     #[serde(rename = "Commodity_Localised")]
-    commodity_localised: Option<String>,
-    cancel_trade: Option<bool>,
-    sale_order: Option<u32>,
-    purchase_order: Option<Credits>,
-    price: Option<Credits>,
+    pub commodity_localised: Option<String>,
+    pub cancel_trade: Option<bool>,
+    pub sale_order: Option<u32>,
+    pub purchase_order: Option<u32>,
+    pub price: Option<Credits>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
@@ -62,7 +63,7 @@ pub struct EDLogCarrierBankTransfer {
     pub carrier_balance: Credits,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum CarrierType {
     FleetCarrier,
     SquadronCarrier,
@@ -74,14 +75,14 @@ pub enum CarrierType {
 #[testcase({ "timestamp":"2025-08-21T19:35:04Z", "event":"CarrierJumpRequest", "CarrierType":"FleetCarrier", "CarrierID":123456789, "SystemName":"Prooe Drye LV-C c1-2", "Body":"Prooe Drye LV-C c1-2", "SystemAddress":631192163082, "BodyID":0, "DepartureTime":"2025-08-21T20:08:10Z" })]
 pub struct EDLogCarrierJumpRequest {
     #[serde(rename = "CarrierID")]
-    carrier_id: u64,
-    carrier_type: Option<CarrierType>,
-    system_name: EDString,
-    body: Option<EDString>,
-    system_address: u64,
+    pub carrier_id: u64,
+    pub carrier_type: Option<CarrierType>,
+    pub system_name: EDString,
+    pub body: Option<EDString>,
+    pub system_address: u64,
     #[serde(rename = "BodyID")]
-    body_id: u64,
-    departure_time: EDString,
+    pub body_id: u64,
+    pub departure_time: EDString,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
@@ -90,13 +91,13 @@ pub struct EDLogCarrierJump {
     docked: bool,
     on_foot: Option<bool>,
     #[serde(flatten)]
-    station_information: Option<StationInformation>,
+    pub station_information: Option<StationInformation>,
     taxi: Option<bool>,
     multicrew: Option<bool>,
     // TODO: location has overlapping fields
-    star_system: EDString,
-    system_address: u64,
-    star_pos: [f64; 3],
+    pub star_system: EDString,
+    pub system_address: u64,
+    pub star_pos: [f64; 3],
     system_allegiance: Allegiance,
     system_economy: EDString,
     #[serde(rename = "SystemEconomy_Localised")]
@@ -133,20 +134,22 @@ pub struct EDLogCarrierJump {
 #[testcase({ "timestamp":"2025-09-01T19:09:57Z", "event":"CarrierJumpCancelled", "CarrierType":"FleetCarrier", "CarrierID":3706278912 })]
 pub struct EDLogCarrierJumpCancelled {
     #[serde(rename = "CarrierID")]
-    carrier_id: u64,
-    carrier_type: Option<CarrierType>,
+    pub carrier_id: u64,
+    pub carrier_type: Option<CarrierType>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogCarrierDepositFuel {
     #[serde(rename = "CarrierID")]
-    carrier_id: u64,
-    amount: u64,
-    total: u64,
+    pub carrier_id: u64,
+    pub amount: u64,
+    pub total: u64,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(
+    Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Display,
+)]
 pub enum CrewRole {
     Repair,
     VoucherRedemption,
@@ -164,7 +167,7 @@ pub enum CrewRole {
     CarrierFuel,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum CrewServiceOperation {
     Activate,
     Pause,
@@ -179,11 +182,11 @@ pub enum CrewServiceOperation {
     "CrewRole":"Bartender", "Operation":"Pause", "CrewName":"Aleeah Bogdani" })]
 pub struct EDLogCarrierCrewServices {
     #[serde(rename = "CarrierID")]
-    carrier_id: u64,
-    carrier_type: Option<CarrierType>,
-    crew_role: CrewRole,
-    operation: CrewServiceOperation,
-    crew_name: EDString,
+    pub carrier_id: u64,
+    pub carrier_type: Option<CarrierType>,
+    pub crew_role: CrewRole,
+    pub operation: CrewServiceOperation,
+    pub crew_name: EDString,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -233,12 +236,12 @@ pub struct EDLogCarrierDockingPermission {
     "CarrierID":123456789, "StarSystem":"BD-11 192", "SystemAddress":908486218450, "BodyID":3 })]
 pub struct EDLogCarrierLocation {
     #[serde(rename = "CarrierID")]
-    carrier_id: u64,
-    carrier_type: Option<CarrierType>,
-    star_system: EDString,
-    system_address: u64,
+    pub carrier_id: u64,
+    pub carrier_type: Option<CarrierType>,
+    pub star_system: EDString,
+    pub system_address: u64,
     #[serde(rename = "BodyID")]
-    body_id: u64,
+    pub body_id: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
@@ -257,25 +260,25 @@ pub enum ShipPack {}
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct ModulePack {
-    pack_theme: EDString,
-    pack_tier: u64,
+    pub pack_theme: EDString,
+    pub pack_tier: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct CrewMember {
-    crew_name: Option<EDString>,
-    crew_role: CrewRole,
-    activated: bool,
-    enabled: Option<bool>,
-    activated_props: Option<ActivatedProps>,
+    pub crew_name: Option<EDString>,
+    pub crew_role: CrewRole,
+    pub activated: bool,
+    pub enabled: Option<bool>,
+    pub activated_props: Option<ActivatedProps>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct ActivatedProps {
-    enabled: bool,
-    crew_name: EDString,
+    pub enabled: bool,
+    pub crew_name: EDString,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -302,13 +305,13 @@ pub struct Finance {
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct SpaceUsage {
-    total_capacity: u64,
-    crew: u64,
-    cargo: u64,
-    cargo_space_reserved: u64,
-    ship_packs: u64,
-    module_packs: u64,
-    free_space: u64,
+    pub total_capacity: u64,
+    pub crew: u64,
+    pub cargo: u64,
+    pub cargo_space_reserved: u64,
+    pub ship_packs: u64,
+    pub module_packs: u64,
+    pub free_space: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
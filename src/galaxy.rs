@@ -0,0 +1,170 @@
+use crate::{
+    EDString,
+    log_line::{EDLogEvent, EDLogLine},
+};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+pub const SOL: [f64; 3] = [0.0, 0.0, 0.0];
+
+pub fn distance(from: &[f64; 3], to: &[f64; 3]) -> f64 {
+    let dx = to[0] - from[0];
+    let dy = to[1] - from[1];
+    let dz = to[2] - from[2];
+    (dx * dx + dy * dy + dz * dz).sqrt()
+}
+
+#[derive(Clone, Debug)]
+pub struct KnownSystem {
+    pub system_address: u64,
+    pub star_system: EDString,
+    pub star_pos: [f64; 3],
+    pub last_visited: DateTime<Utc>,
+}
+
+/// Star systems with coordinates, as seen in FSDJump, Location and CarrierJump events
+#[derive(Clone, Debug, Default)]
+pub struct KnownSystems {
+    systems: HashMap<u64, KnownSystem>,
+}
+
+impl KnownSystems {
+    pub fn new(lines: &[EDLogLine]) -> KnownSystems {
+        let mut known = KnownSystems::default();
+        for line in lines {
+            known.add_line(line);
+        }
+        known
+    }
+
+    pub fn add_line(&mut self, line: &EDLogLine) {
+        let (system_address, star_system, star_pos) = match line.event() {
+            EDLogEvent::FSDJump(jump) => {
+                (jump.system_address, jump.star_system.clone(), jump.star_pos)
+            }
+            EDLogEvent::Location(location) => match location.system_address {
+                Some(address) => (address, location.star_system.clone(), location.star_pos),
+                None => return,
+            },
+            EDLogEvent::CarrierJump(jump) => {
+                (jump.system_address, jump.star_system.clone(), jump.star_pos)
+            }
+            _ => return,
+        };
+        self.add(system_address, star_system, star_pos, *line.timestamp());
+    }
+
+    pub fn add(
+        &mut self,
+        system_address: u64,
+        star_system: EDString,
+        star_pos: [f64; 3],
+        timestamp: DateTime<Utc>,
+    ) {
+        let system = self
+            .systems
+            .entry(system_address)
+            .or_insert_with(|| KnownSystem {
+                system_address,
+                star_system,
+                star_pos,
+                last_visited: timestamp,
+            });
+        if system.last_visited <= timestamp {
+            system.last_visited = timestamp;
+        }
+    }
+
+    pub fn get(&self, system_address: u64) -> Option<&KnownSystem> {
+        self.systems.get(&system_address)
+    }
+
+    pub fn find_by_name(&self, name: &str) -> Option<&KnownSystem> {
+        self.systems
+            .values()
+            .find(|s| s.star_system.as_str().eq_ignore_ascii_case(name))
+    }
+
+    pub fn position(&self, system_address: u64) -> Option<[f64; 3]> {
+        self.get(system_address).map(|s| s.star_pos)
+    }
+
+    /// distance in light years between two known systems
+    pub fn distance_between(&self, from: u64, to: u64) -> Option<f64> {
+        Some(distance(&self.position(from)?, &self.position(to)?))
+    }
+
+    /// all known systems within `radius` light years of `from`, nearest first
+    pub fn within(&self, from: &[f64; 3], radius: f64) -> Vec<(&KnownSystem, f64)> {
+        let mut result: Vec<_> = self
+            .systems
+            .values()
+            .map(|s| (s, distance(from, &s.star_pos)))
+            .filter(|(_, d)| *d <= radius)
+            .collect();
+        result.sort_by(|a, b| a.1.total_cmp(&b.1));
+        result
+    }
+
+    pub fn len(&self) -> usize {
+        self.systems.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.systems.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &KnownSystem> {
+        self.systems.values()
+    }
+}
+
+#[test]
+fn test_known_systems() {
+    let json = [
+        r#"{ "timestamp":"2024-10-13T17:51:23Z", "event":"FSDJump", "StarSystem":"Yukoukha", "SystemAddress":22945325796176, "StarPos":[-134.56250,-34.03125,-4.75000], "SystemAllegiance":"Independent", "SystemEconomy":"$economy_Military;", "SystemEconomy_Localised":"Military", "SystemGovernment":"$government_Corporate;", "SystemGovernment_Localised":"Corporate", "SystemSecurity":"$SYSTEM_SECURITY_low;", "SystemSecurity_Localised":"Low Security", "Population":33893, "JumpDist":13.790, "FuelUsed":0.371038, "FuelLevel":5.628962 }"#,
+        r#"{ "timestamp":"2024-10-13T17:55:23Z", "event":"FSDJump", "StarSystem":"Sol", "SystemAddress":10477373803, "StarPos":[0.0,0.0,0.0], "SystemAllegiance":"Federation", "SystemEconomy":"$economy_Refinery;", "SystemEconomy_Localised":"Refinery", "SystemGovernment":"$government_Democracy;", "SystemGovernment_Localised":"Democracy", "SystemSecurity":"$SYSTEM_SECURITY_high;", "SystemSecurity_Localised":"High Security", "Population":22780919531, "JumpDist":13.790, "FuelUsed":0.371038, "FuelLevel":5.628962 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let known = KnownSystems::new(&lines);
+    assert_eq!(2, known.len());
+    assert_eq!(
+        10477373803,
+        known.find_by_name("sol").unwrap().system_address
+    );
+    let d = known.distance_between(22945325796176, 10477373803).unwrap();
+    assert!((d - 138.88).abs() < 0.01);
+    assert_eq!(1, known.within(&SOL, 100.0).len());
+}
+
+#[test]
+fn test_known_systems_out_of_order() {
+    let known = KnownSystems::new(&[]);
+    assert!(known.is_empty());
+    assert!(
+        known
+            .distance_between(22945325796176, 10477373803)
+            .is_none()
+    );
+    assert!(known.within(&SOL, 1000.0).is_empty());
+
+    let json = [
+        r#"{ "timestamp":"2024-10-13T17:55:23Z", "event":"FSDJump", "StarSystem":"Sol", "SystemAddress":10477373803, "StarPos":[0.0,0.0,0.0], "SystemAllegiance":"Federation", "SystemEconomy":"$economy_Refinery;", "SystemEconomy_Localised":"Refinery", "SystemGovernment":"$government_Democracy;", "SystemGovernment_Localised":"Democracy", "SystemSecurity":"$SYSTEM_SECURITY_high;", "SystemSecurity_Localised":"High Security", "Population":22780919531, "JumpDist":13.790, "FuelUsed":0.371038, "FuelLevel":5.628962 }"#,
+        r#"{ "timestamp":"2024-10-13T17:51:23Z", "event":"FSDJump", "StarSystem":"Sol", "SystemAddress":10477373803, "StarPos":[0.0,0.0,0.0], "SystemAllegiance":"Federation", "SystemEconomy":"$economy_Refinery;", "SystemEconomy_Localised":"Refinery", "SystemGovernment":"$government_Democracy;", "SystemGovernment_Localised":"Democracy", "SystemSecurity":"$SYSTEM_SECURITY_high;", "SystemSecurity_Localised":"High Security", "Population":22780919531, "JumpDist":13.790, "FuelUsed":0.371038, "FuelLevel":5.628962 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    // an older visit doesn't move the last visit back
+    let known = KnownSystems::new(&lines);
+    assert_eq!(1, known.len());
+    assert_eq!(
+        "2024-10-13T17:55:23Z",
+        known
+            .get(10477373803)
+            .unwrap()
+            .last_visited
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+    );
+    assert_eq!(Some(0.0), known.distance_between(10477373803, 10477373803));
+}
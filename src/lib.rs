@@ -1,31 +1,57 @@
+// EDString is only Copy when interned, the clones are needed without the feature
+#![cfg_attr(feature = "interning", allow(clippy::clone_on_copy))]
+
+pub mod bgs;
+pub mod carrier_jumps;
+pub mod carrier_ledger;
 pub mod codex;
+pub mod codex_tracker;
+pub mod colonisation;
+pub mod combat;
 pub mod commander;
 pub mod common_types;
 pub mod community_goal;
+pub mod conflicts;
 pub mod docking;
 pub mod drone;
+pub mod engineer_tracker;
 pub mod engineers;
 pub mod exploration;
 pub mod fleet_carrier;
+pub mod galaxy;
 pub mod loadout;
 pub mod location;
 pub mod locker;
 pub mod log_line;
 pub mod market;
+pub mod material_trader;
 pub mod materials;
+pub mod mining;
 pub mod mission;
 pub mod modules;
 pub mod navigation;
+pub mod on_foot_inventory;
 pub mod powerplay;
+pub mod powerplay_atlas;
+pub mod powerplay_tracker;
+pub mod rank;
+pub mod ring_catalog;
+pub mod sessions;
 pub mod ship;
 pub mod ship_module;
 pub mod ship_type;
 pub mod shipyard;
+pub mod stations;
 pub mod statistics;
+pub mod suit_upgrades;
 pub mod suits;
 pub mod supercruise;
+pub mod synthesis;
+pub mod system_address;
 pub mod transport;
+pub mod travel;
 pub(crate) mod utils;
+pub mod vouchers;
 pub mod wing;
 
 pub use utils::to_human_readable_string;
@@ -1,27 +1,27 @@
-use crate::EDString;
+use crate::{EDString, common_types::OnFootItem};
 use ed_parse_log_files_macros::{Extractable, testcase};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct ShipLockerItem {
-    name: EDString,
+    pub name: OnFootItem,
     #[serde(rename = "Name_Localised")]
-    name_localised: Option<EDString>,
+    pub name_localised: Option<EDString>,
     #[serde(rename = "OwnerID")]
-    owner_id: u64,
+    pub owner_id: u64,
     #[serde(rename = "MissionID")]
-    mission_id: Option<u64>,
-    count: u64,
+    pub mission_id: Option<u64>,
+    pub count: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct LockerContent {
-    items: Vec<ShipLockerItem>,
-    components: Vec<ShipLockerItem>,
-    consumables: Vec<ShipLockerItem>,
-    data: Vec<ShipLockerItem>,
+    pub items: Vec<ShipLockerItem>,
+    pub components: Vec<ShipLockerItem>,
+    pub consumables: Vec<ShipLockerItem>,
+    pub data: Vec<ShipLockerItem>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
@@ -34,5 +34,5 @@ pub struct LockerContent {
     "Data":[ { "Name":"biometricdata", "Name_Localised":"Biometric Data", "OwnerID":0, "Count":8 }] })]
 pub struct EDLogShipLocker {
     #[serde(flatten)]
-    content: Option<LockerContent>,
+    pub content: Option<LockerContent>,
 }
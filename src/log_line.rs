@@ -20,9 +20,9 @@ use crate::{
     },
     docking::{
         EDLogBuyAmmo, EDLogDocked, EDLogDockingCancelled, EDLogDockingDenied, EDLogDockingGranted,
-        EDLogDockingRequested, EDLogDockingTimeout, EDLogMaterialCollected, EDLogMaterialTrade,
-        EDLogOutfitting, EDLogPayBounties, EDLogPayFines, EDLogRefuelAll, EDLogRepair,
-        EDLogRepairAll, EDLogRestockVehicle, EDLogTechnologyBroker, EDLogUndocked,
+        EDLogDockingRequested, EDLogDockingTimeout, EDLogMaterialCollected, EDLogMaterialDiscarded,
+        EDLogMaterialTrade, EDLogOutfitting, EDLogPayBounties, EDLogPayFines, EDLogRefuelAll,
+        EDLogRepair, EDLogRepairAll, EDLogRestockVehicle, EDLogTechnologyBroker, EDLogUndocked,
     },
     drone::{EDLogBuyDrones, EDLogLaunchDrone, EDLogRepairDrone, EDLogSellDrones},
     engineers::{EDLogEngineerContribution, EDLogEngineerCraft, EDLogEngineerProgress},
@@ -91,7 +91,8 @@ use crate::{
         EDLogBackpack, EDLogBackpackChange, EDLogBuySuit, EDLogBuyWeapon, EDLogCollectItems,
         EDLogCreateSuitLoadout, EDLogDeleteSuitLoadout, EDLogDropItems, EDLogLoadoutEquipModule,
         EDLogLoadoutRemoveModule, EDLogRenameSuitLoadout, EDLogSellSuit, EDLogSellWeapon,
-        EDLogSuitLoadout, EDLogUpgradeSuit, EDLogUpgradeWeapon, EDLogUseConsumable,
+        EDLogSuitLoadout, EDLogTransferMicroResources, EDLogUpgradeSuit, EDLogUpgradeWeapon,
+        EDLogUseConsumable,
     },
     supercruise::{EDLogSupercruiseDestinationDrop, EDLogSupercruiseEntry, EDLogSupercruiseExit},
     transport::{
@@ -105,7 +106,7 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use strum::{Display, EnumDiscriminants, EnumIter};
 
-#[derive(Serialize, Deserialize, Clone, Debug, Copy, Display)]
+#[derive(Serialize, Deserialize, Clone, Debug, Copy, PartialEq, Eq, Display)]
 pub enum GameMode {
     Group,
     Solo,
@@ -391,12 +392,12 @@ pub struct EDLogSendText {
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(deny_unknown_fields)]
 pub struct EDLogFileHeader {
-    part: u64,
-    language: EDString,
+    pub part: u64,
+    pub language: EDString,
     #[serde(rename = "Odyssey")]
-    odyssey: Option<bool>,
-    gameversion: EDString,
-    build: EDString,
+    pub odyssey: Option<bool>,
+    pub gameversion: EDString,
+    pub build: EDString,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -443,11 +444,11 @@ pub enum USSType {
 #[serde(deny_unknown_fields)]
 pub struct EDLogUSSDrop {
     #[serde(rename = "USSType")]
-    uss_type: USSType,
+    pub uss_type: USSType,
     #[serde(rename = "USSType_Localised")]
-    uss_type_localised: EDString,
+    pub uss_type_localised: EDString,
     #[serde(rename = "USSThreat")]
-    uss_threat: u64,
+    pub uss_threat: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
@@ -578,6 +579,7 @@ pub enum EDLogEvent {
     Outfitting(EDLogOutfitting),
     MaterialTrade(EDLogMaterialTrade),
     MaterialCollected(EDLogMaterialCollected),
+    MaterialDiscarded(EDLogMaterialDiscarded),
     PayBounties(EDLogPayBounties),
     PayFines(EDLogPayFines),
     TechnologyBroker(EDLogTechnologyBroker),
@@ -730,6 +732,7 @@ pub enum EDLogEvent {
     LoadoutEquipModule(EDLogLoadoutEquipModule),
     UpgradeSuit(EDLogUpgradeSuit),
     DropItems(EDLogDropItems),
+    TransferMicroResources(EDLogTransferMicroResources),
 
     // Transport
     BookDropship(EDLogBookDropship),
@@ -1,12 +1,14 @@
 use crate::{
     EDString,
-    common_types::{CarrierDockingAccess, Credits, StationType},
+    common_types::{CarrierDockingAccess, Credits, OnFootItem, StationType},
 };
 use ed_parse_log_files_macros::{Extractable, testcase, testcase_struct};
 use serde::{Deserialize, Serialize};
 use strum::Display;
 
-#[derive(Serialize, Deserialize, Clone, Debug, Display)]
+#[derive(
+    Serialize, Deserialize, Clone, Copy, Debug, Display, PartialEq, Eq, Hash, PartialOrd, Ord,
+)]
 #[serde(rename_all = "lowercase")]
 pub enum MarketItemType {
     #[serde(alias = "$alexandrite_name;")]
@@ -1252,7 +1254,7 @@ pub struct EDLogMarketSell {
     pub black_market: Option<bool>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum MicroResourceType {
     Data,
     Item,
@@ -1263,11 +1265,11 @@ pub enum MicroResourceType {
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct MicroResource {
-    name: EDString,
+    pub name: OnFootItem,
     #[serde(rename = "Name_Localised")]
-    name_localised: Option<EDString>,
-    category: MicroResourceType,
-    count: u64,
+    pub name_localised: Option<EDString>,
+    pub category: MicroResourceType,
+    pub count: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
@@ -1293,15 +1295,15 @@ pub struct EDLogBuyTradeData {
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogTradeMicroResources {
-    offered: Vec<MicroResource>,
-    total_count: u64,
-    received: EDString,
+    pub offered: Vec<MicroResource>,
+    pub total_count: u64,
+    pub received: OnFootItem,
     #[serde(rename = "Received_Localised")]
-    received_localised: Option<EDString>,
-    count: u64,
-    category: MicroResourceType,
+    pub received_localised: Option<EDString>,
+    pub count: u64,
+    pub category: MicroResourceType,
     #[serde(rename = "MarketID")]
-    market_id: u64,
+    pub market_id: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
@@ -1322,10 +1324,10 @@ pub struct EDLogSellMicroResources {
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogDeliverPowerMicroResources {
-    total_count: u64,
-    micro_resources: Vec<MicroResource>,
+    pub total_count: u64,
+    pub micro_resources: Vec<MicroResource>,
     #[serde(rename = "MarketID")]
-    market_id: u64,
+    pub market_id: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Display)]
@@ -1372,7 +1374,7 @@ pub enum MarketItemCategory {
 #[testcase_struct({ "id":128793127, "Name":"$thargoidheart_name;", "Name_Localised":"Thargoid Heart", "Category":"$MARKET_category_salvage;", "Category_Localised":"Salvage", "BuyPrice":106696, "SellPrice":105639, "MeanPrice":140275, "StockBracket":0, "DemandBracket":0, "Stock":0, "Demand":0, "Consumer":false, "Producer":false, "Rare":false })]
 pub struct MarketItem {
     #[serde(rename = "id")]
-    id: u64,
+    pub id: u64,
     #[serde(rename = "Name")]
     pub market_item_name: MarketItemType,
     #[serde(rename = "Name_Localised")]
@@ -1380,16 +1382,16 @@ pub struct MarketItem {
     pub category: MarketItemCategory,
     #[serde(rename = "Category_Localised")]
     pub category_localised: Option<EDString>,
-    buy_price: Credits,
-    sell_price: Credits,
-    mean_price: Credits,
-    stock_bracket: u64,
-    demand_bracket: u64,
-    stock: u64,
-    demand: u64,
-    consumer: bool,
-    producer: bool,
-    rare: bool,
+    pub buy_price: Credits,
+    pub sell_price: Credits,
+    pub mean_price: Credits,
+    pub stock_bracket: u64,
+    pub demand_bracket: u64,
+    pub stock: u64,
+    pub demand: u64,
+    pub consumer: bool,
+    pub producer: bool,
+    pub rare: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
@@ -1431,7 +1433,7 @@ pub struct EDLogCargoDepot {
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct RequiredResource {
-    pub name: EDString,
+    pub name: MarketItemType,
     #[serde(rename = "Name_Localised")]
     pub name_localised: Option<EDString>,
     pub required_amount: u64,
@@ -1442,10 +1444,10 @@ pub struct RequiredResource {
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct ContributedResource {
-    name: EDString,
+    pub name: MarketItemType,
     #[serde(rename = "Name_Localised")]
-    name_localised: Option<EDString>,
-    amount: u64,
+    pub name_localised: Option<EDString>,
+    pub amount: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
@@ -1463,8 +1465,8 @@ pub struct EDLogColonisationConstructionDepot {
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogColonisationContribution {
     #[serde(rename = "MarketID")]
-    market_id: u64,
-    contributions: Vec<ContributedResource>,
+    pub market_id: u64,
+    pub contributions: Vec<ContributedResource>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -0,0 +1,348 @@
+use crate::{
+    EDString,
+    common_types::{StationService, TraderType},
+    galaxy::{KnownSystems, distance},
+    log_line::{EDLogEvent, EDLogLine},
+    materials::{MaterialInfo, TRADEABLE_MATERIALS, material_info},
+};
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// Number of units paid and received for one exchange at a material trader.
+///
+/// Trading up a grade costs 6 units, trading down a grade gives 3 units, and
+/// trading into another family of the same trader costs an extra factor 6.
+/// Returns None if the materials are not handled by the same trader.
+pub fn exchange_rate(paid: &MaterialInfo, received: &MaterialInfo) -> Option<(u64, u64)> {
+    if paid.trader != received.trader || paid.name == received.name {
+        return None;
+    }
+
+    let mut pay = if paid.family == received.family { 1 } else { 6 };
+    let mut get = 1;
+    if received.grade > paid.grade {
+        pay *= 6u64.pow((received.grade - paid.grade) as u32);
+    } else {
+        get *= 3u64.pow((paid.grade - received.grade) as u32);
+    }
+
+    let divisor = gcd(pay, get);
+    Some((pay / divisor, get / divisor))
+}
+
+// value of one unit expressed in grade 1 units of the same family
+fn grade_value(material: &MaterialInfo) -> u64 {
+    6u64.pow(material.grade as u32 - 1)
+}
+
+#[derive(Clone, Debug)]
+pub struct MaterialExchange {
+    pub trader: TraderType,
+    pub paid: &'static MaterialInfo,
+    pub paid_quantity: u64,
+    pub received: &'static MaterialInfo,
+    pub received_quantity: u64,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct MaterialTradePlan {
+    pub exchanges: Vec<MaterialExchange>,
+    /// materials that cannot be traded for with the current inventory
+    pub shortfall: Vec<(String, u64)>,
+}
+
+impl MaterialTradePlan {
+    /// Plan the cheapest exchanges to get the `needed` materials from `inventory`.
+    ///
+    /// The inventory is keyed by lowercase material name, see [`crate::materials::EDLogMaterials::counts`].
+    /// Needed materials are never used to pay for other exchanges.
+    pub fn new(inventory: &HashMap<String, u64>, needed: &[(&str, u64)]) -> MaterialTradePlan {
+        let mut plan = MaterialTradePlan::default();
+
+        let mut reserved: HashMap<String, u64> = HashMap::new();
+        for (name, count) in needed {
+            *reserved.entry(name.to_lowercase()).or_default() += count;
+        }
+
+        let mut surplus: HashMap<&'static str, u64> = TRADEABLE_MATERIALS
+            .iter()
+            .map(|m| {
+                let have = inventory.get(m.name).copied().unwrap_or(0);
+                let keep = reserved.get(m.name).copied().unwrap_or(0);
+                (m.name, have.saturating_sub(keep))
+            })
+            .collect();
+
+        let mut deficits: Vec<(String, u64)> = reserved
+            .iter()
+            .map(|(name, count)| {
+                let have = inventory.get(name).copied().unwrap_or(0);
+                (name.clone(), count.saturating_sub(have))
+            })
+            .filter(|(_, deficit)| *deficit > 0)
+            .collect();
+        // trade for the most expensive materials first
+        deficits.sort_by_key(|(name, _)| {
+            std::cmp::Reverse(material_info(name).map(|m| m.grade).unwrap_or(0))
+        });
+
+        for (name, mut deficit) in deficits {
+            let Some(target) = material_info(&name) else {
+                plan.shortfall.push((name, deficit));
+                continue;
+            };
+
+            while deficit > 0 {
+                let best = TRADEABLE_MATERIALS
+                    .iter()
+                    .filter_map(|source| {
+                        let (pay, get) = exchange_rate(source, target)?;
+                        let available = surplus.get(source.name).copied().unwrap_or(0);
+                        (available >= pay).then_some((source, pay, get))
+                    })
+                    .min_by(|a, b| {
+                        let cost_a = (a.1 * grade_value(a.0)) as f64 / a.2 as f64;
+                        let cost_b = (b.1 * grade_value(b.0)) as f64 / b.2 as f64;
+                        cost_a.total_cmp(&cost_b)
+                    });
+
+                let Some((source, pay, get)) = best else {
+                    plan.shortfall.push((name.clone(), deficit));
+                    break;
+                };
+
+                let available = surplus[source.name];
+                let lots = deficit.div_ceil(get).min(available / pay);
+                surplus.insert(source.name, available - lots * pay);
+                deficit = deficit.saturating_sub(lots * get);
+                plan.exchanges.push(MaterialExchange {
+                    trader: target.trader,
+                    paid: source,
+                    paid_quantity: lots * pay,
+                    received: target,
+                    received_quantity: lots * get,
+                });
+            }
+        }
+
+        plan
+    }
+
+    pub fn by_trader(&self) -> BTreeMap<TraderType, Vec<&MaterialExchange>> {
+        let mut result: BTreeMap<TraderType, Vec<&MaterialExchange>> = BTreeMap::new();
+        for exchange in &self.exchanges {
+            result.entry(exchange.trader).or_default().push(exchange);
+        }
+        result
+    }
+}
+
+/// The trader type of a station is only known after trading there. Until then
+/// it is derived from the economy of the station.
+pub fn trader_type_for_economy(economy: &str) -> Option<TraderType> {
+    let economy = economy.to_lowercase();
+    if economy.contains("extraction") || economy.contains("refinery") {
+        Some(TraderType::Raw)
+    } else if economy.contains("industrial") {
+        Some(TraderType::Manufactured)
+    } else if economy.contains("hightech") || economy.contains("military") {
+        Some(TraderType::Encoded)
+    } else {
+        None
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct KnownMaterialTrader {
+    pub market_id: u64,
+    pub station_name: EDString,
+    pub star_system: EDString,
+    pub system_address: u64,
+    pub trader_type: Option<TraderType>,
+    pub trader_type_confirmed: bool,
+    pub last_docked: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct MaterialTraders {
+    traders: HashMap<u64, KnownMaterialTrader>,
+}
+
+impl MaterialTraders {
+    pub fn new(lines: &[EDLogLine]) -> MaterialTraders {
+        let mut result = MaterialTraders::default();
+        let mut confirmed: HashMap<u64, TraderType> = HashMap::new();
+
+        for line in lines {
+            match line.event() {
+                EDLogEvent::Docked(docked) => {
+                    let Some(market_id) = docked.station_identification.market_id else {
+                        continue;
+                    };
+                    if !docked
+                        .station_services
+                        .iter()
+                        .any(|s| matches!(s, StationService::MaterialTrader))
+                    {
+                        continue;
+                    }
+                    result.traders.insert(
+                        market_id,
+                        KnownMaterialTrader {
+                            market_id,
+                            station_name: docked.station_identification.station_name.clone(),
+                            star_system: docked.star_system.clone(),
+                            system_address: docked.system_address,
+                            trader_type: trader_type_for_economy(docked.station_economy.as_str()),
+                            trader_type_confirmed: false,
+                            last_docked: *line.timestamp(),
+                        },
+                    );
+                }
+                EDLogEvent::MaterialTrade(trade) => {
+                    confirmed.insert(trade.market_id, trade.trader_type);
+                }
+                _ => {}
+            }
+        }
+
+        for (market_id, trader_type) in confirmed {
+            if let Some(trader) = result.traders.get_mut(&market_id) {
+                trader.trader_type = Some(trader_type);
+                trader.trader_type_confirmed = true;
+            }
+        }
+
+        result
+    }
+
+    pub fn get(&self, market_id: u64) -> Option<&KnownMaterialTrader> {
+        self.traders.get(&market_id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &KnownMaterialTrader> {
+        self.traders.values()
+    }
+
+    /// nearest known trader of the given type and its distance in light years
+    pub fn nearest(
+        &self,
+        trader_type: TraderType,
+        from: &[f64; 3],
+        systems: &KnownSystems,
+    ) -> Option<(&KnownMaterialTrader, f64)> {
+        self.traders
+            .values()
+            .filter(|t| t.trader_type == Some(trader_type))
+            .filter_map(|t| Some((t, distance(from, &systems.position(t.system_address)?))))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    }
+}
+
+#[test]
+fn test_exchange_rate() {
+    let grade = |name| material_info(name).unwrap();
+
+    // upgrade in same family
+    assert_eq!(
+        Some((6, 1)),
+        exchange_rate(grade("carbon"), grade("vanadium"))
+    );
+    // downgrade in same family
+    assert_eq!(
+        Some((1, 9)),
+        exchange_rate(grade("niobium"), grade("carbon"))
+    );
+    // other family, same grade
+    assert_eq!(Some((6, 1)), exchange_rate(grade("carbon"), grade("iron")));
+    // other family, one grade lower
+    assert_eq!(Some((2, 1)), exchange_rate(grade("zinc"), grade("carbon")));
+    // different trader
+    assert_eq!(None, exchange_rate(grade("carbon"), grade("heatvanes")));
+}
+
+#[test]
+fn test_material_trade_plan() {
+    let inventory: HashMap<String, u64> = [
+        ("heatexchangers".to_string(), 12),
+        ("temperedalloys".to_string(), 100),
+        ("carbon".to_string(), 3),
+    ]
+    .into_iter()
+    .collect();
+
+    let plan = MaterialTradePlan::new(&inventory, &[("heatvanes", 2), ("carbon", 5)]);
+    let by_trader = plan.by_trader();
+
+    let manufactured = &by_trader[&TraderType::Manufactured];
+    assert_eq!(1, manufactured.len());
+    assert_eq!("heatexchangers", manufactured[0].paid.name);
+    assert_eq!(12, manufactured[0].paid_quantity);
+    assert_eq!(2, manufactured[0].received_quantity);
+
+    // no raw materials to trade from
+    assert!(!by_trader.contains_key(&TraderType::Raw));
+    assert_eq!(vec![("carbon".to_string(), 2)], plan.shortfall);
+}
+
+#[test]
+fn test_material_trade_plan_nothing_to_trade() {
+    let plan = MaterialTradePlan::new(&HashMap::new(), &[]);
+    assert!(plan.exchanges.is_empty());
+    assert!(plan.shortfall.is_empty());
+
+    // enough in stock already
+    let inventory: HashMap<String, u64> = [("carbon".to_string(), 10)].into_iter().collect();
+    let plan = MaterialTradePlan::new(&inventory, &[("Carbon", 5)]);
+    assert!(plan.exchanges.is_empty());
+    assert!(plan.shortfall.is_empty());
+
+    // not a tradeable material
+    let plan = MaterialTradePlan::new(&inventory, &[("unobtainium", 1)]);
+    assert!(plan.exchanges.is_empty());
+    assert_eq!(vec![("unobtainium".to_string(), 1)], plan.shortfall);
+}
+
+#[test]
+fn test_material_traders() {
+    assert_eq!(0, MaterialTraders::new(&[]).iter().count());
+
+    let json = [
+        // traded before the station was seen docking, e.g. the first line of a partial log
+        r#"{ "timestamp":"2025-04-02T18:10:00Z", "event":"MaterialTrade", "MarketID":3228342528, "TraderType":"encoded", "Paid":{ "Material":"scandatabanks", "Material_Localised":"Classified Scan Databanks", "Category":"Encoded", "Quantity":6 }, "Received":{ "Material":"shielddensityreports", "Material_Localised":"Untypical Shield Scans ", "Category":"Encoded", "Quantity":1 } }"#,
+        r#"{ "timestamp":"2025-04-02T18:20:00Z", "event":"Docked", "StationName":"McDevitt Laboratory", "StationType":"Outpost", "Taxi":false, "Multicrew":false, "StarSystem":"Kholul", "SystemAddress":2415659059547, "MarketID":3228342528, "StationFaction":{ "Name":"SI Terraforming" }, "StationGovernment":"$government_Corporate;", "StationGovernment_Localised":"Corporate", "StationAllegiance":"Independent", "StationServices":[ "dock", "autodock", "commodities", "facilitator", "materialtrader" ], "StationEconomy":"$economy_Industrial;", "StationEconomy_Localised":"Industrial", "StationEconomies":[ { "Name":"$economy_Industrial;", "Name_Localised":"Industrial", "Proportion":1.0 } ], "DistFromStarLS":320.5, "LandingPads":{ "Small":2, "Medium":4, "Large":0 } }"#,
+        r#"{ "timestamp":"2025-04-02T18:40:00Z", "event":"Docked", "StationName":"Rescue Ship", "StationType":"Outpost", "Taxi":false, "Multicrew":false, "StarSystem":"Kholul", "SystemAddress":2415659059547, "MarketID":3228342529, "StationFaction":{ "Name":"SI Terraforming" }, "StationGovernment":"$government_Corporate;", "StationGovernment_Localised":"Corporate", "StationAllegiance":"Independent", "StationServices":[ "dock", "autodock", "commodities" ], "StationEconomy":"$economy_Industrial;", "StationEconomy_Localised":"Industrial", "StationEconomies":[ { "Name":"$economy_Industrial;", "Name_Localised":"Industrial", "Proportion":1.0 } ], "DistFromStarLS":320.5, "LandingPads":{ "Small":2, "Medium":4, "Large":0 } }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let traders = MaterialTraders::new(&lines);
+    // the second station has no material trader
+    assert_eq!(1, traders.iter().count());
+    let trader = traders.get(3228342528).unwrap();
+    assert_eq!(Some(TraderType::Encoded), trader.trader_type);
+    assert!(trader.trader_type_confirmed);
+
+    // no position known for Kholul
+    assert!(
+        traders
+            .nearest(
+                TraderType::Encoded,
+                &crate::galaxy::SOL,
+                &KnownSystems::default()
+            )
+            .is_none()
+    );
+    assert!(
+        traders
+            .nearest(
+                TraderType::Raw,
+                &crate::galaxy::SOL,
+                &KnownSystems::new(&lines)
+            )
+            .is_none()
+    );
+}
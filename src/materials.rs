@@ -1,29 +1,746 @@
 use ed_parse_log_files_macros::{Extractable, testcase_struct};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
-use crate::EDString;
+use crate::{
+    EDString,
+    common_types::TraderType::{self, Encoded as E, Manufactured as M, Raw as R},
+    engineers::EngineerContributionType,
+    log_line::{EDLogEvent, EDLogLine},
+};
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct RawMaterial {
-    name: EDString,
-    count: u64,
+    pub name: EDString,
+    pub count: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 #[testcase_struct({ "Name":"decodedemissiondata", "Count":9 })]
 pub struct NonRawMaterial {
-    name: EDString,
+    pub name: EDString,
     #[serde(rename = "Name_Localised")]
-    name_localised: Option<EDString>,
-    count: u64,
+    pub name_localised: Option<EDString>,
+    pub count: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogMaterials {
-    raw: Vec<RawMaterial>,
-    manufactured: Vec<NonRawMaterial>,
-    encoded: Vec<NonRawMaterial>,
+    pub raw: Vec<RawMaterial>,
+    pub manufactured: Vec<NonRawMaterial>,
+    pub encoded: Vec<NonRawMaterial>,
+}
+
+impl EDLogMaterials {
+    /// counts of all materials keyed by their lowercase journal name
+    pub fn counts(&self) -> HashMap<String, u64> {
+        self.raw
+            .iter()
+            .map(|m| (m.name.as_str().to_lowercase(), m.count))
+            .chain(
+                self.manufactured
+                    .iter()
+                    .chain(self.encoded.iter())
+                    .map(|m| (m.name.as_str().to_lowercase(), m.count)),
+            )
+            .collect()
+    }
+}
+
+/// Material counts from the latest Materials event, updated with the materials
+/// collected, rewarded, traded, discarded and spent after it. Lines should be sorted by timestamp.
+pub fn current_material_counts(lines: &[EDLogLine]) -> Option<HashMap<String, u64>> {
+    let mut counts: Option<HashMap<String, u64>> = None;
+
+    for line in lines {
+        if let EDLogEvent::Materials(materials) = line.event() {
+            counts = Some(materials.counts());
+            continue;
+        }
+        let Some(counts) = counts.as_mut() else {
+            continue;
+        };
+
+        let mut add = |name: &str, count: u64| {
+            *counts.entry(name.to_lowercase()).or_default() += count;
+        };
+        match line.event() {
+            EDLogEvent::MaterialCollected(collected) => {
+                add(collected.name.as_str(), collected.count)
+            }
+            EDLogEvent::MaterialTrade(trade) => {
+                add(trade.received.material.as_str(), trade.received.quantity)
+            }
+            EDLogEvent::MissionCompleted(mission) => mission
+                .materials_reward
+                .iter()
+                .flatten()
+                .for_each(|m| add(m.name.as_str(), m.count)),
+            _ => {}
+        }
+
+        let mut remove = |name: &str, count: u64| {
+            let current = counts.entry(name.to_lowercase()).or_default();
+            *current = current.saturating_sub(count);
+        };
+        match line.event() {
+            EDLogEvent::MaterialTrade(trade) => {
+                remove(trade.paid.material.as_str(), trade.paid.quantity)
+            }
+            EDLogEvent::Synthesis(synthesis) => synthesis
+                .materials
+                .iter()
+                .for_each(|m| remove(m.name.as_str(), m.count)),
+            EDLogEvent::EngineerCraft(craft) => craft
+                .ingredients
+                .iter()
+                .for_each(|m| remove(m.name.as_str(), m.count)),
+            EDLogEvent::MaterialDiscarded(discarded) => {
+                remove(discarded.name.as_str(), discarded.count)
+            }
+            EDLogEvent::TechnologyBroker(broker) => broker
+                .materials
+                .iter()
+                .for_each(|m| remove(m.name.as_str(), m.count)),
+            EDLogEvent::EngineerContribution(contribution)
+                if contribution.contribution_type == EngineerContributionType::Materials =>
+            {
+                if let Some(material) = &contribution.material {
+                    remove(material.as_str(), contribution.quantity)
+                }
+            }
+            _ => {}
+        }
+    }
+
+    counts
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MaterialInfo {
+    pub name: &'static str,
+    pub localised: &'static str,
+    pub trader: TraderType,
+    pub family: &'static str,
+    pub grade: u8,
+}
+
+impl MaterialInfo {
+    /// maximum number of units of this grade that can be stored
+    pub fn storage_limit(&self) -> u64 {
+        match self.grade {
+            1 => 300,
+            2 => 250,
+            3 => 200,
+            4 => 150,
+            _ => 100,
+        }
+    }
+}
+
+const fn material(
+    name: &'static str,
+    localised: &'static str,
+    trader: TraderType,
+    family: &'static str,
+    grade: u8,
+) -> MaterialInfo {
+    MaterialInfo {
+        name,
+        localised,
+        trader,
+        family,
+        grade,
+    }
+}
+
+/// materials that can be exchanged at a material trader
+pub const TRADEABLE_MATERIALS: [MaterialInfo; 108] = [
+    material("carbon", "Carbon", R, "Raw 1", 1),
+    material("vanadium", "Vanadium", R, "Raw 1", 2),
+    material("niobium", "Niobium", R, "Raw 1", 3),
+    material("yttrium", "Yttrium", R, "Raw 1", 4),
+    material("phosphorus", "Phosphorus", R, "Raw 2", 1),
+    material("chromium", "Chromium", R, "Raw 2", 2),
+    material("molybdenum", "Molybdenum", R, "Raw 2", 3),
+    material("technetium", "Technetium", R, "Raw 2", 4),
+    material("sulphur", "Sulphur", R, "Raw 3", 1),
+    material("manganese", "Manganese", R, "Raw 3", 2),
+    material("cadmium", "Cadmium", R, "Raw 3", 3),
+    material("ruthenium", "Ruthenium", R, "Raw 3", 4),
+    material("iron", "Iron", R, "Raw 4", 1),
+    material("zinc", "Zinc", R, "Raw 4", 2),
+    material("tin", "Tin", R, "Raw 4", 3),
+    material("selenium", "Selenium", R, "Raw 4", 4),
+    material("nickel", "Nickel", R, "Raw 5", 1),
+    material("germanium", "Germanium", R, "Raw 5", 2),
+    material("tungsten", "Tungsten", R, "Raw 5", 3),
+    material("tellurium", "Tellurium", R, "Raw 5", 4),
+    material("rhenium", "Rhenium", R, "Raw 6", 1),
+    material("arsenic", "Arsenic", R, "Raw 6", 2),
+    material("mercury", "Mercury", R, "Raw 6", 3),
+    material("polonium", "Polonium", R, "Raw 6", 4),
+    material("lead", "Lead", R, "Raw 7", 1),
+    material("zirconium", "Zirconium", R, "Raw 7", 2),
+    material("boron", "Boron", R, "Raw 7", 3),
+    material("antimony", "Antimony", R, "Raw 7", 4),
+    material(
+        "chemicalstorageunits",
+        "Chemical Storage Units",
+        M,
+        "Chemical",
+        1,
+    ),
+    material(
+        "chemicalprocessors",
+        "Chemical Processors",
+        M,
+        "Chemical",
+        2,
+    ),
+    material(
+        "chemicaldistillery",
+        "Chemical Distillery",
+        M,
+        "Chemical",
+        3,
+    ),
+    material(
+        "chemicalmanipulators",
+        "Chemical Manipulators",
+        M,
+        "Chemical",
+        4,
+    ),
+    material(
+        "pharmaceuticalisolators",
+        "Pharmaceutical Isolators",
+        M,
+        "Chemical",
+        5,
+    ),
+    material("temperedalloys", "Tempered Alloys", M, "Thermic", 1),
+    material(
+        "heatresistantceramics",
+        "Heat Resistant Ceramics",
+        M,
+        "Thermic",
+        2,
+    ),
+    material("precipitatedalloys", "Precipitated Alloys", M, "Thermic", 3),
+    material("thermicalloys", "Thermic Alloys", M, "Thermic", 4),
+    material(
+        "militarygradealloys",
+        "Military Grade Alloys",
+        M,
+        "Thermic",
+        5,
+    ),
+    material(
+        "heatconductionwiring",
+        "Heat Conduction Wiring",
+        M,
+        "Heat",
+        1,
+    ),
+    material("heatdispersionplate", "Heat Dispersion Plate", M, "Heat", 2),
+    material("heatexchangers", "Heat Exchangers", M, "Heat", 3),
+    material("heatvanes", "Heat Vanes", M, "Heat", 4),
+    material("protoheatradiators", "Proto Heat Radiators", M, "Heat", 5),
+    material("basicconductors", "Basic Conductors", M, "Conductive", 1),
+    material(
+        "conductivecomponents",
+        "Conductive Components",
+        M,
+        "Conductive",
+        2,
+    ),
+    material(
+        "conductiveceramics",
+        "Conductive Ceramics",
+        M,
+        "Conductive",
+        3,
+    ),
+    material(
+        "conductivepolymers",
+        "Conductive Polymers",
+        M,
+        "Conductive",
+        4,
+    ),
+    material(
+        "biotechconductors",
+        "Biotech Conductors",
+        M,
+        "Conductive",
+        5,
+    ),
+    material(
+        "mechanicalscrap",
+        "Mechanical Scrap",
+        M,
+        "Mechanical Components",
+        1,
+    ),
+    material(
+        "mechanicalequipment",
+        "Mechanical Equipment",
+        M,
+        "Mechanical Components",
+        2,
+    ),
+    material(
+        "mechanicalcomponents",
+        "Mechanical Components",
+        M,
+        "Mechanical Components",
+        3,
+    ),
+    material(
+        "configurablecomponents",
+        "Configurable Components",
+        M,
+        "Mechanical Components",
+        4,
+    ),
+    material(
+        "improvisedcomponents",
+        "Improvised Components",
+        M,
+        "Mechanical Components",
+        5,
+    ),
+    material("gridresistors", "Grid Resistors", M, "Capacitors", 1),
+    material("hybridcapacitors", "Hybrid Capacitors", M, "Capacitors", 2),
+    material(
+        "electrochemicalarrays",
+        "Electrochemical Arrays",
+        M,
+        "Capacitors",
+        3,
+    ),
+    material(
+        "polymercapacitors",
+        "Polymer Capacitors",
+        M,
+        "Capacitors",
+        4,
+    ),
+    material(
+        "militarysupercapacitors",
+        "Military Supercapacitors",
+        M,
+        "Capacitors",
+        5,
+    ),
+    material(
+        "wornshieldemitters",
+        "Worn Shield Emitters",
+        M,
+        "Shielding",
+        1,
+    ),
+    material("shieldemitters", "Shield Emitters", M, "Shielding", 2),
+    material("shieldingsensors", "Shielding Sensors", M, "Shielding", 3),
+    material("compoundshielding", "Compound Shielding", M, "Shielding", 4),
+    material("imperialshielding", "Imperial Shielding", M, "Shielding", 5),
+    material("compactcomposites", "Compact Composites", M, "Composite", 1),
+    material(
+        "filamentcomposites",
+        "Filament Composites",
+        M,
+        "Composite",
+        2,
+    ),
+    material(
+        "highdensitycomposites",
+        "High Density Composites",
+        M,
+        "Composite",
+        3,
+    ),
+    material(
+        "fedproprietarycomposites",
+        "Proprietary Composites",
+        M,
+        "Composite",
+        4,
+    ),
+    material(
+        "fedcorecomposites",
+        "Core Dynamics Composites",
+        M,
+        "Composite",
+        5,
+    ),
+    material("crystalshards", "Crystal Shards", M, "Crystals", 1),
+    material(
+        "uncutfocuscrystals",
+        "Flawed Focus Crystals",
+        M,
+        "Crystals",
+        2,
+    ),
+    material("focuscrystals", "Focus Crystals", M, "Crystals", 3),
+    material(
+        "refinedfocuscrystals",
+        "Refined Focus Crystals",
+        M,
+        "Crystals",
+        4,
+    ),
+    material(
+        "exquisitefocuscrystals",
+        "Exquisite Focus Crystals",
+        M,
+        "Crystals",
+        5,
+    ),
+    material("salvagedalloys", "Salvaged Alloys", M, "Alloys", 1),
+    material("galvanisingalloys", "Galvanising Alloys", M, "Alloys", 2),
+    material("phasealloys", "Phase Alloys", M, "Alloys", 3),
+    material("protolightalloys", "Proto Light Alloys", M, "Alloys", 4),
+    material(
+        "protoradiolicalloys",
+        "Proto Radiolic Alloys",
+        M,
+        "Alloys",
+        5,
+    ),
+    material(
+        "scrambledemissiondata",
+        "Exceptional Scrambled Emission Data",
+        E,
+        "Emission Data",
+        1,
+    ),
+    material(
+        "archivedemissiondata",
+        "Irregular Emission Data",
+        E,
+        "Emission Data",
+        2,
+    ),
+    material(
+        "emissiondata",
+        "Unexpected Emission Data",
+        E,
+        "Emission Data",
+        3,
+    ),
+    material(
+        "decodedemissiondata",
+        "Decoded Emission Data",
+        E,
+        "Emission Data",
+        4,
+    ),
+    material(
+        "compactemissionsdata",
+        "Abnormal Compact Emissions Data",
+        E,
+        "Emission Data",
+        5,
+    ),
+    material(
+        "disruptedwakeechoes",
+        "Atypical Disrupted Wake Echoes",
+        E,
+        "Wake Scans",
+        1,
+    ),
+    material(
+        "fsdtelemetry",
+        "Anomalous FSD Telemetry",
+        E,
+        "Wake Scans",
+        2,
+    ),
+    material(
+        "wakesolutions",
+        "Strange Wake Solutions",
+        E,
+        "Wake Scans",
+        3,
+    ),
+    material(
+        "hyperspacetrajectories",
+        "Eccentric Hyperspace Trajectories",
+        E,
+        "Wake Scans",
+        4,
+    ),
+    material(
+        "dataminedwake",
+        "Datamined Wake Exceptions",
+        E,
+        "Wake Scans",
+        5,
+    ),
+    material(
+        "shieldcyclerecordings",
+        "Distorted Shield Cycle Recordings",
+        E,
+        "Shield Data",
+        1,
+    ),
+    material(
+        "shieldsoakanalysis",
+        "Inconsistent Shield Soak Analysis",
+        E,
+        "Shield Data",
+        2,
+    ),
+    material(
+        "shielddensityreports",
+        "Untypical Shield Scans",
+        E,
+        "Shield Data",
+        3,
+    ),
+    material(
+        "shieldpatternanalysis",
+        "Aberrant Shield Pattern Analysis",
+        E,
+        "Shield Data",
+        4,
+    ),
+    material(
+        "shieldfrequencydata",
+        "Peculiar Shield Frequency Data",
+        E,
+        "Shield Data",
+        5,
+    ),
+    material(
+        "encryptedfiles",
+        "Unusual Encrypted Files",
+        E,
+        "Encryption Files",
+        1,
+    ),
+    material(
+        "encryptioncodes",
+        "Tagged Encryption Codes",
+        E,
+        "Encryption Files",
+        2,
+    ),
+    material(
+        "symmetrickeys",
+        "Open Symmetric Keys",
+        E,
+        "Encryption Files",
+        3,
+    ),
+    material(
+        "encryptionarchives",
+        "Atypical Encryption Archives",
+        E,
+        "Encryption Files",
+        4,
+    ),
+    material(
+        "adaptiveencryptors",
+        "Adaptive Encryptors Capture",
+        E,
+        "Encryption Files",
+        5,
+    ),
+    material(
+        "bulkscandata",
+        "Anomalous Bulk Scan Data",
+        E,
+        "Data Archives",
+        1,
+    ),
+    material(
+        "scanarchives",
+        "Unidentified Scan Archives",
+        E,
+        "Data Archives",
+        2,
+    ),
+    material(
+        "scandatabanks",
+        "Classified Scan Databanks",
+        E,
+        "Data Archives",
+        3,
+    ),
+    material(
+        "encodedscandata",
+        "Divergent Scan Data",
+        E,
+        "Data Archives",
+        4,
+    ),
+    material(
+        "classifiedscandata",
+        "Classified Scan Fragment",
+        E,
+        "Data Archives",
+        5,
+    ),
+    material(
+        "legacyfirmware",
+        "Specialised Legacy Firmware",
+        E,
+        "Encoded Firmware",
+        1,
+    ),
+    material(
+        "consumerfirmware",
+        "Modified Consumer Firmware",
+        E,
+        "Encoded Firmware",
+        2,
+    ),
+    material(
+        "industrialfirmware",
+        "Cracked Industrial Firmware",
+        E,
+        "Encoded Firmware",
+        3,
+    ),
+    material(
+        "securityfirmware",
+        "Security Firmware Patch",
+        E,
+        "Encoded Firmware",
+        4,
+    ),
+    material(
+        "embeddedfirmware",
+        "Modified Embedded Firmware",
+        E,
+        "Encoded Firmware",
+        5,
+    ),
+];
+
+pub fn material_info(name: &str) -> Option<&'static MaterialInfo> {
+    TRADEABLE_MATERIALS
+        .iter()
+        .find(|m| m.name.eq_ignore_ascii_case(name))
+}
+
+#[test]
+fn test_material_info() {
+    let info = material_info("DecodedEmissionData").expect("should be known");
+    assert_eq!(TraderType::Encoded, info.trader);
+    assert_eq!(4, info.grade);
+    assert_eq!(150, info.storage_limit());
+    assert!(material_info("unknownartifact").is_none());
+
+    for trader in [
+        TraderType::Raw,
+        TraderType::Manufactured,
+        TraderType::Encoded,
+    ] {
+        let max_grade = if trader == TraderType::Raw { 4 } else { 5 };
+        assert!(
+            TRADEABLE_MATERIALS
+                .iter()
+                .filter(|m| m.trader == trader)
+                .all(|m| (1..=max_grade).contains(&m.grade))
+        );
+    }
+}
+
+#[test]
+fn test_current_material_counts() {
+    let json = [
+        r#"{ "timestamp":"2024-03-10T18:19:38Z", "event":"Materials", "Raw":[ { "Name":"carbon", "Count":20 } ], "Manufactured":[], "Encoded":[ { "Name":"decodedemissiondata", "Name_Localised":"Decoded Emission Data", "Count":9 } ] }"#,
+        r#"{ "timestamp":"2024-03-10T18:20:38Z", "event":"MaterialCollected", "Category":"Raw", "Name":"carbon", "Count":3 }"#,
+        r#"{ "timestamp":"2024-03-10T18:21:38Z", "event":"Synthesis", "Name":"FSD Basic", "Materials":[ { "Name":"carbon", "Count":5 }, { "Name":"vanadium", "Count":3 }, { "Name":"germanium", "Count":1 } ] }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    assert!(current_material_counts(&lines[1..]).is_none());
+
+    let counts = current_material_counts(&lines).unwrap();
+    assert_eq!(Some(&18), counts.get("carbon"));
+    assert_eq!(Some(&0), counts.get("vanadium"));
+    assert_eq!(Some(&9), counts.get("decodedemissiondata"));
+}
+
+#[cfg(test)]
+const MATERIALS_SNAPSHOT: &str = r#"{ "timestamp":"2024-03-10T18:19:38Z", "event":"Materials", "Raw":[ { "Name":"iron", "Count":20 } ], "Manufactured":[ { "Name":"guardian_sentinel_weaponparts", "Name_Localised":"Guardian Sentinel Weapon Parts", "Count":30 } ], "Encoded":[ { "Name":"shieldpatternanalysis", "Name_Localised":"Aberrant Shield Pattern Analysis", "Count":60 } ] }"#;
+
+#[test]
+fn test_material_discarded() {
+    let json = [
+        MATERIALS_SNAPSHOT,
+        r#"{ "timestamp":"2024-03-10T18:22:38Z", "event":"MaterialDiscarded", "Category":"Raw", "Name":"iron", "Count":10 }"#,
+        r#"{ "timestamp":"2024-03-10T18:23:38Z", "event":"MaterialDiscarded", "Category":"Raw", "Name":"iron", "Count":15 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let counts = current_material_counts(&lines[..2]).unwrap();
+    assert_eq!(Some(&10), counts.get("iron"));
+    // never below zero
+    let counts = current_material_counts(&lines).unwrap();
+    assert_eq!(Some(&0), counts.get("iron"));
+}
+
+#[test]
+fn test_materials_mission_reward() {
+    let json = [
+        MATERIALS_SNAPSHOT,
+        r#"{ "timestamp":"2024-03-10T18:22:38Z", "event":"MissionCompleted", "Faction":"CdE Corporation", "Name":"Mission_Delivery_name", "LocalisedName":"Deliver 20 units of Gold", "MissionID":12341235, "Reward":250000, "MaterialsReward":[ { "Name":"iron", "Category":"$MICRORESOURCE_CATEGORY_Raw;", "Category_Localised":"Raw", "Count":6 }, { "Name":"nickel", "Category":"$MICRORESOURCE_CATEGORY_Raw;", "Category_Localised":"Raw", "Count":3 } ], "FactionEffects":[] }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let counts = current_material_counts(&lines).unwrap();
+    assert_eq!(Some(&26), counts.get("iron"));
+    assert_eq!(Some(&3), counts.get("nickel"));
+}
+
+#[test]
+fn test_materials_technology_broker() {
+    let json = [
+        MATERIALS_SNAPSHOT,
+        r#"{ "timestamp":"2024-03-10T18:22:38Z", "event":"TechnologyBroker", "BrokerType":"guardian", "MarketID":128666762, "ItemsUnlocked":[ { "Name":"Hpt_Guardian_GaussCannon_Fixed_Medium", "Name_Localised":"Guardian Gauss Cannon" } ], "Commodities":[ { "Name":"guardian_moduleblueprint", "Name_Localised":"Guardian Module Blueprint Segment", "Count":4 } ], "Materials":[ { "Name":"guardian_sentinel_weaponparts", "Name_Localised":"Guardian Sentinel Weapon Parts", "Count":18, "Category":"Manufactured" } ] }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let counts = current_material_counts(&lines).unwrap();
+    assert_eq!(Some(&12), counts.get("guardian_sentinel_weaponparts"));
+}
+
+#[test]
+fn test_materials_engineer_contribution() {
+    let json = [
+        MATERIALS_SNAPSHOT,
+        r#"{ "timestamp":"2024-03-10T18:22:38Z", "event":"EngineerContribution", "Engineer":"The Sarge", "EngineerID":300040, "Type":"Materials", "Material":"shieldpatternanalysis", "Material_Localised":"Aberrant Shield Pattern Analysis", "Quantity":50, "TotalQuantity":50 }"#,
+        r#"{ "timestamp":"2024-03-10T18:23:38Z", "event":"EngineerContribution", "Engineer":"Colonel Bris Dekker", "EngineerID":300140, "Type":"Bond", "Quantity":126004, "TotalQuantity":1000000 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let counts = current_material_counts(&lines).unwrap();
+    assert_eq!(Some(&10), counts.get("shieldpatternanalysis"));
+    assert_eq!(3, counts.len());
+}
+
+#[test]
+fn test_material_counts_latest_snapshot() {
+    assert!(current_material_counts(&[]).is_none());
+
+    let json = [
+        r#"{ "timestamp":"2024-03-10T18:10:38Z", "event":"Materials", "Raw":[ { "Name":"iron", "Count":5 } ], "Manufactured":[], "Encoded":[] }"#,
+        r#"{ "timestamp":"2024-03-10T18:15:38Z", "event":"MaterialCollected", "Category":"Raw", "Name":"iron", "Count":3 }"#,
+        MATERIALS_SNAPSHOT,
+        r#"{ "timestamp":"2024-03-10T18:20:38Z", "event":"MaterialCollected", "Category":"Raw", "Name":"Iron", "Count":3 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    // the collection before the latest snapshot is already counted in it
+    let counts = current_material_counts(&lines).unwrap();
+    assert_eq!(Some(&23), counts.get("iron"));
+    assert_eq!(None, counts.get("nickel"));
 }
@@ -0,0 +1,331 @@
+use crate::{
+    EDString,
+    common_types::{Credits, DroneType},
+    exploration::EDLogProspectedAsteroid,
+    log_line::{EDLogEvent, EDLogLine},
+    market::MarketItemType,
+};
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Mining activity at one ring or body, between dropping out of supercruise and jumping back in
+#[derive(Clone, Debug)]
+pub struct MiningSession {
+    pub star_system: Option<EDString>,
+    pub body: Option<EDString>,
+    pub start: DateTime<Utc>,
+    /// time of the last mining event
+    pub end: DateTime<Utc>,
+    pub prospectors: u64,
+    pub collectors: u64,
+    pub prospected: Vec<EDLogProspectedAsteroid>,
+    pub cracked: u64,
+    pub refined: BTreeMap<MarketItemType, u64>,
+    pub sold: BTreeMap<MarketItemType, u64>,
+    pub ejected: BTreeMap<MarketItemType, u64>,
+    /// credits from selling what was refined in this session
+    pub revenue: Credits,
+    /// limpets bought before the session
+    pub drone_cost: Credits,
+}
+
+impl MiningSession {
+    fn new(
+        location: &Option<(EDString, EDString)>,
+        timestamp: DateTime<Utc>,
+        drone_cost: Credits,
+    ) -> MiningSession {
+        MiningSession {
+            star_system: location.as_ref().map(|(system, _)| system.clone()),
+            body: location.as_ref().map(|(_, body)| body.clone()),
+            start: timestamp,
+            end: timestamp,
+            prospectors: 0,
+            collectors: 0,
+            prospected: Vec::new(),
+            cracked: 0,
+            refined: BTreeMap::new(),
+            sold: BTreeMap::new(),
+            ejected: BTreeMap::new(),
+            revenue: Credits(0),
+            drone_cost,
+        }
+    }
+
+    pub fn hours(&self) -> f64 {
+        (self.end - self.start).num_seconds() as f64 / 3600.0
+    }
+
+    pub fn refined_tons(&self) -> u64 {
+        self.refined.values().sum()
+    }
+
+    pub fn tons_per_hour(&self) -> Option<f64> {
+        let hours = self.hours();
+        (hours > 0.0).then(|| self.refined_tons() as f64 / hours)
+    }
+
+    /// tons refined and neither sold nor ejected yet
+    pub fn unsold(&self, commodity: MarketItemType) -> u64 {
+        let gone =
+            self.sold.get(&commodity).unwrap_or(&0) + self.ejected.get(&commodity).unwrap_or(&0);
+        self.refined
+            .get(&commodity)
+            .unwrap_or(&0)
+            .saturating_sub(gone)
+    }
+
+    pub fn is_sold(&self) -> bool {
+        self.refined.keys().all(|c| self.unsold(*c) == 0)
+    }
+
+    /// fraction of prospected asteroids with at least `threshold` percent of `commodity`
+    pub fn hit_rate(&self, commodity: MarketItemType, threshold: f64) -> f64 {
+        if self.prospected.is_empty() {
+            return 0.0;
+        }
+        let hits = self
+            .prospected
+            .iter()
+            .filter(|a| a.proportion(commodity) >= threshold)
+            .count();
+        hits as f64 / self.prospected.len() as f64
+    }
+
+    /// hit rate of every prospected commodity
+    pub fn hit_rates(&self, threshold: f64) -> BTreeMap<MarketItemType, f64> {
+        let commodities: BTreeSet<MarketItemType> = self
+            .prospected
+            .iter()
+            .flat_map(|a| a.materials.iter().filter_map(|m| m.commodity()))
+            .collect();
+        commodities
+            .into_iter()
+            .map(|c| (c, self.hit_rate(c, threshold)))
+            .collect()
+    }
+
+    /// fraction of prospected asteroids that were motherlodes
+    pub fn motherlode_rate(&self) -> f64 {
+        if self.prospected.is_empty() {
+            return 0.0;
+        }
+        let motherlodes = self
+            .prospected
+            .iter()
+            .filter(|a| a.motherlode_material.is_some())
+            .count();
+        motherlodes as f64 / self.prospected.len() as f64
+    }
+
+    /// realised credits per hour after the cost of limpets, counting only what has been sold
+    pub fn credits_per_hour(&self) -> Option<f64> {
+        let hours = self.hours();
+        (hours > 0.0).then(|| (self.revenue - self.drone_cost).0 as f64 / hours)
+    }
+}
+
+/// Mining sessions, sales of refined cargo are credited to the oldest session first
+#[derive(Clone, Debug, Default)]
+pub struct MiningSessions {
+    sessions: Vec<MiningSession>,
+    location: Option<(EDString, EDString)>,
+    in_session: bool,
+    drone_cost: Credits,
+}
+
+impl MiningSessions {
+    /// build the sessions from log lines sorted by timestamp
+    pub fn new(lines: &[EDLogLine]) -> MiningSessions {
+        let mut sessions = MiningSessions::default();
+        for line in lines {
+            sessions.add_line(line);
+        }
+        sessions
+    }
+
+    fn session(&mut self, timestamp: DateTime<Utc>) -> &mut MiningSession {
+        if !self.in_session {
+            self.in_session = true;
+            let drone_cost = std::mem::take(&mut self.drone_cost);
+            self.sessions
+                .push(MiningSession::new(&self.location, timestamp, drone_cost));
+        }
+        let session = self.sessions.last_mut().expect("session was just pushed");
+        session.end = timestamp;
+        session
+    }
+
+    /// spread `count` tons over the sessions with some of `commodity` left, returns the tons per session
+    fn take_unsold(&self, commodity: MarketItemType, mut count: u64) -> Vec<(usize, u64)> {
+        let mut taken = Vec::new();
+        for (i, session) in self.sessions.iter().enumerate() {
+            if count == 0 {
+                break;
+            }
+            let n = session.unsold(commodity).min(count);
+            if n > 0 {
+                taken.push((i, n));
+                count -= n;
+            }
+        }
+        taken
+    }
+
+    pub fn add_line(&mut self, line: &EDLogLine) {
+        let timestamp = *line.timestamp();
+        match line.event() {
+            EDLogEvent::SupercruiseExit(exit) => {
+                self.location = Some((exit.star_system.clone(), exit.body.clone()));
+                self.in_session = false;
+            }
+            EDLogEvent::SupercruiseEntry(_) | EDLogEvent::FSDJump(_) | EDLogEvent::Docked(_) => {
+                self.in_session = false;
+            }
+            EDLogEvent::BuyDrones(drones) => self.drone_cost += drones.total_cost,
+            EDLogEvent::LaunchDrone(drone) => match drone.drone_type {
+                DroneType::Prospector => self.session(timestamp).prospectors += 1,
+                DroneType::Collection => self.session(timestamp).collectors += 1,
+                _ => {}
+            },
+            EDLogEvent::ProspectedAsteroid(asteroid) => {
+                self.session(timestamp).prospected.push(asteroid.clone());
+            }
+            EDLogEvent::AsteroidCracked(_) => self.session(timestamp).cracked += 1,
+            EDLogEvent::MiningRefined(refined) => {
+                *self
+                    .session(timestamp)
+                    .refined
+                    .entry(refined.material_type)
+                    .or_default() += 1;
+            }
+            EDLogEvent::MarketSell(sell) => {
+                for (i, n) in self.take_unsold(sell.sell_type, sell.count) {
+                    let session = &mut self.sessions[i];
+                    *session.sold.entry(sell.sell_type).or_default() += n;
+                    session.revenue += sell.sell_price * n;
+                }
+            }
+            EDLogEvent::EjectCargo(eject) if !eject.abandoned => {
+                for (i, n) in self.take_unsold(eject.cargo_type, eject.count) {
+                    *self.sessions[i]
+                        .ejected
+                        .entry(eject.cargo_type)
+                        .or_default() += n;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &MiningSession> {
+        self.sessions.iter()
+    }
+
+    pub fn last(&self) -> Option<&MiningSession> {
+        self.sessions.last()
+    }
+
+    /// sessions at a ring or body
+    pub fn at(&self, body: &str) -> impl Iterator<Item = &MiningSession> {
+        self.sessions
+            .iter()
+            .filter(move |s| s.body.as_ref().is_some_and(|b| b.as_str() == body))
+    }
+}
+
+#[test]
+fn test_mining_sessions() {
+    let json = [
+        r#"{ "timestamp":"2025-03-01T10:00:00Z", "event":"BuyDrones", "Type":"Drones", "Count":20, "BuyPrice":101, "TotalCost":2020 }"#,
+        r#"{ "timestamp":"2025-03-01T10:10:00Z", "event":"SupercruiseExit", "Taxi":false, "Multicrew":false, "StarSystem":"BD-11 1025", "SystemAddress":1733187048130, "Body":"BD-11 1025 2 A Ring", "BodyID":9, "BodyType":"PlanetaryRing" }"#,
+        r#"{ "timestamp":"2025-03-01T10:15:00Z", "event":"LaunchDrone", "Type":"Prospector" }"#,
+        r#"{ "timestamp":"2025-03-01T10:15:05Z", "event":"ProspectedAsteroid", "Materials":[ { "Name":"Platinum", "Proportion":32.5 }, { "Name":"LowTemperatureDiamond", "Name_Localised":"Low Temperature Diamonds", "Proportion":8.1 } ], "Content":"$AsteroidMaterialContent_High;", "Content_Localised":"Material Content: High", "Remaining":100.0 }"#,
+        r#"{ "timestamp":"2025-03-01T10:20:00Z", "event":"LaunchDrone", "Type":"Prospector" }"#,
+        r#"{ "timestamp":"2025-03-01T10:20:05Z", "event":"ProspectedAsteroid", "Materials":[ { "Name":"Platinum", "Proportion":12.0 } ], "MotherlodeMaterial":"Alexandrite", "Content":"$AsteroidMaterialContent_Medium;", "Content_Localised":"Material Content: Medium", "Remaining":100.0 }"#,
+        r#"{ "timestamp":"2025-03-01T10:30:00Z", "event":"MiningRefined", "Type":"$platinum_name;", "Type_Localised":"Platinum" }"#,
+        r#"{ "timestamp":"2025-03-01T10:45:00Z", "event":"MiningRefined", "Type":"$platinum_name;", "Type_Localised":"Platinum" }"#,
+        r#"{ "timestamp":"2025-03-01T11:15:00Z", "event":"MiningRefined", "Type":"$platinum_name;", "Type_Localised":"Platinum" }"#,
+        r#"{ "timestamp":"2025-03-01T11:20:00Z", "event":"SupercruiseEntry", "Taxi":false, "Multicrew":false, "StarSystem":"BD-11 1025", "SystemAddress":1733187048130 }"#,
+        r#"{ "timestamp":"2025-03-01T11:40:00Z", "event":"EjectCargo", "Type":"platinum", "Count":1, "Abandoned":false }"#,
+        r#"{ "timestamp":"2025-03-01T11:50:00Z", "event":"MarketSell", "MarketID":3228342528, "Type":"platinum", "Count":2, "SellPrice":250000, "TotalSale":500000, "AvgPricePaid":0 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let sessions = MiningSessions::new(&lines);
+    assert_eq!(1, sessions.iter().count());
+    let session = sessions.at("BD-11 1025 2 A Ring").next().unwrap();
+    assert_eq!(2, session.prospectors);
+    assert_eq!(3, session.refined_tons());
+    assert_eq!(Some(3.0), session.tons_per_hour());
+    assert_eq!(0.5, session.hit_rate(MarketItemType::Platinum, 20.0));
+    assert_eq!(
+        0.5,
+        session.hit_rates(5.0)[&MarketItemType::LowTempDiamonds]
+    );
+    assert_eq!(0.5, session.motherlode_rate());
+    assert_eq!(
+        Some(MarketItemType::Alexandrite),
+        session.prospected[1].motherlode()
+    );
+
+    assert!(session.is_sold());
+    assert_eq!(1, session.ejected[&MarketItemType::Platinum]);
+    assert_eq!(Credits(500000), session.revenue);
+    assert_eq!(Credits(2020), session.drone_cost);
+    assert_eq!(Some(497980.0), session.credits_per_hour());
+}
+
+#[test]
+fn test_mining_sessions_without_location() {
+    let json = [
+        // mining right after logging in, no SupercruiseExit
+        r#"{ "timestamp":"2025-03-01T10:00:00Z", "event":"MiningRefined", "Type":"$platinum_name;", "Type_Localised":"Platinum" }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    assert!(MiningSessions::new(&[]).last().is_none());
+    let sessions = MiningSessions::new(&lines);
+    let session = sessions.last().unwrap();
+    assert_eq!(None, session.star_system);
+    assert_eq!(None, session.body);
+    assert_eq!(0, sessions.at("BD-11 1025 2 A Ring").count());
+    assert_eq!(None, session.tons_per_hour());
+    assert_eq!(None, session.credits_per_hour());
+    assert_eq!(0.0, session.hit_rate(MarketItemType::Platinum, 0.0));
+    assert_eq!(0.0, session.motherlode_rate());
+    assert!(session.hit_rates(0.0).is_empty());
+    assert!(!session.is_sold());
+}
+
+#[test]
+fn test_mining_sales_across_sessions() {
+    let json = [
+        // nothing refined yet, the sale isn't from mining
+        r#"{ "timestamp":"2025-03-01T09:00:00Z", "event":"MarketSell", "MarketID":3228342528, "Type":"platinum", "Count":4, "SellPrice":250000, "TotalSale":1000000, "AvgPricePaid":0 }"#,
+        r#"{ "timestamp":"2025-03-01T10:00:00Z", "event":"BuyDrones", "Type":"Drones", "Count":20, "BuyPrice":101, "TotalCost":2020 }"#,
+        r#"{ "timestamp":"2025-03-01T10:10:00Z", "event":"SupercruiseExit", "Taxi":false, "Multicrew":false, "StarSystem":"BD-11 1025", "SystemAddress":1733187048130, "Body":"BD-11 1025 2 A Ring", "BodyID":9, "BodyType":"PlanetaryRing" }"#,
+        r#"{ "timestamp":"2025-03-01T10:30:00Z", "event":"MiningRefined", "Type":"$platinum_name;", "Type_Localised":"Platinum" }"#,
+        r#"{ "timestamp":"2025-03-01T10:45:00Z", "event":"MiningRefined", "Type":"$platinum_name;", "Type_Localised":"Platinum" }"#,
+        r#"{ "timestamp":"2025-03-01T11:20:00Z", "event":"SupercruiseEntry", "Taxi":false, "Multicrew":false, "StarSystem":"BD-11 1025", "SystemAddress":1733187048130 }"#,
+        r#"{ "timestamp":"2025-03-01T11:30:00Z", "event":"SupercruiseExit", "Taxi":false, "Multicrew":false, "StarSystem":"BD-11 1025", "SystemAddress":1733187048130, "Body":"BD-11 1025 2 A Ring", "BodyID":9, "BodyType":"PlanetaryRing" }"#,
+        r#"{ "timestamp":"2025-03-01T11:40:00Z", "event":"MiningRefined", "Type":"$platinum_name;", "Type_Localised":"Platinum" }"#,
+        // abandoned cargo is still in the hold
+        r#"{ "timestamp":"2025-03-01T11:45:00Z", "event":"EjectCargo", "Type":"platinum", "Count":1, "Abandoned":true }"#,
+        r#"{ "timestamp":"2025-03-01T12:00:00Z", "event":"SupercruiseEntry", "Taxi":false, "Multicrew":false, "StarSystem":"BD-11 1025", "SystemAddress":1733187048130 }"#,
+        // more than was refined, the rest came from elsewhere
+        r#"{ "timestamp":"2025-03-01T12:30:00Z", "event":"MarketSell", "MarketID":3228342528, "Type":"platinum", "Count":5, "SellPrice":250000, "TotalSale":1250000, "AvgPricePaid":0 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let sessions = MiningSessions::new(&lines);
+    let at_ring: Vec<&MiningSession> = sessions.at("BD-11 1025 2 A Ring").collect();
+    assert_eq!(2, at_ring.len());
+    assert_eq!(2, at_ring[0].sold[&MarketItemType::Platinum]);
+    assert_eq!(Credits(500000), at_ring[0].revenue);
+    assert_eq!(Credits(2020), at_ring[0].drone_cost);
+    assert_eq!(1, at_ring[1].sold[&MarketItemType::Platinum]);
+    assert!(at_ring[1].ejected.is_empty());
+    assert_eq!(Credits(0), at_ring[1].drone_cost);
+    assert!(at_ring.iter().all(|s| s.is_sold()));
+}
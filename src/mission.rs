@@ -112,40 +112,40 @@ pub struct CommodityReward {
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct MaterialReward {
-    name: EDString,
+    pub name: EDString,
     #[serde(rename = "Name_Localised")]
-    name_localised: Option<EDString>,
-    category: EDString,
+    pub name_localised: Option<EDString>,
+    pub category: EDString,
     #[serde(rename = "Category_Localised")]
-    category_localised: EDString,
-    count: u64,
+    pub category_localised: EDString,
+    pub count: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct Effect {
-    effect: EDString,
+    pub effect: EDString,
     #[serde(rename = "Effect_Localised")]
-    effect_localised: EDString,
-    trend: EDString,
+    pub effect_localised: EDString,
+    pub trend: EDString,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct Influence {
-    system_address: u64,
-    trend: EDString,
-    influence: EDString,
+    pub system_address: u64,
+    pub trend: EDString,
+    pub influence: EDString,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct FactionEffect {
-    faction: EDString,
-    effects: Vec<Effect>,
-    influence: Vec<Influence>,
-    reputation_trend: EDString,
-    reputation: EDString,
+    pub faction: EDString,
+    pub effects: Vec<Effect>,
+    pub influence: Vec<Influence>,
+    pub reputation_trend: EDString,
+    pub reputation: EDString,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
@@ -253,7 +253,11 @@ pub struct BountyPilot {
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogBounty {
     pub rewards: Option<Vec<BountyReward>>,
+    /// skimmers and drones pay a single reward by `faction`
     pub reward: Option<f64>,
+    pub faction: Option<EDString>,
+    #[serde(rename = "Faction_Localised")]
+    pub faction_localised: Option<EDString>,
     #[serde(flatten)]
     pub pilot: Option<BountyPilot>,
     pub target: EDString,
@@ -266,7 +270,7 @@ pub struct EDLogBounty {
     pub shared_with_others: Option<u64>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[serde(rename_all = "lowercase")]
 pub enum VoucherType {
     #[serde(rename = "CombatBond")]
@@ -281,8 +285,8 @@ pub enum VoucherType {
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct VoucherFaction {
-    faction: EDString,
-    amount: Credits,
+    pub faction: EDString,
+    pub amount: Credits,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
@@ -310,9 +314,9 @@ pub struct EDLogDatalinkVoucher {
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogCapitalShipBond {
-    reward: Credits,
-    awarding_faction: EDString,
-    victim_faction: EDString,
+    pub reward: Credits,
+    pub awarding_faction: EDString,
+    pub victim_faction: EDString,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -351,8 +355,8 @@ pub struct EDLogPassengers {
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogPVPKill {
-    victim: EDString,
-    combat_rank: u64,
+    pub victim: EDString,
+    pub combat_rank: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
@@ -360,11 +364,11 @@ pub struct EDLogPVPKill {
 #[testcase({ "timestamp":"2026-01-05T20:04:31Z", "event":"FactionKillBond", "Reward":52500, 
     "AwardingFaction":"Sirius Inc", "VictimFaction":"Race Marshalls" })]
 pub struct EDLogFactionKillBond {
-    reward: Credits,
-    awarding_faction: EDString,
+    pub reward: Credits,
+    pub awarding_faction: EDString,
     #[serde(rename = "AwardingFaction_Localised")]
-    awarding_faction_localised: Option<EDString>,
-    victim_faction: EDString,
+    pub awarding_faction_localised: Option<EDString>,
+    pub victim_faction: EDString,
     #[serde(rename = "VictimFaction_Localised")]
-    victim_faction_localised: Option<EDString>,
+    pub victim_faction_localised: Option<EDString>,
 }
@@ -166,7 +166,7 @@ pub struct EDLogTouchdown {
     pub nearest_destination_localised: Option<EDString>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum JumpType {
     Hyperspace,
     Supercruise,
@@ -175,33 +175,33 @@ pub enum JumpType {
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct JumpToStarsystem {
-    star_system: EDString,
-    system_address: u64,
-    star_class: StarClass,
+    pub star_system: EDString,
+    pub system_address: u64,
+    pub star_class: StarClass,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogStartJump {
-    jump_type: JumpType,
-    taxi: Option<bool>,
+    pub jump_type: JumpType,
+    pub taxi: Option<bool>,
     #[serde(flatten)]
-    star_system: Option<JumpToStarsystem>,
+    pub star_system: Option<JumpToStarsystem>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogJetConeBoost {
-    boost_value: f64,
+    pub boost_value: f64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 #[testcase({ "timestamp":"2026-04-07T17:14:53Z", "event":"JetConeDamage", "Module":"$modularcargobaydoor_name;", "Module_Localised":"Cargo Hatch" })]
 pub struct EDLogJetConeDamage {
-    module: EDString,
+    pub module: EDString,
     #[serde(rename = "Module_Localised")]
-    module_localised: Option<EDString>,
+    pub module_localised: Option<EDString>,
 }
 
 #[test]
@@ -0,0 +1,520 @@
+use crate::{
+    common_types::OnFootItem,
+    log_line::{EDLogEvent, EDLogLine},
+    market::{MicroResource, MicroResourceType},
+    suits::{BackpackItem, BackpackItemType, TransferDirection},
+};
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum InventoryLocation {
+    ShipLocker,
+    Backpack,
+}
+
+/// items bound to a mission are kept apart from the ones we own
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct InventoryKey {
+    pub item: OnFootItem,
+    pub mission_id: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InventoryEntry {
+    pub category: BackpackItemType,
+    pub count: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InventoryChangeReason {
+    /// correction from a ShipLocker or Backpack snapshot
+    Snapshot,
+    Collected,
+    Dropped,
+    Consumed,
+    Bought,
+    Sold,
+    Traded,
+    Transferred,
+    Delivered,
+    Other,
+}
+
+#[derive(Clone, Debug)]
+pub struct InventoryChange {
+    pub timestamp: DateTime<Utc>,
+    pub location: InventoryLocation,
+    pub key: InventoryKey,
+    pub category: BackpackItemType,
+    pub delta: i64,
+    pub reason: InventoryChangeReason,
+}
+
+fn category(category: MicroResourceType) -> BackpackItemType {
+    match category {
+        MicroResourceType::Data => BackpackItemType::Data,
+        MicroResourceType::Item => BackpackItemType::Item,
+        MicroResourceType::Component => BackpackItemType::Component,
+        MicroResourceType::Consumable => BackpackItemType::Consumable,
+    }
+}
+
+type Contents = BTreeMap<InventoryKey, InventoryEntry>;
+
+/// Odyssey micro-resources held in the ship locker and in the suit backpack
+#[derive(Clone, Debug, Default)]
+pub struct OnFootInventory {
+    ship_locker: Contents,
+    backpack: Contents,
+    pub history: Vec<InventoryChange>,
+    // CollectItems, DropItems and UseConsumable are followed by a BackpackChange
+    // with the same timestamp, which is the one applied
+    pending_reason: Option<(DateTime<Utc>, InventoryChangeReason)>,
+}
+
+impl OnFootInventory {
+    /// build the inventory from log lines sorted by timestamp
+    pub fn new(lines: &[EDLogLine]) -> OnFootInventory {
+        let mut inventory = OnFootInventory::default();
+        for line in lines {
+            inventory.add_line(line);
+        }
+        inventory
+    }
+
+    fn contents_mut(&mut self, location: InventoryLocation) -> &mut Contents {
+        match location {
+            InventoryLocation::ShipLocker => &mut self.ship_locker,
+            InventoryLocation::Backpack => &mut self.backpack,
+        }
+    }
+
+    fn contents(&self, location: InventoryLocation) -> &Contents {
+        match location {
+            InventoryLocation::ShipLocker => &self.ship_locker,
+            InventoryLocation::Backpack => &self.backpack,
+        }
+    }
+
+    fn change(
+        &mut self,
+        timestamp: DateTime<Utc>,
+        location: InventoryLocation,
+        key: InventoryKey,
+        category: BackpackItemType,
+        delta: i64,
+        reason: InventoryChangeReason,
+    ) {
+        if delta == 0 {
+            return;
+        }
+        let contents = self.contents_mut(location);
+        let entry = contents
+            .entry(key.clone())
+            .or_insert(InventoryEntry { category, count: 0 });
+        entry.count = entry.count.saturating_add_signed(delta);
+        if entry.count == 0 {
+            contents.remove(&key);
+        }
+        self.history.push(InventoryChange {
+            timestamp,
+            location,
+            key,
+            category,
+            delta,
+            reason,
+        });
+    }
+
+    /// replace the content of a location, recording the differences as history
+    fn snapshot(&mut self, timestamp: DateTime<Utc>, location: InventoryLocation, new: Contents) {
+        let old = std::mem::replace(self.contents_mut(location), new.clone());
+        let keys = old
+            .keys()
+            .chain(new.keys().filter(|key| !old.contains_key(key)));
+        for key in keys {
+            let (previous, current) = (old.get(key), new.get(key));
+            let delta =
+                current.map_or(0, |e| e.count as i64) - previous.map_or(0, |e| e.count as i64);
+            let Some(entry) = current.or(previous) else {
+                continue;
+            };
+            if delta != 0 {
+                self.history.push(InventoryChange {
+                    timestamp,
+                    location,
+                    key: key.clone(),
+                    category: entry.category,
+                    delta,
+                    reason: InventoryChangeReason::Snapshot,
+                });
+            }
+        }
+    }
+
+    fn micro_resources(
+        &mut self,
+        timestamp: DateTime<Utc>,
+        resources: &[MicroResource],
+        sign: i64,
+        reason: InventoryChangeReason,
+    ) {
+        for resource in resources {
+            let key = InventoryKey {
+                item: resource.name.clone(),
+                mission_id: None,
+            };
+            self.change(
+                timestamp,
+                InventoryLocation::ShipLocker,
+                key,
+                category(resource.category),
+                sign * resource.count as i64,
+                reason,
+            );
+        }
+    }
+
+    fn backpack_items(
+        &mut self,
+        timestamp: DateTime<Utc>,
+        items: &[BackpackItem],
+        sign: i64,
+        reason: InventoryChangeReason,
+    ) {
+        for item in items {
+            let key = InventoryKey {
+                item: item.name.clone(),
+                mission_id: item.mission_id,
+            };
+            let category = item
+                .item_type
+                .or(self.backpack.get(&key).map(|e| e.category))
+                .unwrap_or(BackpackItemType::Item);
+            self.change(
+                timestamp,
+                InventoryLocation::Backpack,
+                key,
+                category,
+                sign * item.count as i64,
+                reason,
+            );
+        }
+    }
+
+    pub fn add_line(&mut self, line: &EDLogLine) {
+        let timestamp = *line.timestamp();
+        match line.event() {
+            EDLogEvent::ShipLocker(locker) => {
+                let Some(content) = &locker.content else {
+                    return;
+                };
+                let mut contents = Contents::new();
+                for (items, category) in [
+                    (&content.items, BackpackItemType::Item),
+                    (&content.components, BackpackItemType::Component),
+                    (&content.consumables, BackpackItemType::Consumable),
+                    (&content.data, BackpackItemType::Data),
+                ] {
+                    for item in items {
+                        insert(
+                            &mut contents,
+                            item.name.clone(),
+                            item.mission_id,
+                            category,
+                            item.count,
+                        );
+                    }
+                }
+                self.snapshot(timestamp, InventoryLocation::ShipLocker, contents);
+            }
+            EDLogEvent::Backpack(backpack) => {
+                let mut contents = Contents::new();
+                for (items, category) in [
+                    (&backpack.items, BackpackItemType::Item),
+                    (&backpack.components, BackpackItemType::Component),
+                    (&backpack.consumables, BackpackItemType::Consumable),
+                    (&backpack.data, BackpackItemType::Data),
+                ] {
+                    for item in items {
+                        insert(
+                            &mut contents,
+                            item.name.clone(),
+                            item.mission_id,
+                            category,
+                            item.count,
+                        );
+                    }
+                }
+                self.snapshot(timestamp, InventoryLocation::Backpack, contents);
+            }
+            EDLogEvent::CollectItems(_) => {
+                self.pending_reason = Some((timestamp, InventoryChangeReason::Collected));
+            }
+            EDLogEvent::DropItems(_) => {
+                self.pending_reason = Some((timestamp, InventoryChangeReason::Dropped));
+            }
+            EDLogEvent::UseConsumable(_) => {
+                self.pending_reason = Some((timestamp, InventoryChangeReason::Consumed));
+            }
+            EDLogEvent::BackpackChange(change) => {
+                let reason = match self.pending_reason.take() {
+                    Some((at, reason)) if at == timestamp => reason,
+                    _ => InventoryChangeReason::Other,
+                };
+                self.backpack_items(
+                    timestamp,
+                    change.added.as_deref().unwrap_or_default(),
+                    1,
+                    reason,
+                );
+                self.backpack_items(
+                    timestamp,
+                    change.removed.as_deref().unwrap_or_default(),
+                    -1,
+                    reason,
+                );
+            }
+            EDLogEvent::TransferMicroResources(transfer) => {
+                for transfer in &transfer.transfers {
+                    let key = InventoryKey {
+                        item: transfer.name.clone(),
+                        mission_id: None,
+                    };
+                    let moved = transfer
+                        .locker_old_count
+                        .abs_diff(transfer.locker_new_count) as i64;
+                    let to_backpack = match transfer.direction {
+                        TransferDirection::ToBackpack => moved,
+                        TransferDirection::ToShipLocker => -moved,
+                    };
+                    let reason = InventoryChangeReason::Transferred;
+                    self.change(
+                        timestamp,
+                        InventoryLocation::ShipLocker,
+                        key.clone(),
+                        transfer.category,
+                        -to_backpack,
+                        reason,
+                    );
+                    self.change(
+                        timestamp,
+                        InventoryLocation::Backpack,
+                        key,
+                        transfer.category,
+                        to_backpack,
+                        reason,
+                    );
+                }
+            }
+            EDLogEvent::BuyMicroResources(buy) => {
+                let resources: Vec<MicroResource> = buy
+                    .micro_resource
+                    .iter()
+                    .chain(buy.micro_resources.iter().flatten())
+                    .cloned()
+                    .collect();
+                self.micro_resources(timestamp, &resources, 1, InventoryChangeReason::Bought);
+            }
+            EDLogEvent::SellMicroResources(sell) => {
+                self.micro_resources(
+                    timestamp,
+                    &sell.micro_resources,
+                    -1,
+                    InventoryChangeReason::Sold,
+                );
+            }
+            EDLogEvent::TradeMicroResources(trade) => {
+                self.micro_resources(timestamp, &trade.offered, -1, InventoryChangeReason::Traded);
+                let key = InventoryKey {
+                    item: trade.received.clone(),
+                    mission_id: None,
+                };
+                self.change(
+                    timestamp,
+                    InventoryLocation::ShipLocker,
+                    key,
+                    category(trade.category),
+                    trade.count as i64,
+                    InventoryChangeReason::Traded,
+                );
+            }
+            EDLogEvent::DeliverPowerMicroResources(deliver) => {
+                self.micro_resources(
+                    timestamp,
+                    &deliver.micro_resources,
+                    -1,
+                    InventoryChangeReason::Delivered,
+                );
+            }
+            _ => {}
+        }
+    }
+
+    pub fn iter(
+        &self,
+        location: InventoryLocation,
+    ) -> impl Iterator<Item = (&InventoryKey, &InventoryEntry)> {
+        self.contents(location).iter()
+    }
+
+    /// count of an item at a location, mission-bound items included
+    pub fn count(&self, location: InventoryLocation, item: OnFootItem) -> u64 {
+        self.contents(location)
+            .iter()
+            .filter(|(key, _)| key.item == item)
+            .map(|(_, entry)| entry.count)
+            .sum()
+    }
+
+    /// count of an item we own, in the ship locker and in the backpack
+    pub fn owned(&self, item: OnFootItem) -> u64 {
+        let key = InventoryKey {
+            item,
+            mission_id: None,
+        };
+        [InventoryLocation::ShipLocker, InventoryLocation::Backpack]
+            .into_iter()
+            .filter_map(|location| self.contents(location).get(&key))
+            .map(|entry| entry.count)
+            .sum()
+    }
+
+    pub fn by_category(
+        &self,
+        location: InventoryLocation,
+        category: BackpackItemType,
+    ) -> BTreeMap<OnFootItem, u64> {
+        let mut result = BTreeMap::new();
+        for (key, entry) in self.iter(location) {
+            if entry.category == category {
+                *result.entry(key.item.clone()).or_default() += entry.count;
+            }
+        }
+        result
+    }
+
+    /// items bound to a mission, wherever they are
+    pub fn mission_items(&self, mission_id: u64) -> Vec<(InventoryLocation, OnFootItem, u64)> {
+        [InventoryLocation::ShipLocker, InventoryLocation::Backpack]
+            .into_iter()
+            .flat_map(|location| {
+                self.iter(location)
+                    .filter(|(key, _)| key.mission_id == Some(mission_id))
+                    .map(move |(key, entry)| (location, key.item.clone(), entry.count))
+            })
+            .collect()
+    }
+
+    pub fn history_of(&self, item: OnFootItem) -> impl Iterator<Item = &InventoryChange> {
+        self.history.iter().filter(move |c| c.key.item == item)
+    }
+}
+
+fn insert(
+    contents: &mut Contents,
+    item: OnFootItem,
+    mission_id: Option<u64>,
+    category: BackpackItemType,
+    count: u64,
+) {
+    contents
+        .entry(InventoryKey { item, mission_id })
+        .or_insert(InventoryEntry { category, count: 0 })
+        .count += count;
+}
+
+#[test]
+fn test_on_foot_inventory() {
+    let json = [
+        r#"{ "timestamp":"2025-12-09T20:19:43Z", "event":"ShipLocker", "Items":[ { "Name":"geneticsample", "Name_Localised":"Biological Sample", "OwnerID":0, "Count":20 }, { "Name":"lazarus", "OwnerID":0, "MissionID":1234, "Count":2 } ], "Components":[ { "Name":"graphene", "OwnerID":0, "Count":40 } ], "Consumables":[ { "Name":"healthpack", "Name_Localised":"Medkit", "OwnerID":0, "Count":100 } ], "Data":[] }"#,
+        r#"{ "timestamp":"2025-12-09T20:20:00Z", "event":"Backpack", "Items":[], "Components":[], "Consumables":[], "Data":[] }"#,
+        r#"{ "timestamp":"2025-12-09T20:21:02Z", "event":"TransferMicroResources", "Transfers":[ { "Name":"healthpack", "Name_Localised":"Medkit", "Category":"Consumable", "LockerOldCount":100, "LockerNewCount":98, "Direction":"ToBackpack" } ] }"#,
+        r#"{ "timestamp":"2025-12-09T20:30:00Z", "event":"CollectItems", "Name":"graphene", "Type":"Component", "OwnerID":0, "Count":3, "Stolen":false }"#,
+        r#"{ "timestamp":"2025-12-09T20:30:00Z", "event":"BackpackChange", "Added":[ { "Name":"graphene", "OwnerID":0, "Count":3, "Type":"Component" } ] }"#,
+        r#"{ "timestamp":"2025-12-09T20:31:00Z", "event":"UseConsumable", "Name":"healthpack", "Name_Localised":"Medkit", "Type":"Consumable" }"#,
+        r#"{ "timestamp":"2025-12-09T20:31:00Z", "event":"BackpackChange", "Removed":[ { "Name":"healthpack", "Name_Localised":"Medkit", "OwnerID":0, "Count":1, "Type":"Consumable" } ] }"#,
+        r#"{ "timestamp":"2026-01-21T20:24:20Z", "event":"SellMicroResources", "TotalCount":5, "MicroResources":[ { "Name":"geneticsample", "Name_Localised":"Biological Sample", "Category":"Item", "Count":5 } ], "Price":25000, "MarketID":3228823296 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let inventory = OnFootInventory::new(&lines);
+    assert_eq!(
+        98,
+        inventory.count(InventoryLocation::ShipLocker, OnFootItem::HealthPack)
+    );
+    assert_eq!(
+        1,
+        inventory.count(InventoryLocation::Backpack, OnFootItem::HealthPack)
+    );
+    assert_eq!(99, inventory.owned(OnFootItem::HealthPack));
+    assert_eq!(43, inventory.owned(OnFootItem::Graphene));
+    assert_eq!(
+        Some(&15),
+        inventory
+            .by_category(InventoryLocation::ShipLocker, BackpackItemType::Item)
+            .get(&OnFootItem::GeneticSample)
+    );
+    assert_eq!(
+        vec![(InventoryLocation::ShipLocker, OnFootItem::Lazarus, 2)],
+        inventory.mission_items(1234)
+    );
+    assert_eq!(0, inventory.owned(OnFootItem::Lazarus));
+
+    let reasons: Vec<InventoryChangeReason> = inventory
+        .history_of(OnFootItem::HealthPack)
+        .map(|c| c.reason)
+        .collect();
+    assert_eq!(
+        vec![
+            InventoryChangeReason::Snapshot,
+            InventoryChangeReason::Transferred,
+            InventoryChangeReason::Transferred,
+            InventoryChangeReason::Consumed,
+        ],
+        reasons
+    );
+}
+
+#[test]
+fn test_on_foot_inventory_empty() {
+    let inventory = OnFootInventory::new(&[]);
+    assert_eq!(0, inventory.iter(InventoryLocation::ShipLocker).count());
+    assert_eq!(0, inventory.owned(OnFootItem::HealthPack));
+    assert!(inventory.mission_items(1234).is_empty());
+    assert!(inventory.history.is_empty());
+}
+
+#[test]
+fn test_on_foot_inventory_without_snapshot() {
+    let json = [
+        // selling more than we know of doesn't go below zero
+        r#"{ "timestamp":"2026-01-21T20:24:20Z", "event":"SellMicroResources", "TotalCount":5, "MicroResources":[ { "Name":"geneticsample", "Name_Localised":"Biological Sample", "Category":"Item", "Count":5 } ], "Price":25000, "MarketID":3228823296 }"#,
+        // CollectItems at another timestamp doesn't give the reason of the change
+        r#"{ "timestamp":"2026-01-21T20:29:59Z", "event":"CollectItems", "Name":"graphene", "Type":"Component", "OwnerID":0, "Count":3, "Stolen":false }"#,
+        r#"{ "timestamp":"2026-01-21T20:30:00Z", "event":"BackpackChange", "Added":[ { "Name":"graphene", "OwnerID":0, "Count":3 } ] }"#,
+        r#"{ "timestamp":"2026-01-21T20:40:00Z", "event":"ShipLocker", "Items":[ { "Name":"geneticsample", "Name_Localised":"Biological Sample", "OwnerID":0, "Count":15 } ], "Components":[], "Consumables":[], "Data":[] }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let inventory = OnFootInventory::new(&lines);
+    assert_eq!(15, inventory.owned(OnFootItem::GeneticSample));
+    assert_eq!(
+        vec![-5, 15],
+        inventory
+            .history_of(OnFootItem::GeneticSample)
+            .map(|c| c.delta)
+            .collect::<Vec<_>>()
+    );
+
+    let graphene: Vec<&InventoryChange> = inventory.history_of(OnFootItem::Graphene).collect();
+    assert_eq!(1, graphene.len());
+    assert_eq!(InventoryChangeReason::Other, graphene[0].reason);
+    // no type in the change and none known, counted as an item
+    assert_eq!(BackpackItemType::Item, graphene[0].category);
+    assert_eq!(
+        3,
+        inventory.count(InventoryLocation::Backpack, OnFootItem::Graphene)
+    );
+}
@@ -34,23 +34,23 @@ pub struct EDLogPowerplayJoin {
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogPowerplayCollect {
-    power: EDString,
+    pub power: EDString,
     #[serde(rename = "Type")]
-    power_type: EDString,
+    pub power_type: EDString,
     #[serde(rename = "Type_Localised")]
-    power_type_localised: EDString,
-    count: u64,
+    pub power_type_localised: EDString,
+    pub count: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogPowerplayDeliver {
-    power: EDString,
+    pub power: EDString,
     #[serde(rename = "Type")]
-    power_type: EDString,
+    pub power_type: EDString,
     #[serde(rename = "Type_Localised")]
-    power_type_localised: EDString,
-    count: u64,
+    pub power_type_localised: EDString,
+    pub count: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
@@ -91,15 +91,15 @@ pub struct EDLogPowerplayRank {
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogPowerplaySalary {
-    power: EDString,
-    amount: u64,
+    pub power: EDString,
+    pub amount: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogHoloscreenHacked {
-    power_before: Option<EDString>,
-    power_after: EDString,
+    pub power_before: Option<EDString>,
+    pub power_after: EDString,
 }
 
 #[test]
@@ -0,0 +1,237 @@
+use crate::{
+    EDString,
+    common_types::{Power, PowerplayConflictProgress, PowerplayState, Powers},
+    log_line::{EDLogEvent, EDLogLine},
+    powerplay_tracker::powerplay_cycle_start,
+};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+
+/// powerplay state of a system as seen on arrival
+#[derive(Clone, Debug)]
+pub struct PowerplaySnapshot {
+    pub timestamp: DateTime<Utc>,
+    pub controlling_power: Option<Power>,
+    pub powers: Vec<Power>,
+    pub state: PowerplayState,
+    pub control_progress: Option<f64>,
+    pub reinforcement: Option<u64>,
+    pub undermining: Option<u64>,
+    pub conflict_progress: Vec<PowerplayConflictProgress>,
+}
+
+impl PowerplaySnapshot {
+    fn new(timestamp: DateTime<Utc>, powers: &Powers) -> PowerplaySnapshot {
+        PowerplaySnapshot {
+            timestamp,
+            controlling_power: powers.controlling_power,
+            powers: powers.powers.clone(),
+            state: powers.powerplay_state,
+            control_progress: powers.powerplay_state_control_progress,
+            reinforcement: powers.powerplay_state_reinforcement,
+            undermining: powers.powerplay_state_undermining,
+            conflict_progress: powers
+                .powerplay_conflict_progress
+                .clone()
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn is_undermined(&self) -> bool {
+        self.undermining.unwrap_or(0) > 0
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PowerplaySystemHistory {
+    pub system_address: u64,
+    pub star_system: EDString,
+    /// oldest first
+    pub snapshots: Vec<PowerplaySnapshot>,
+}
+
+impl PowerplaySystemHistory {
+    pub fn latest(&self) -> Option<&PowerplaySnapshot> {
+        self.snapshots.last()
+    }
+
+    /// snapshots taken in the powerplay cycle starting at `cycle_start`
+    pub fn in_cycle(&self, cycle_start: DateTime<Utc>) -> impl Iterator<Item = &PowerplaySnapshot> {
+        let cycle_end = cycle_start + Duration::weeks(1);
+        self.snapshots
+            .iter()
+            .filter(move |s| s.timestamp >= cycle_start && s.timestamp < cycle_end)
+    }
+
+    pub fn control_progress_trend(&self) -> Vec<(DateTime<Utc>, f64)> {
+        self.snapshots
+            .iter()
+            .filter_map(|s| Some((s.timestamp, s.control_progress?)))
+            .collect()
+    }
+}
+
+/// Powerplay state history of all visited systems, from FSDJump and Location events
+#[derive(Clone, Debug, Default)]
+pub struct PowerplayAtlas {
+    systems: HashMap<u64, PowerplaySystemHistory>,
+}
+
+impl PowerplayAtlas {
+    /// build the atlas from log lines sorted by timestamp
+    pub fn new(lines: &[EDLogLine]) -> PowerplayAtlas {
+        let mut atlas = PowerplayAtlas::default();
+        for line in lines {
+            atlas.add_line(line);
+        }
+        atlas
+    }
+
+    pub fn add_line(&mut self, line: &EDLogLine) {
+        let (system_address, star_system, powers) = match line.event() {
+            EDLogEvent::FSDJump(jump) => (
+                jump.system_address,
+                jump.star_system.clone(),
+                &jump.powerplay,
+            ),
+            EDLogEvent::Location(location) => match location.system_address {
+                Some(address) => (address, location.star_system.clone(), &location.powers),
+                None => return,
+            },
+            _ => return,
+        };
+        let Some(powers) = powers else {
+            return;
+        };
+
+        self.systems
+            .entry(system_address)
+            .or_insert_with(|| PowerplaySystemHistory {
+                system_address,
+                star_system,
+                snapshots: Vec::new(),
+            })
+            .snapshots
+            .push(PowerplaySnapshot::new(*line.timestamp(), powers));
+    }
+
+    pub fn get(&self, system_address: u64) -> Option<&PowerplaySystemHistory> {
+        self.systems.get(&system_address)
+    }
+
+    pub fn find_by_name(&self, name: &str) -> Option<&PowerplaySystemHistory> {
+        self.systems
+            .values()
+            .find(|s| s.star_system.as_str().eq_ignore_ascii_case(name))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &PowerplaySystemHistory> {
+        self.systems.values()
+    }
+
+    /// systems last seen controlled by `power`
+    pub fn controlled_by(&self, power: Power) -> Vec<&PowerplaySystemHistory> {
+        self.systems
+            .values()
+            .filter(|s| s.latest().and_then(|l| l.controlling_power) == Some(power))
+            .collect()
+    }
+
+    /// systems controlled by `power` that were seen undermined in the cycle containing `now`
+    pub fn undermined_this_cycle(
+        &self,
+        power: Power,
+        now: DateTime<Utc>,
+    ) -> Vec<&PowerplaySystemHistory> {
+        let cycle_start = powerplay_cycle_start(now);
+        self.systems
+            .values()
+            .filter(|s| {
+                s.in_cycle(cycle_start).any(|snapshot| {
+                    snapshot.controlling_power == Some(power) && snapshot.is_undermined()
+                })
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test_powerplay_atlas() {
+    let json = [
+        r#"{ "timestamp":"2025-03-10T17:51:23Z", "event":"FSDJump", "StarSystem":"Yukoukha", "SystemAddress":22945325796176, "StarPos":[-134.56250,-34.03125,-4.75000], "SystemAllegiance":"Independent", "SystemEconomy":"$economy_Military;", "SystemEconomy_Localised":"Military", "SystemGovernment":"$government_Corporate;", "SystemGovernment_Localised":"Corporate", "SystemSecurity":"$SYSTEM_SECURITY_low;", "SystemSecurity_Localised":"Low Security", "Population":33893, "ControllingPower":"Jerome Archer", "Powers":[ "Jerome Archer" ], "PowerplayState":"Exploited", "PowerplayStateControlProgress":0.250000, "PowerplayStateReinforcement":100, "PowerplayStateUndermining":0, "JumpDist":13.790, "FuelUsed":0.371038, "FuelLevel":5.628962 }"#,
+        r#"{ "timestamp":"2025-03-11T17:51:23Z", "event":"FSDJump", "StarSystem":"Yukoukha", "SystemAddress":22945325796176, "StarPos":[-134.56250,-34.03125,-4.75000], "SystemAllegiance":"Independent", "SystemEconomy":"$economy_Military;", "SystemEconomy_Localised":"Military", "SystemGovernment":"$government_Corporate;", "SystemGovernment_Localised":"Corporate", "SystemSecurity":"$SYSTEM_SECURITY_low;", "SystemSecurity_Localised":"Low Security", "Population":33893, "ControllingPower":"Jerome Archer", "Powers":[ "Jerome Archer", "Zachary Hudson" ], "PowerplayState":"Exploited", "PowerplayStateControlProgress":0.200000, "PowerplayStateReinforcement":100, "PowerplayStateUndermining":800, "JumpDist":13.790, "FuelUsed":0.371038, "FuelLevel":5.628962 }"#,
+        r#"{ "timestamp":"2025-03-11T17:55:23Z", "event":"FSDJump", "StarSystem":"Sol", "SystemAddress":10477373803, "StarPos":[0.0,0.0,0.0], "SystemAllegiance":"Federation", "SystemEconomy":"$economy_Refinery;", "SystemEconomy_Localised":"Refinery", "SystemGovernment":"$government_Democracy;", "SystemGovernment_Localised":"Democracy", "SystemSecurity":"$SYSTEM_SECURITY_high;", "SystemSecurity_Localised":"High Security", "Population":22780919531, "JumpDist":13.790, "FuelUsed":0.371038, "FuelLevel":5.628962 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let atlas = PowerplayAtlas::new(&lines);
+    // Sol has no powerplay information in this journal
+    assert_eq!(1, atlas.iter().count());
+
+    let yukoukha = atlas.find_by_name("yukoukha").unwrap();
+    assert_eq!(
+        vec![0.25, 0.2],
+        yukoukha
+            .control_progress_trend()
+            .into_iter()
+            .map(|(_, p)| p)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(1, atlas.controlled_by(Power::JeromeArcher).len());
+
+    let now = "2025-03-12T00:00:00Z".parse().unwrap();
+    assert_eq!(
+        1,
+        atlas.undermined_this_cycle(Power::JeromeArcher, now).len()
+    );
+    let next_cycle = "2025-03-13T08:00:00Z".parse().unwrap();
+    assert!(
+        atlas
+            .undermined_this_cycle(Power::JeromeArcher, next_cycle)
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_powerplay_atlas_empty() {
+    let atlas = PowerplayAtlas::new(&[]);
+    assert_eq!(0, atlas.iter().count());
+    assert!(atlas.get(22945325796176).is_none());
+    assert!(atlas.find_by_name("Yukoukha").is_none());
+    assert!(atlas.controlled_by(Power::JeromeArcher).is_empty());
+
+    let now = "2025-03-12T00:00:00Z".parse().unwrap();
+    assert!(
+        atlas
+            .undermined_this_cycle(Power::JeromeArcher, now)
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_powerplay_atlas_unoccupied() {
+    let json = [
+        r#"{ "timestamp":"2025-03-20T19:54:09Z", "event":"Location", "DistFromStarLS":2700.571292, "Docked":false, "Taxi":false, "Multicrew":false, "StarSystem":"Kholul", "StarPos":[-46.93750,-7.84375,-152.18750], "SystemAllegiance":"Independent", "SystemEconomy":"$economy_Colony;", "SystemEconomy_Localised":"Colony", "SystemGovernment":"$government_Corporate;", "SystemGovernment_Localised":"Corporate", "SystemSecurity":"$SYSTEM_SECURITY_medium;", "SystemSecurity_Localised":"Medium Security", "Population":178961, "Body":"Kholul 9", "BodyID":37, "BodyType":"Planet", "Powers":[ "Pranav Antal", "Jerome Archer" ], "PowerplayState":"Unoccupied", "PowerplayConflictProgress":[ { "Power":"Pranav Antal", "ConflictProgress":0.005875 } ] }"#,
+        r#"{ "timestamp":"2025-03-20T20:54:09Z", "event":"Location", "DistFromStarLS":2700.571292, "Docked":false, "Taxi":false, "Multicrew":false, "StarSystem":"Kholul", "SystemAddress":2415659059547, "StarPos":[-46.93750,-7.84375,-152.18750], "SystemAllegiance":"Independent", "SystemEconomy":"$economy_Colony;", "SystemEconomy_Localised":"Colony", "SystemGovernment":"$government_Corporate;", "SystemGovernment_Localised":"Corporate", "SystemSecurity":"$SYSTEM_SECURITY_medium;", "SystemSecurity_Localised":"Medium Security", "Population":178961, "Body":"Kholul 9", "BodyID":37, "BodyType":"Planet", "Powers":[ "Pranav Antal", "Jerome Archer" ], "PowerplayState":"Unoccupied", "PowerplayConflictProgress":[ { "Power":"Pranav Antal", "ConflictProgress":0.005875 } ] }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let atlas = PowerplayAtlas::new(&lines);
+    // the location without a system address is skipped
+    let kholul = atlas.get(2415659059547).unwrap();
+    assert_eq!(1, kholul.snapshots.len());
+
+    let latest = kholul.latest().unwrap();
+    assert_eq!(None, latest.controlling_power);
+    assert_eq!(1, latest.conflict_progress.len());
+    assert!(!latest.is_undermined());
+    assert!(kholul.control_progress_trend().is_empty());
+    assert!(atlas.controlled_by(Power::PranavAntal).is_empty());
+
+    let now = "2025-03-21T00:00:00Z".parse().unwrap();
+    assert!(
+        atlas
+            .undermined_this_cycle(Power::PranavAntal, now)
+            .is_empty()
+    );
+}
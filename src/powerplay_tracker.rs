@@ -0,0 +1,373 @@
+use crate::{
+    EDString,
+    common_types::{Credits, Power},
+    log_line::{EDLogEvent, EDLogEventDiscriminants, EDLogLine},
+    powerplay::power_play_rank_range,
+};
+use chrono::{DateTime, Datelike, Duration, NaiveTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+use strum::Display;
+
+/// merits gained within this time after an activity are attributed to it
+const ATTRIBUTION_WINDOW: Duration = Duration::seconds(30);
+
+/// events the tracker reads, callers can pre-filter the journal with these
+pub const POWERPLAY_EVENTS: [EDLogEventDiscriminants; 17] = [
+    EDLogEventDiscriminants::Powerplay,
+    EDLogEventDiscriminants::PowerplayMerits,
+    EDLogEventDiscriminants::PowerplayRank,
+    EDLogEventDiscriminants::PowerplaySalary,
+    EDLogEventDiscriminants::PowerplayCollect,
+    EDLogEventDiscriminants::PowerplayDeliver,
+    EDLogEventDiscriminants::HoloscreenHacked,
+    EDLogEventDiscriminants::Bounty,
+    EDLogEventDiscriminants::FactionKillBond,
+    EDLogEventDiscriminants::MarketSell,
+    EDLogEventDiscriminants::SellExplorationData,
+    EDLogEventDiscriminants::MultiSellExplorationData,
+    EDLogEventDiscriminants::SellOrganicData,
+    EDLogEventDiscriminants::MissionCompleted,
+    EDLogEventDiscriminants::FSDJump,
+    EDLogEventDiscriminants::Location,
+    EDLogEventDiscriminants::CarrierJump,
+];
+
+/// Start of the powerplay cycle containing `timestamp`, cycles tick on Thursday 07:00 UTC.
+pub fn powerplay_cycle_start(timestamp: DateTime<Utc>) -> DateTime<Utc> {
+    let days_since_thursday = (timestamp.weekday().num_days_from_monday() + 4) % 7;
+    let start = (timestamp.date_naive() - Duration::days(days_since_thursday as i64))
+        .and_time(NaiveTime::from_hms_opt(7, 0, 0).unwrap())
+        .and_utc();
+    if start > timestamp {
+        start - Duration::weeks(1)
+    } else {
+        start
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Display)]
+pub enum PowerplayActivity {
+    Collect,
+    Deliver,
+    #[strum(to_string = "Holoscreen Hack")]
+    HoloscreenHack,
+    Combat,
+    Trade,
+    Exploration,
+    Exobiology,
+    Missions,
+    Other,
+}
+
+impl PowerplayActivity {
+    fn from_event(event: &EDLogEvent) -> Option<PowerplayActivity> {
+        match event {
+            EDLogEvent::PowerplayCollect(_) => Some(PowerplayActivity::Collect),
+            EDLogEvent::PowerplayDeliver(_) => Some(PowerplayActivity::Deliver),
+            EDLogEvent::HoloscreenHacked(_) => Some(PowerplayActivity::HoloscreenHack),
+            EDLogEvent::Bounty(_) | EDLogEvent::FactionKillBond(_) => {
+                Some(PowerplayActivity::Combat)
+            }
+            EDLogEvent::MarketSell(_) => Some(PowerplayActivity::Trade),
+            EDLogEvent::SellExplorationData(_) | EDLogEvent::MultiSellExplorationData(_) => {
+                Some(PowerplayActivity::Exploration)
+            }
+            EDLogEvent::SellOrganicData(_) => Some(PowerplayActivity::Exobiology),
+            EDLogEvent::MissionCompleted(_) => Some(PowerplayActivity::Missions),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MeritGain {
+    pub timestamp: DateTime<Utc>,
+    pub power: Option<Power>,
+    pub merits: u64,
+    pub total_merits: u64,
+    pub activity: PowerplayActivity,
+    pub star_system: Option<EDString>,
+}
+
+#[derive(Clone, Debug)]
+pub struct PowerplayCycle {
+    pub start: DateTime<Utc>,
+    pub merits: u64,
+    pub by_activity: BTreeMap<PowerplayActivity, u64>,
+    pub by_system: HashMap<EDString, u64>,
+    pub salary: Credits,
+}
+
+impl PowerplayCycle {
+    fn new(start: DateTime<Utc>) -> PowerplayCycle {
+        PowerplayCycle {
+            start,
+            merits: 0,
+            by_activity: BTreeMap::new(),
+            by_system: HashMap::new(),
+            salary: Credits(0),
+        }
+    }
+
+    pub fn end(&self) -> DateTime<Utc> {
+        self.start + Duration::weeks(1)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PowerplayTracker {
+    pub power: Option<Power>,
+    pub rank: Option<u64>,
+    pub total_merits: Option<u64>,
+    pub gains: Vec<MeritGain>,
+    pub salaries: Vec<(DateTime<Utc>, Credits)>,
+    pub rank_changes: Vec<(DateTime<Utc>, u64)>,
+    current_system: Option<EDString>,
+    last_activity: Option<(DateTime<Utc>, PowerplayActivity)>,
+}
+
+impl PowerplayTracker {
+    /// build the tracker from log lines sorted by timestamp
+    pub fn new(lines: &[EDLogLine]) -> PowerplayTracker {
+        let mut tracker = PowerplayTracker::default();
+        for line in lines {
+            tracker.add_line(line);
+        }
+        tracker
+    }
+
+    pub fn add_line(&mut self, line: &EDLogLine) {
+        let timestamp = *line.timestamp();
+        if let Some(activity) = PowerplayActivity::from_event(line.event()) {
+            self.last_activity = Some((timestamp, activity));
+        }
+
+        match line.event() {
+            EDLogEvent::Powerplay(pp) => {
+                self.power = Power::from_name(pp.power.as_str());
+                self.rank = Some(pp.rank);
+                self.total_merits = Some(pp.merits.value());
+            }
+            EDLogEvent::PowerplayMerits(merits) => {
+                let activity = match self.last_activity {
+                    Some((at, activity)) if timestamp - at <= ATTRIBUTION_WINDOW => activity,
+                    _ => PowerplayActivity::Other,
+                };
+                self.power = Power::from_name(merits.power.as_str());
+                self.total_merits = Some(merits.total_merits.value());
+                self.gains.push(MeritGain {
+                    timestamp,
+                    power: self.power,
+                    merits: merits.merits_gained.value(),
+                    total_merits: merits.total_merits.value(),
+                    activity,
+                    star_system: self.current_system.clone(),
+                });
+            }
+            EDLogEvent::PowerplayRank(rank) => {
+                self.power = Power::from_name(rank.power.as_str());
+                self.rank = Some(rank.rank);
+                self.rank_changes.push((timestamp, rank.rank));
+            }
+            EDLogEvent::PowerplaySalary(salary) => {
+                self.salaries
+                    .push((timestamp, Credits(salary.amount as i64)));
+            }
+            EDLogEvent::FSDJump(jump) => self.current_system = Some(jump.star_system.clone()),
+            EDLogEvent::Location(location) => {
+                self.current_system = Some(location.star_system.clone())
+            }
+            EDLogEvent::CarrierJump(jump) => self.current_system = Some(jump.star_system.clone()),
+            _ => {}
+        }
+    }
+
+    /// merits, activities, systems and salary per weekly cycle, oldest first
+    pub fn cycles(&self) -> Vec<PowerplayCycle> {
+        let mut cycles: BTreeMap<DateTime<Utc>, PowerplayCycle> = BTreeMap::new();
+        for gain in &self.gains {
+            let start = powerplay_cycle_start(gain.timestamp);
+            let cycle = cycles
+                .entry(start)
+                .or_insert_with(|| PowerplayCycle::new(start));
+            cycle.merits += gain.merits;
+            *cycle.by_activity.entry(gain.activity).or_default() += gain.merits;
+            if let Some(system) = &gain.star_system {
+                *cycle.by_system.entry(system.clone()).or_default() += gain.merits;
+            }
+        }
+        for (timestamp, amount) in &self.salaries {
+            let start = powerplay_cycle_start(*timestamp);
+            cycles
+                .entry(start)
+                .or_insert_with(|| PowerplayCycle::new(start))
+                .salary += *amount;
+        }
+        cycles.into_values().collect()
+    }
+
+    pub fn merits_by_activity(&self) -> BTreeMap<PowerplayActivity, u64> {
+        let mut result = BTreeMap::new();
+        for gain in &self.gains {
+            *result.entry(gain.activity).or_default() += gain.merits;
+        }
+        result
+    }
+
+    pub fn merits_by_system(&self) -> HashMap<EDString, u64> {
+        let mut result = HashMap::new();
+        for gain in self.gains.iter() {
+            if let Some(system) = &gain.star_system {
+                *result.entry(system.clone()).or_default() += gain.merits;
+            }
+        }
+        result
+    }
+
+    pub fn total_salary(&self) -> Credits {
+        self.salaries.iter().map(|(_, amount)| *amount).sum()
+    }
+
+    /// merits still needed to reach the next rank
+    pub fn merits_to_next_rank(&self) -> Option<u64> {
+        let (_, next) = power_play_rank_range(self.rank?);
+        Some(next.saturating_sub(self.total_merits?))
+    }
+
+    /// average merits per day gained between `since` and `now`
+    pub fn merits_per_day(&self, since: DateTime<Utc>, now: DateTime<Utc>) -> f64 {
+        let days = (now - since).num_seconds() as f64 / 86400.0;
+        if days <= 0.0 {
+            return 0.0;
+        }
+        let merits: u64 = self
+            .gains
+            .iter()
+            .filter(|g| g.timestamp >= since && g.timestamp <= now)
+            .map(|g| g.merits)
+            .sum();
+        merits as f64 / days
+    }
+
+    /// time to the next rank at the rate of merits gained between `since` and `now`
+    pub fn time_to_next_rank(&self, since: DateTime<Utc>, now: DateTime<Utc>) -> Option<Duration> {
+        let remaining = self.merits_to_next_rank()?;
+        let rate = self.merits_per_day(since, now);
+        if rate <= 0.0 {
+            return None;
+        }
+        Some(Duration::seconds(
+            (remaining as f64 / rate * 86400.0).round() as i64,
+        ))
+    }
+}
+
+#[test]
+fn test_powerplay_cycle_start() {
+    let ts = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+    // Thursday before the tick belongs to the previous cycle
+    assert_eq!(
+        ts("2025-03-06T07:00:00Z"),
+        powerplay_cycle_start(ts("2025-03-13T06:59:59Z"))
+    );
+    assert_eq!(
+        ts("2025-03-13T07:00:00Z"),
+        powerplay_cycle_start(ts("2025-03-13T07:00:00Z"))
+    );
+    assert_eq!(
+        ts("2025-03-13T07:00:00Z"),
+        powerplay_cycle_start(ts("2025-03-17T18:21:04Z"))
+    );
+}
+
+#[test]
+fn test_powerplay_tracker() {
+    let json = [
+        r#"{ "timestamp":"2025-03-10T18:21:04Z", "event":"Powerplay", "Power":"Jerome Archer", "Rank":89, "Merits":687268, "TimePledged":4578634 }"#,
+        r#"{ "timestamp":"2025-03-10T18:25:23Z", "event":"FSDJump", "StarSystem":"Sol", "SystemAddress":10477373803, "StarPos":[0.0,0.0,0.0], "SystemAllegiance":"Federation", "SystemEconomy":"$economy_Refinery;", "SystemEconomy_Localised":"Refinery", "SystemGovernment":"$government_Democracy;", "SystemGovernment_Localised":"Democracy", "SystemSecurity":"$SYSTEM_SECURITY_high;", "SystemSecurity_Localised":"High Security", "Population":22780919531, "JumpDist":13.790, "FuelUsed":0.371038, "FuelLevel":5.628962 }"#,
+        r#"{ "timestamp":"2025-03-10T18:30:00Z", "event":"PowerplayCollect", "Power":"Jerome Archer", "Type":"powerclassifiedintel", "Type_Localised":"Classified Power Intelligence", "Count":2 }"#,
+        r#"{ "timestamp":"2025-03-10T18:30:01Z", "event":"PowerplayMerits", "Power":"Jerome Archer", "MeritsGained":60, "TotalMerits":687328 }"#,
+        r#"{ "timestamp":"2025-03-11T18:30:01Z", "event":"PowerplayMerits", "Power":"Jerome Archer", "MeritsGained":40, "TotalMerits":687368 }"#,
+        r#"{ "timestamp":"2025-03-13T08:00:00Z", "event":"PowerplaySalary", "Power":"Jerome Archer", "Amount":1000000 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let tracker = PowerplayTracker::new(&lines);
+    assert_eq!(Some(Power::JeromeArcher), tracker.power);
+    let cycles = tracker.cycles();
+    assert_eq!(2, cycles.len());
+    assert_eq!(100, cycles[0].merits);
+    assert_eq!(
+        Some(&60),
+        cycles[0].by_activity.get(&PowerplayActivity::Collect)
+    );
+    assert_eq!(
+        Some(&40),
+        cycles[0].by_activity.get(&PowerplayActivity::Other)
+    );
+    assert_eq!(Some(&100), cycles[0].by_system.get(&EDString::from("Sol")));
+    assert_eq!(Credits(1000000), cycles[1].salary);
+
+    // rank 90 at 695000 merits
+    assert_eq!(Some(7632), tracker.merits_to_next_rank());
+    let since = "2025-03-10T18:00:00Z".parse().unwrap();
+    let now = "2025-03-12T18:00:00Z".parse().unwrap();
+    assert_eq!(50.0, tracker.merits_per_day(since, now));
+    assert_eq!(
+        Some(
+            Duration::days(152)
+                + Duration::hours(15)
+                + Duration::minutes(21)
+                + Duration::seconds(36)
+        ),
+        tracker.time_to_next_rank(since, now)
+    );
+}
+
+#[test]
+fn test_powerplay_tracker_without_status() {
+    let tracker = PowerplayTracker::new(&[]);
+    assert!(tracker.cycles().is_empty());
+    assert_eq!(None, tracker.merits_to_next_rank());
+    let since = "2025-03-12T18:00:00Z".parse().unwrap();
+    let now = "2025-03-10T18:00:00Z".parse().unwrap();
+    // `now` before `since`
+    assert_eq!(0.0, tracker.merits_per_day(since, now));
+    assert_eq!(None, tracker.time_to_next_rank(since, now));
+
+    let json = [
+        r#"{ "timestamp":"2025-03-10T18:00:00Z", "event":"PowerplayCollect", "Power":"Jerome Archer", "Type":"powerclassifiedintel", "Type_Localised":"Classified Power Intelligence", "Count":2 }"#,
+        // too long after the collection to be attributed to it, and no system known yet
+        r#"{ "timestamp":"2025-03-10T18:30:01Z", "event":"PowerplayMerits", "Power":"Jerome Archer", "MeritsGained":60, "TotalMerits":687328 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let tracker = PowerplayTracker::new(&lines);
+    assert_eq!(
+        Some(&60),
+        tracker.merits_by_activity().get(&PowerplayActivity::Other)
+    );
+    assert!(tracker.merits_by_system().is_empty());
+    // no Powerplay or PowerplayRank event for the rank
+    assert_eq!(None, tracker.rank);
+    assert_eq!(None, tracker.merits_to_next_rank());
+}
+
+#[test]
+fn test_powerplay_tracker_rank_pending() {
+    let json = [
+        r#"{ "timestamp":"2025-03-10T18:21:04Z", "event":"Powerplay", "Power":"Jerome Archer", "Rank":89, "Merits":687268, "TimePledged":4578634 }"#,
+        // over the threshold of rank 90, the PowerplayRank event hasn't been written yet
+        r#"{ "timestamp":"2025-03-10T18:30:01Z", "event":"PowerplayMerits", "Power":"Jerome Archer", "MeritsGained":8000, "TotalMerits":695268 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let tracker = PowerplayTracker::new(&lines);
+    assert_eq!(Some(0), tracker.merits_to_next_rank());
+    let since = "2025-03-10T18:00:00Z".parse().unwrap();
+    let now = "2025-03-11T18:00:00Z".parse().unwrap();
+    assert_eq!(
+        Some(Duration::zero()),
+        tracker.time_to_next_rank(since, now)
+    );
+}
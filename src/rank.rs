@@ -0,0 +1,354 @@
+use crate::{
+    commander::{
+        COMBAT_RANK, CQC_RANK, EDLogRank, EDLogReputation, EMPIRE_RANK, EXOBIOLOGIST_RANK,
+        EXPLORE_RANK, FEDERATION_RANK, SOLDIER_RANK, TRADE_RANK,
+    },
+    log_line::{EDLogEvent, EDLogLine},
+};
+use chrono::{DateTime, Duration, Utc};
+use std::{collections::BTreeMap, fmt::Display};
+use strum::{Display, EnumIter, IntoEnumIterator};
+
+/// progress samples older than this before the latest one are ignored for the rate of progress
+pub const RATE_WINDOW: Duration = Duration::days(30);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Display, EnumIter)]
+pub enum RankKind {
+    Combat,
+    Trade,
+    Explore,
+    Soldier,
+    Exobiologist,
+    Empire,
+    Federation,
+    #[strum(to_string = "CQC")]
+    Cqc,
+}
+
+impl RankKind {
+    pub fn names(&self) -> &'static [&'static str] {
+        match self {
+            RankKind::Combat => &COMBAT_RANK,
+            RankKind::Trade => &TRADE_RANK,
+            RankKind::Explore => &EXPLORE_RANK,
+            RankKind::Soldier => &SOLDIER_RANK,
+            RankKind::Exobiologist => &EXOBIOLOGIST_RANK,
+            RankKind::Empire => &EMPIRE_RANK,
+            RankKind::Federation => &FEDERATION_RANK,
+            // the journal starts CQC at Helpless
+            RankKind::Cqc => &CQC_RANK[1..],
+        }
+    }
+
+    /// superpower navy ranks, earned with the Empire and the Federation
+    pub fn is_navy(&self) -> bool {
+        matches!(self, RankKind::Empire | RankKind::Federation)
+    }
+
+    pub fn max_level(&self) -> u8 {
+        (self.names().len() - 1) as u8
+    }
+
+    fn of(&self, rank: &EDLogRank) -> Option<u8> {
+        match self {
+            RankKind::Combat => Some(rank.combat),
+            RankKind::Trade => Some(rank.trade),
+            RankKind::Explore => Some(rank.explore),
+            RankKind::Soldier => rank.soldier,
+            RankKind::Exobiologist => rank.exobiologist,
+            RankKind::Empire => Some(rank.empire),
+            RankKind::Federation => Some(rank.federation),
+            RankKind::Cqc => Some(rank.cqc),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RankLevel {
+    pub kind: RankKind,
+    pub level: u8,
+}
+
+impl RankLevel {
+    pub fn name(&self) -> &'static str {
+        self.kind
+            .names()
+            .get(self.level as usize)
+            .copied()
+            .unwrap_or("Unknown")
+    }
+
+    pub fn next(&self) -> Option<RankLevel> {
+        (self.level < self.kind.max_level()).then_some(RankLevel {
+            kind: self.kind,
+            level: self.level + 1,
+        })
+    }
+}
+
+impl Display for RankLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RankSample {
+    pub timestamp: DateTime<Utc>,
+    pub level: u8,
+    /// percentage towards the next level
+    pub percent: u8,
+}
+
+impl RankSample {
+    // levels and percentages as one number, a level is worth 100
+    fn value(&self) -> f64 {
+        self.level as f64 * 100.0 + self.percent as f64
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RankHistory {
+    pub kind: RankKind,
+    pub level: u8,
+    /// oldest first, only changes are kept
+    pub progress: Vec<RankSample>,
+    pub promotions: Vec<(DateTime<Utc>, RankLevel)>,
+}
+
+impl RankHistory {
+    fn new(kind: RankKind) -> RankHistory {
+        RankHistory {
+            kind,
+            level: 0,
+            progress: Vec::new(),
+            promotions: Vec::new(),
+        }
+    }
+
+    fn sample(&mut self, timestamp: DateTime<Utc>, percent: u8) {
+        let sample = RankSample {
+            timestamp,
+            level: self.level,
+            percent,
+        };
+        if self
+            .progress
+            .last()
+            .is_none_or(|s| s.level != sample.level || s.percent != sample.percent)
+        {
+            self.progress.push(sample);
+        }
+    }
+
+    pub fn current(&self) -> RankLevel {
+        RankLevel {
+            kind: self.kind,
+            level: self.level,
+        }
+    }
+
+    pub fn percent(&self) -> Option<u8> {
+        self.progress
+            .last()
+            .filter(|s| s.level == self.level)
+            .map(|s| s.percent)
+    }
+
+    /// percent points gained per day within `RATE_WINDOW` of the latest sample
+    pub fn rate_per_day(&self) -> Option<f64> {
+        let last = self.progress.last()?;
+        let first = self
+            .progress
+            .iter()
+            .find(|s| last.timestamp - s.timestamp <= RATE_WINDOW)?;
+        let days = (last.timestamp - first.timestamp).num_seconds() as f64 / 86400.0;
+        let rate = (last.value() - first.value()) / days;
+        (days > 0.0 && rate > 0.0).then_some(rate)
+    }
+
+    /// time to the next level at the recent rate of progress
+    pub fn time_to_next(&self) -> Option<Duration> {
+        self.current().next()?;
+        let remaining = 100.0 - self.percent()? as f64;
+        let days = remaining / self.rate_per_day()?;
+        Some(Duration::seconds((days * 86400.0) as i64))
+    }
+}
+
+/// Ranks, rank progress and superpower reputation over time
+#[derive(Clone, Debug, Default)]
+pub struct Ranks {
+    ranks: BTreeMap<RankKind, RankHistory>,
+    pub reputation: Vec<(DateTime<Utc>, EDLogReputation)>,
+}
+
+impl Ranks {
+    /// build the ranks from log lines sorted by timestamp
+    pub fn new(lines: &[EDLogLine]) -> Ranks {
+        let mut ranks = Ranks::default();
+        for line in lines {
+            ranks.add_line(line);
+        }
+        ranks
+    }
+
+    fn history(&mut self, kind: RankKind) -> &mut RankHistory {
+        self.ranks
+            .entry(kind)
+            .or_insert_with(|| RankHistory::new(kind))
+    }
+
+    pub fn add_line(&mut self, line: &EDLogLine) {
+        let timestamp = *line.timestamp();
+        match line.event() {
+            EDLogEvent::Rank(rank) => {
+                for kind in RankKind::iter() {
+                    if let Some(level) = kind.of(rank) {
+                        self.history(kind).level = level;
+                    }
+                }
+            }
+            // progress uses the rank fields for percentages
+            EDLogEvent::Progress(progress) => {
+                for kind in RankKind::iter() {
+                    if let Some(percent) = kind.of(progress) {
+                        self.history(kind).sample(timestamp, percent);
+                    }
+                }
+            }
+            EDLogEvent::Promotion(promotion) => {
+                let promoted = [
+                    (RankKind::Combat, promotion.combat),
+                    (RankKind::Trade, promotion.trade),
+                    (RankKind::Explore, promotion.explore),
+                    (RankKind::Soldier, promotion.soldier),
+                    (RankKind::Exobiologist, promotion.exobiologist),
+                    (RankKind::Empire, promotion.empire),
+                    (RankKind::Federation, promotion.federation),
+                    (RankKind::Cqc, promotion.cqc),
+                ];
+                for (kind, level) in promoted {
+                    let Some(level) = level else {
+                        continue;
+                    };
+                    let history = self.history(kind);
+                    history.level = level;
+                    history
+                        .promotions
+                        .push((timestamp, RankLevel { kind, level }));
+                    history.sample(timestamp, 0);
+                }
+            }
+            EDLogEvent::Reputation(reputation) => {
+                self.reputation.push((timestamp, reputation.clone()));
+            }
+            _ => {}
+        }
+    }
+
+    pub fn get(&self, kind: RankKind) -> Option<&RankHistory> {
+        self.ranks.get(&kind)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &RankHistory> {
+        self.ranks.values()
+    }
+
+    pub fn navy(&self) -> impl Iterator<Item = &RankHistory> {
+        self.iter().filter(|r| r.kind.is_navy())
+    }
+
+    /// all promotions, oldest first
+    pub fn promotions(&self) -> Vec<(DateTime<Utc>, RankLevel)> {
+        let mut promotions: Vec<_> = self
+            .iter()
+            .flat_map(|r| r.promotions.iter().copied())
+            .collect();
+        promotions.sort_by_key(|(timestamp, _)| *timestamp);
+        promotions
+    }
+}
+
+#[test]
+fn test_ranks() {
+    let json = [
+        r#"{ "timestamp":"2025-09-01T18:00:00Z", "event":"Rank", "Combat":9, "Trade":12, "Explore":7, "Soldier":8, "Exobiologist":8, "Empire":12, "Federation":12, "CQC":0 }"#,
+        r#"{ "timestamp":"2025-09-01T18:00:00Z", "event":"Progress", "Combat":10, "Trade":100, "Explore":40, "Soldier":0, "Exobiologist":0, "Empire":20, "Federation":33, "CQC":0 }"#,
+        r#"{ "timestamp":"2025-09-11T18:00:00Z", "event":"Rank", "Combat":9, "Trade":12, "Explore":7, "Soldier":8, "Exobiologist":8, "Empire":12, "Federation":12, "CQC":0 }"#,
+        r#"{ "timestamp":"2025-09-11T18:00:00Z", "event":"Progress", "Combat":10, "Trade":100, "Explore":60, "Soldier":0, "Exobiologist":0, "Empire":20, "Federation":33, "CQC":0 }"#,
+        r#"{ "timestamp":"2025-09-12T18:00:00Z", "event":"Promotion", "Federation":13 }"#,
+        r#"{ "timestamp":"2025-09-12T19:00:00Z", "event":"Reputation", "Empire":75.0, "Federation":100.0, "Independent":10.0, "Alliance":-5.0 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let ranks = Ranks::new(&lines);
+    let explore = ranks.get(RankKind::Explore).unwrap();
+    assert_eq!("Pioneer", explore.current().name());
+    assert_eq!("Elite", explore.current().next().unwrap().to_string());
+    assert_eq!(Some(60), explore.percent());
+    assert_eq!(Some(2.0), explore.rate_per_day());
+    assert_eq!(Some(Duration::days(20)), explore.time_to_next());
+    // no progress made, so no estimate
+    assert_eq!(None, ranks.get(RankKind::Combat).unwrap().time_to_next());
+
+    let federation = ranks.get(RankKind::Federation).unwrap();
+    assert_eq!("Vice Admiral", federation.current().name());
+    assert_eq!(Some(0), federation.percent());
+    assert_eq!(2, ranks.navy().count());
+    assert_eq!(1, ranks.promotions().len());
+    assert_eq!(
+        "Helpless",
+        ranks.get(RankKind::Cqc).unwrap().current().name()
+    );
+    assert_eq!(1, ranks.reputation.len());
+}
+
+#[test]
+fn test_ranks_empty() {
+    let ranks = Ranks::new(&[]);
+    assert!(ranks.get(RankKind::Combat).is_none());
+    assert_eq!(0, ranks.navy().count());
+    assert!(ranks.promotions().is_empty());
+    assert_eq!(
+        "Unknown",
+        RankLevel {
+            kind: RankKind::Combat,
+            level: 14
+        }
+        .name()
+    );
+}
+
+#[test]
+fn test_ranks_edges() {
+    let json = [
+        // progress before the first Rank event, and without the Odyssey ranks
+        r#"{ "timestamp":"2025-07-01T18:00:00Z", "event":"Progress", "Combat":90, "Trade":0, "Explore":10, "Empire":0, "Federation":0, "CQC":0 }"#,
+        r#"{ "timestamp":"2025-09-01T18:00:00Z", "event":"Rank", "Combat":9, "Trade":13, "Explore":7, "Empire":0, "Federation":0, "CQC":0 }"#,
+        r#"{ "timestamp":"2025-09-01T18:00:00Z", "event":"Progress", "Combat":10, "Trade":0, "Explore":10, "Empire":0, "Federation":0, "CQC":0 }"#,
+        r#"{ "timestamp":"2025-09-11T18:00:00Z", "event":"Progress", "Combat":5, "Trade":0, "Explore":20, "Empire":0, "Federation":0, "CQC":0 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let ranks = Ranks::new(&lines);
+    assert!(ranks.get(RankKind::Soldier).is_none());
+    assert!(ranks.get(RankKind::Exobiologist).is_none());
+
+    // the July sample, taken at level 0 before the Rank event, is outside RATE_WINDOW
+    let explore = ranks.get(RankKind::Explore).unwrap();
+    assert_eq!(3, explore.progress.len());
+    assert_eq!(Some(1.0), explore.rate_per_day());
+
+    // progress went down
+    let combat = ranks.get(RankKind::Combat).unwrap();
+    assert_eq!(Some(5), combat.percent());
+    assert_eq!(None, combat.rate_per_day());
+    assert_eq!(None, combat.time_to_next());
+
+    // Elite V is the highest trade rank
+    let trade = ranks.get(RankKind::Trade).unwrap();
+    assert_eq!(13, RankKind::Trade.max_level());
+    assert_eq!(None, trade.current().next());
+    assert_eq!(None, trade.time_to_next());
+}
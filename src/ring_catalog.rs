@@ -0,0 +1,213 @@
+use crate::{
+    EDString,
+    exploration::{BodySignalType, ReserveLevel, RingClass},
+    galaxy::{KnownSystems, distance},
+    log_line::{EDLogEvent, EDLogLine},
+    mining::{MiningSession, MiningSessions},
+    system_address::SystemAddress,
+};
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Clone, Debug)]
+pub struct RingInfo {
+    pub name: EDString,
+    pub system_address: u64,
+    pub star_system: Option<EDString>,
+    pub ring_class: Option<RingClass>,
+    pub reserve_level: Option<ReserveLevel>,
+    /// hotspot counts from surface scanning the ring
+    pub hotspots: BTreeMap<BodySignalType, u64>,
+}
+
+impl RingInfo {
+    fn new(name: EDString, system_address: u64) -> RingInfo {
+        RingInfo {
+            name,
+            system_address,
+            star_system: None,
+            ring_class: None,
+            reserve_level: None,
+            hotspots: BTreeMap::new(),
+        }
+    }
+
+    pub fn hotspot_count(&self, hotspot: BodySignalType) -> u64 {
+        self.hotspots.get(&hotspot).copied().unwrap_or(0)
+    }
+
+    pub fn is_mapped(&self) -> bool {
+        !self.hotspots.is_empty()
+    }
+}
+
+/// Rings we scanned, with their hotspots and what we mined there
+#[derive(Clone, Debug, Default)]
+pub struct RingCatalog {
+    rings: HashMap<EDString, RingInfo>,
+    pub systems: KnownSystems,
+    pub mining: MiningSessions,
+}
+
+impl RingCatalog {
+    /// build the catalog from log lines sorted by timestamp
+    pub fn new(lines: &[EDLogLine]) -> RingCatalog {
+        let mut catalog = RingCatalog::default();
+        for line in lines {
+            catalog.add_line(line);
+        }
+        catalog
+    }
+
+    fn ring(&mut self, name: EDString, system_address: u64) -> &mut RingInfo {
+        self.rings
+            .entry(name.clone())
+            .or_insert_with(|| RingInfo::new(name, system_address))
+    }
+
+    pub fn add_line(&mut self, line: &EDLogLine) {
+        self.systems.add_line(line);
+        self.mining.add_line(line);
+        match line.event() {
+            EDLogEvent::Scan(scan) => {
+                let Some(system_address) = scan.system_address else {
+                    return;
+                };
+                let rings = scan
+                    .body_details
+                    .iter()
+                    .flat_map(|d| d.rings.iter().flatten());
+                for ring in rings {
+                    let info = self.ring(ring.name.clone(), system_address);
+                    info.star_system = scan.star_system.clone().or_else(|| info.star_system.take());
+                    info.ring_class = Some(ring.ring_class);
+                    // the reserve level of a body is the one of its rings
+                    info.reserve_level = scan.reserve_level.or(info.reserve_level);
+                }
+            }
+            EDLogEvent::SAASignalsFound(signals) => {
+                if !signals.body_name.as_str().ends_with(" Ring") {
+                    return;
+                }
+                let info = self.ring(signals.body_name.clone(), signals.system_address);
+                info.hotspots = signals
+                    .signals
+                    .iter()
+                    .filter(|s| s.body_signal_type.commodity().is_some())
+                    .map(|s| (s.body_signal_type, s.count))
+                    .collect();
+            }
+            _ => {}
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&RingInfo> {
+        self.rings.values().find(|r| r.name.as_str() == name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &RingInfo> {
+        self.rings.values()
+    }
+
+    pub fn sessions(&self, ring: &RingInfo) -> impl Iterator<Item = &MiningSession> {
+        self.mining.at(ring.name.as_str())
+    }
+
+    /// position of the ring's system, estimated from its address if we never visited it
+    pub fn position(&self, ring: &RingInfo) -> [f64; 3] {
+        self.systems
+            .position(ring.system_address)
+            .unwrap_or_else(|| SystemAddress::decode(ring.system_address).estimated_position())
+    }
+
+    /// rings within `radius` light years of `from` that match `filter`, nearest first
+    pub fn search(
+        &self,
+        from: &[f64; 3],
+        radius: f64,
+        filter: impl Fn(&RingInfo) -> bool,
+    ) -> Vec<(&RingInfo, f64)> {
+        let mut result: Vec<_> = self
+            .rings
+            .values()
+            .filter(|r| filter(r))
+            .map(|r| (r, distance(from, &self.position(r))))
+            .filter(|(_, d)| *d <= radius)
+            .collect();
+        result.sort_by(|a, b| a.1.total_cmp(&b.1));
+        result
+    }
+}
+
+#[test]
+fn test_ring_catalog() {
+    let json = [
+        r#"{ "timestamp":"2025-03-01T09:00:00Z", "event":"FSDJump", "StarSystem":"BD-11 1025", "SystemAddress":1733187048130, "StarPos":[52.87500,-58.31250,-89.87500], "SystemAllegiance":"", "SystemEconomy":"$economy_None;", "SystemEconomy_Localised":"None", "SystemGovernment":"$government_None;", "SystemGovernment_Localised":"None", "SystemSecurity":"$GAlAXY_MAP_INFO_state_anarchy;", "SystemSecurity_Localised":"Anarchy", "Population":0, "JumpDist":13.790, "FuelUsed":0.371038, "FuelLevel":5.628962 }"#,
+        r#"{ "timestamp":"2025-03-01T09:05:00Z", "event":"Scan", "ScanType":"Detailed", "BodyName":"BD-11 1025 2", "BodyID":8, "StarSystem":"BD-11 1025", "SystemAddress":1733187048130, "DistanceFromArrivalLS":1200.0, "TidalLock":false, "TerraformState":"", "PlanetClass":"Sudarsky class I gas giant", "Atmosphere":"", "AtmosphereType":"None", "Volcanism":"", "MassEM":300.0, "Radius":70000000.0, "SurfaceGravity":25.0, "SurfaceTemperature":150.0, "SurfacePressure":0.0, "Landable":false, "Composition":{ "Ice":0.0, "Rock":0.0, "Metal":0.0 }, "Rings":[ { "Name":"BD-11 1025 2 A Ring", "RingClass":"eRingClass_Metalic", "MassMT":1.0e10, "InnerRad":1.0e8, "OuterRad":2.0e8 } ], "ReserveLevel":"PristineResources", "WasDiscovered":true, "WasMapped":false }"#,
+        r#"{ "timestamp":"2025-03-01T09:10:00Z", "event":"SAASignalsFound", "BodyName":"BD-11 1025 2 A Ring", "SystemAddress":1733187048130, "BodyID":9, "Signals":[ { "Type":"Platinum", "Count":2 }, { "Type":"Painite", "Count":1 } ] }"#,
+        r#"{ "timestamp":"2025-03-01T10:10:00Z", "event":"SupercruiseExit", "Taxi":false, "Multicrew":false, "StarSystem":"BD-11 1025", "SystemAddress":1733187048130, "Body":"BD-11 1025 2 A Ring", "BodyID":9, "BodyType":"PlanetaryRing" }"#,
+        r#"{ "timestamp":"2025-03-01T10:30:00Z", "event":"MiningRefined", "Type":"$platinum_name;", "Type_Localised":"Platinum" }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let catalog = RingCatalog::new(&lines);
+    let ring = catalog.get("BD-11 1025 2 A Ring").unwrap();
+    assert_eq!(Some(RingClass::Metallic), ring.ring_class);
+    assert_eq!(Some(ReserveLevel::Pristine), ring.reserve_level);
+    assert_eq!(2, ring.hotspot_count(BodySignalType::Platinum));
+    assert_eq!(1, catalog.sessions(ring).count());
+
+    let found = catalog.search(&[0.0, 0.0, 0.0], 150.0, |r| {
+        r.ring_class == Some(RingClass::Metallic)
+            && r.reserve_level == Some(ReserveLevel::Pristine)
+            && r.hotspot_count(BodySignalType::Platinum) >= 2
+    });
+    assert_eq!(1, found.len());
+    assert!(
+        catalog
+            .search(&[0.0, 0.0, 0.0], 150.0, |r| r
+                .hotspot_count(BodySignalType::Painite)
+                >= 2)
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_ring_catalog_empty() {
+    let catalog = RingCatalog::new(&[]);
+    assert_eq!(0, catalog.iter().count());
+    assert!(catalog.get("BD-11 1025 2 A Ring").is_none());
+    assert!(
+        catalog
+            .search(&[0.0, 0.0, 0.0], f64::MAX, |_| true)
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_ring_catalog_signals_before_scan() {
+    let json = [
+        r#"{ "timestamp":"2025-03-01T09:00:00Z", "event":"SAASignalsFound", "BodyName":"BD-11 1025 2 A Ring", "SystemAddress":1733187048130, "BodyID":9, "Signals":[ { "Type":"$SAA_SignalType_Other;", "Type_Localised":"Other", "Count":1 } ] }"#,
+        // only rings are catalogued
+        r#"{ "timestamp":"2025-03-01T09:01:00Z", "event":"SAASignalsFound", "BodyName":"BD-11 1025 2", "SystemAddress":1733187048130, "BodyID":8, "Signals":[ { "Type":"$SAA_SignalType_Geological;", "Type_Localised":"Geological", "Count":3 } ] }"#,
+        r#"{ "timestamp":"2025-03-01T09:05:00Z", "event":"Scan", "ScanType":"Detailed", "BodyName":"BD-11 1025 2", "BodyID":8, "StarSystem":"BD-11 1025", "SystemAddress":1733187048130, "DistanceFromArrivalLS":1200.0, "TidalLock":false, "TerraformState":"", "PlanetClass":"Sudarsky class I gas giant", "Atmosphere":"", "AtmosphereType":"None", "Volcanism":"", "MassEM":300.0, "Radius":70000000.0, "SurfaceGravity":25.0, "SurfaceTemperature":150.0, "SurfacePressure":0.0, "Landable":false, "Composition":{ "Ice":0.0, "Rock":0.0, "Metal":0.0 }, "Rings":[ { "Name":"BD-11 1025 2 A Ring", "RingClass":"eRingClass_Metalic", "MassMT":1.0e10, "InnerRad":1.0e8, "OuterRad":2.0e8 } ], "WasDiscovered":true, "WasMapped":false }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let catalog = RingCatalog::new(&lines);
+    assert_eq!(1, catalog.iter().count());
+    let ring = catalog.get("BD-11 1025 2 A Ring").unwrap();
+    assert_eq!(Some(RingClass::Metallic), ring.ring_class);
+    assert_eq!(None, ring.reserve_level);
+    assert_eq!("BD-11 1025", ring.star_system.as_ref().unwrap().as_str());
+    // surface scanned, but without any hotspot
+    assert!(!ring.is_mapped());
+    assert_eq!(0, catalog.sessions(ring).count());
+
+    // never jumped there, the position comes from the system address
+    let position = catalog.position(ring);
+    assert_eq!(
+        SystemAddress::decode(1733187048130).estimated_position(),
+        position
+    );
+    assert_eq!(1, catalog.search(&position, 0.0, |_| true).len());
+}
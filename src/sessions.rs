@@ -0,0 +1,282 @@
+use crate::{
+    EDString,
+    common_types::Credits,
+    log_line::{EDLogEvent, EDLogEventDiscriminants, EDLogLine, GameMode},
+    ship_type::ShipType,
+};
+use chrono::{DateTime, Duration, Utc};
+
+/// a gap in the journal longer than this starts a new session
+pub const SESSION_GAP: Duration = Duration::hours(1);
+
+/// events worth mentioning in a session summary
+pub const NOTABLE_EVENTS: [EDLogEventDiscriminants; 8] = [
+    EDLogEventDiscriminants::Died,
+    EDLogEventDiscriminants::Promotion,
+    EDLogEventDiscriminants::Interdicted,
+    EDLogEventDiscriminants::ShipyardBuy,
+    EDLogEventDiscriminants::EngineerCraft,
+    EDLogEventDiscriminants::CommunityGoalReward,
+    EDLogEventDiscriminants::CarrierJump,
+    EDLogEventDiscriminants::ShipyardSell,
+];
+
+/// events the game writes while we are idle or quitting, they don't start a session
+pub const PASSIVE_EVENTS: [EDLogEventDiscriminants; 4] = [
+    EDLogEventDiscriminants::Shutdown,
+    EDLogEventDiscriminants::Music,
+    EDLogEventDiscriminants::Friends,
+    EDLogEventDiscriminants::ReceiveText,
+];
+
+#[derive(Clone, Debug)]
+pub struct Session {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub commander: Option<EDString>,
+    pub game_mode: Option<GameMode>,
+    pub group: Option<EDString>,
+    pub odyssey: Option<bool>,
+    pub horizons: Option<bool>,
+    /// credits when the game was loaded
+    pub start_credits: Option<Credits>,
+    /// ships flown, in order
+    pub ships: Vec<ShipType>,
+    pub jumps: u64,
+    pub distance: f64,
+    /// systems visited, in order of first visit
+    pub systems: Vec<EDString>,
+    /// income from sales, missions, vouchers and data
+    pub earned: Credits,
+    pub notable: Vec<(DateTime<Utc>, EDLogEventDiscriminants)>,
+    pub lines: usize,
+    loaded: bool,
+}
+
+impl Session {
+    fn new(timestamp: DateTime<Utc>) -> Session {
+        Session {
+            start: timestamp,
+            end: timestamp,
+            commander: None,
+            game_mode: None,
+            group: None,
+            odyssey: None,
+            horizons: None,
+            start_credits: None,
+            ships: Vec::new(),
+            jumps: 0,
+            distance: 0.0,
+            systems: Vec::new(),
+            earned: Credits(0),
+            notable: Vec::new(),
+            lines: 0,
+            loaded: false,
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+
+    fn visit(&mut self, system: EDString) {
+        if !self.systems.contains(&system) {
+            self.systems.push(system);
+        }
+    }
+
+    fn add_line(&mut self, line: &EDLogLine) {
+        self.lines += 1;
+        let event = line.event();
+        let kind = EDLogEventDiscriminants::from(event);
+        if !PASSIVE_EVENTS.contains(&kind) {
+            self.end = *line.timestamp();
+        }
+        if NOTABLE_EVENTS.contains(&kind) {
+            self.notable.push((*line.timestamp(), kind));
+        }
+        match event {
+            EDLogEvent::FileHeader(header) => {
+                self.odyssey = header.odyssey.or(self.odyssey);
+            }
+            EDLogEvent::LoadGame(game) => {
+                self.loaded = true;
+                self.commander = Some(game.commander.clone());
+                self.game_mode = game.game_mode;
+                self.group = game.group.clone();
+                self.odyssey = game.odyssey.or(self.odyssey);
+                self.horizons = game.horizons;
+                self.start_credits = Some(game.credits);
+            }
+            EDLogEvent::Loadout(loadout) if self.ships.last() != Some(&loadout.ship) => {
+                self.ships.push(loadout.ship);
+            }
+            EDLogEvent::Location(location) => self.visit(location.star_system.clone()),
+            EDLogEvent::FSDJump(jump) => {
+                self.jumps += 1;
+                self.distance += jump.jump_dist;
+                self.visit(jump.star_system.clone());
+            }
+            EDLogEvent::CarrierJump(jump) => self.visit(jump.star_system.clone()),
+            EDLogEvent::MarketSell(sell) => self.earned += sell.total_sale,
+            EDLogEvent::MissionCompleted(mission) => {
+                self.earned += mission.reward.unwrap_or_default();
+            }
+            EDLogEvent::RedeemVoucher(voucher) => self.earned += voucher.amount,
+            EDLogEvent::SellExplorationData(data) => {
+                self.earned += data.total_earnings.unwrap_or(data.base_value + data.bonus);
+            }
+            EDLogEvent::MultiSellExplorationData(data) => self.earned += data.total_earnings,
+            EDLogEvent::SellOrganicData(data) => {
+                self.earned += data.bio_data.iter().map(|b| b.value + b.bonus).sum();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Play sessions, split on game start, shutdown and gaps in the journal
+#[derive(Clone, Debug, Default)]
+pub struct Sessions {
+    sessions: Vec<Session>,
+    closed: bool,
+}
+
+impl Sessions {
+    /// split log lines sorted by timestamp, from one or several journal files
+    pub fn new(lines: &[EDLogLine]) -> Sessions {
+        let mut sessions = Sessions::default();
+        for line in lines {
+            sessions.add_line(line);
+        }
+        sessions
+    }
+
+    fn starts_session(&self, line: &EDLogLine) -> bool {
+        let Some(current) = self.sessions.last() else {
+            return true;
+        };
+        if PASSIVE_EVENTS.contains(&EDLogEventDiscriminants::from(line.event())) {
+            return false;
+        }
+        if self.closed || *line.timestamp() - current.end > SESSION_GAP {
+            return true;
+        }
+        match line.event() {
+            // later parts of a journal continue the session
+            EDLogEvent::FileHeader(header) => header.part <= 1,
+            EDLogEvent::LoadGame(_) => current.loaded,
+            _ => false,
+        }
+    }
+
+    pub fn add_line(&mut self, line: &EDLogLine) {
+        if self.starts_session(line) {
+            self.sessions.push(Session::new(*line.timestamp()));
+            self.closed = false;
+        }
+        self.sessions
+            .last_mut()
+            .expect("session was just pushed")
+            .add_line(line);
+        if matches!(line.event(), EDLogEvent::Shutdown) {
+            self.closed = true;
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Session> {
+        self.sessions.iter()
+    }
+
+    pub fn last(&self) -> Option<&Session> {
+        self.sessions.last()
+    }
+
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+}
+
+#[test]
+fn test_sessions() {
+    let json = [
+        r#"{ "timestamp":"2025-11-30T20:10:00Z", "event":"Fileheader", "part":1, "language":"English/UK", "Odyssey":true, "gameversion":"4.2.2.1", "build":"r321306/r0 " }"#,
+        r#"{ "timestamp":"2025-11-30T20:10:08Z", "event":"LoadGame", "FID":"F1234567", "Commander":"MySelf", "Horizons":true, "Odyssey":true, "Ship":"Python_NX", "Ship_Localised":"Python Mk II", "ShipID":12, "ShipName":"MyName", "ShipIdent":"IDENT1", "FuelLevel":16.000000, "FuelCapacity":16.000000, "GameMode":"Solo", "Credits":12341234, "Loan":0, "language":"English/UK", "gameversion":"4.2.2.1", "build":"r321306/r0 " }"#,
+        r#"{ "timestamp":"2025-11-30T20:20:00Z", "event":"FSDJump", "StarSystem":"Kholul", "SystemAddress":2415659059547, "StarPos":[-46.93750,-7.84375,-152.18750], "SystemAllegiance":"", "SystemEconomy":"$economy_None;", "SystemEconomy_Localised":"None", "SystemGovernment":"$government_None;", "SystemGovernment_Localised":"None", "SystemSecurity":"$GAlAXY_MAP_INFO_state_anarchy;", "SystemSecurity_Localised":"Anarchy", "Population":0, "JumpDist":13.790, "FuelUsed":0.371038, "FuelLevel":5.628962 }"#,
+        r#"{ "timestamp":"2025-11-30T20:40:00Z", "event":"MarketSell", "MarketID":3228342528, "Type":"platinum", "Count":2, "SellPrice":250000, "TotalSale":500000, "AvgPricePaid":0 }"#,
+        r#"{ "timestamp":"2025-11-30T21:00:00Z", "event":"Fileheader", "part":2, "language":"English/UK", "Odyssey":true, "gameversion":"4.2.2.1", "build":"r321306/r0 " }"#,
+        r#"{ "timestamp":"2025-11-30T21:05:00Z", "event":"Shutdown" }"#,
+        r#"{ "timestamp":"2025-12-01T19:00:00Z", "event":"LoadGame", "Commander":"MySelf", "Horizons":true, "GameMode":"Group", "Group":"REINIER", "Credits":12841234, "Loan":0 }"#,
+        r#"{ "timestamp":"2025-12-01T23:00:00Z", "event":"Shutdown" }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let sessions = Sessions::new(&lines);
+    assert_eq!(2, sessions.len());
+    let first = sessions.iter().next().unwrap();
+    assert_eq!(6, first.lines);
+    assert_eq!(Some(GameMode::Solo), first.game_mode);
+    assert_eq!(Some(true), first.odyssey);
+    assert_eq!(1, first.jumps);
+    assert_eq!(
+        vec!["Kholul"],
+        first.systems.iter().map(|s| s.as_str()).collect::<Vec<_>>()
+    );
+    assert_eq!(Credits(500000), first.earned);
+    // until the last active line, the shutdown doesn't count
+    assert_eq!(Duration::minutes(50), first.duration());
+
+    let second = sessions.last().unwrap();
+    assert_eq!(Some(GameMode::Group), second.game_mode);
+    assert_eq!("REINIER", second.group.unwrap().as_str());
+    // the shutdown hours later closes the session without counting the idle time
+    assert_eq!(2, second.lines);
+    assert_eq!(Duration::zero(), second.duration());
+}
+
+#[test]
+fn test_sessions_empty() {
+    let sessions = Sessions::new(&[]);
+    assert!(sessions.is_empty());
+    assert!(sessions.last().is_none());
+}
+
+#[test]
+fn test_sessions_without_load_game() {
+    let json = [
+        // the journal starts in the middle of a session
+        r#"{ "timestamp":"2025-11-30T20:20:00Z", "event":"FSDJump", "StarSystem":"Kholul", "SystemAddress":2415659059547, "StarPos":[-46.93750,-7.84375,-152.18750], "SystemAllegiance":"", "SystemEconomy":"$economy_None;", "SystemEconomy_Localised":"None", "SystemGovernment":"$government_None;", "SystemGovernment_Localised":"None", "SystemSecurity":"$GAlAXY_MAP_INFO_state_anarchy;", "SystemSecurity_Localised":"Anarchy", "Population":0, "JumpDist":13.790, "FuelUsed":0.371038, "FuelLevel":5.628962 }"#,
+        // idle hours later, still the same session
+        r#"{ "timestamp":"2025-11-30T23:00:00Z", "event":"Music", "MusicTrack":"MainMenu" }"#,
+        r#"{ "timestamp":"2025-11-30T23:05:00Z", "event":"LoadGame", "Commander":"MySelf", "Horizons":true, "GameMode":"Open", "Credits":12341234, "Loan":0 }"#,
+        // loading the game again without a shutdown
+        r#"{ "timestamp":"2025-11-30T23:10:00Z", "event":"LoadGame", "Commander":"MySelf", "Horizons":true, "GameMode":"Solo", "Credits":12341234, "Loan":0 }"#,
+        r#"{ "timestamp":"2025-11-30T23:15:00Z", "event":"FSDJump", "StarSystem":"Kholul", "SystemAddress":2415659059547, "StarPos":[-46.93750,-7.84375,-152.18750], "SystemAllegiance":"", "SystemEconomy":"$economy_None;", "SystemEconomy_Localised":"None", "SystemGovernment":"$government_None;", "SystemGovernment_Localised":"None", "SystemSecurity":"$GAlAXY_MAP_INFO_state_anarchy;", "SystemSecurity_Localised":"Anarchy", "Population":0, "JumpDist":13.790, "FuelUsed":0.371038, "FuelLevel":5.628962 }"#,
+        r#"{ "timestamp":"2025-11-30T23:16:00Z", "event":"FSDJump", "StarSystem":"Kholul", "SystemAddress":2415659059547, "StarPos":[-46.93750,-7.84375,-152.18750], "SystemAllegiance":"", "SystemEconomy":"$economy_None;", "SystemEconomy_Localised":"None", "SystemGovernment":"$government_None;", "SystemGovernment_Localised":"None", "SystemSecurity":"$GAlAXY_MAP_INFO_state_anarchy;", "SystemSecurity_Localised":"Anarchy", "Population":0, "JumpDist":13.790, "FuelUsed":0.371038, "FuelLevel":5.628962 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let sessions = Sessions::new(&lines);
+    let sessions: Vec<&Session> = sessions.iter().collect();
+    assert_eq!(3, sessions.len());
+
+    let first = sessions[0];
+    assert_eq!(None, first.commander);
+    assert_eq!(None, first.start_credits);
+    assert_eq!(2, first.lines);
+    assert_eq!(Duration::zero(), first.duration());
+
+    // a gap of more than SESSION_GAP starts a new session, even without a LoadGame
+    assert_eq!(Some(GameMode::Open), sessions[1].game_mode);
+    assert_eq!(1, sessions[1].lines);
+
+    let last = sessions[2];
+    assert_eq!(Some(GameMode::Solo), last.game_mode);
+    assert_eq!(2, last.jumps);
+    // systems are listed once
+    assert_eq!(1, last.systems.len());
+}
@@ -27,12 +27,12 @@ pub struct Inventory {
              "Type_Localised":"Alliance Trade Agreements", "Count":2, "Abandoned":false, "PowerplayOrigin":"" })]
 pub struct EDLogEjectCargo {
     #[serde(rename = "Type")]
-    cargo_type: MarketItemType,
+    pub cargo_type: MarketItemType,
     #[serde(rename = "Type_Localised")]
-    cargo_type_localised: Option<EDString>,
-    count: u64,
-    abandoned: bool,
-    powerplay_origin: Option<EDString>,
+    pub cargo_type_localised: Option<EDString>,
+    pub count: u64,
+    pub abandoned: bool,
+    pub powerplay_origin: Option<EDString>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
@@ -47,7 +47,7 @@ pub struct EDLogCargo {
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogShieldState {
-    shields_up: bool,
+    pub shields_up: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
@@ -115,23 +115,23 @@ pub struct EDLogShipTargeted {
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogInterdiction {
-    success: bool,
-    is_player: bool,
-    faction: EDString,
-    power: Option<EDString>,
+    pub success: bool,
+    pub is_player: bool,
+    pub faction: EDString,
+    pub power: Option<EDString>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogInterdicted {
-    submitted: bool,
-    interdictor: Option<EDString>,
+    pub submitted: bool,
+    pub interdictor: Option<EDString>,
     #[serde(rename = "Interdictor_Localised")]
-    interdictor_localised: Option<EDString>,
-    is_player: bool,
-    combat_rank: Option<u8>,
-    faction: Option<EDString>,
-    is_thargoid: Option<bool>,
+    pub interdictor_localised: Option<EDString>,
+    pub is_player: bool,
+    pub combat_rank: Option<u8>,
+    pub faction: Option<EDString>,
+    pub is_thargoid: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
@@ -152,31 +152,31 @@ pub struct EDLogRebootRepair {
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogUnderAttack {
-    target: Option<EDString>,
+    pub target: Option<EDString>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogHullDamage {
-    health: f64,
-    player_pilot: bool,
-    fighter: Option<bool>,
+    pub health: f64,
+    pub player_pilot: bool,
+    pub fighter: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct SynthesisMaterial {
-    name: EDString,
+    pub name: EDString,
     #[serde(rename = "Name_Localised")]
-    name_localised: Option<EDString>,
-    count: u64,
+    pub name_localised: Option<EDString>,
+    pub count: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogSynthesis {
-    name: EDString,
-    materials: Vec<SynthesisMaterial>,
+    pub name: EDString,
+    pub materials: Vec<SynthesisMaterial>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
@@ -238,7 +238,7 @@ pub struct EDLogDockFighter {
 #[serde(deny_unknown_fields)]
 pub struct EDLogFighterDestroyed {
     #[serde(rename = "ID")]
-    id: u64,
+    pub id: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
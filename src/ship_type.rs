@@ -1,7 +1,15 @@
-use serde::{Deserialize, Serialize};
+use serde::{
+    Deserialize, Serialize,
+    de::{
+        IntoDeserializer,
+        value::{Error as ValueError, StrDeserializer},
+    },
+};
 use strum::Display;
 
-#[derive(Serialize, Deserialize, Clone, Debug, Display, Copy, PartialEq)]
+#[derive(
+    Serialize, Deserialize, Clone, Debug, Display, Copy, PartialEq, Eq, Hash, PartialOrd, Ord,
+)]
 #[serde(rename_all = "lowercase")]
 pub enum ShipType {
     #[strum(to_string = "Adder")]
@@ -225,3 +233,15 @@ pub enum ShipType {
     #[serde(rename = "TacticalSuit_Class5")]
     TacticalSuitClass5,
 }
+
+impl ShipType {
+    /// ship type from a journal name like "vulture" or "Krait_MkII", in any case
+    pub fn from_name(name: &str) -> Option<ShipType> {
+        let deserializer: StrDeserializer<ValueError> = name.into_deserializer();
+        ShipType::deserialize(deserializer).ok().or_else(|| {
+            let name = name.to_lowercase();
+            let deserializer: StrDeserializer<ValueError> = name.as_str().into_deserializer();
+            ShipType::deserialize(deserializer).ok()
+        })
+    }
+}
@@ -0,0 +1,433 @@
+use crate::{
+    EDString,
+    common_types::{Allegiance, StationEconomy, StationInformation, StationService, StationType},
+    docking::LandingPads,
+    galaxy::{KnownSystems, distance},
+    log_line::{EDLogEvent, EDLogEventDiscriminants, EDLogLine},
+};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use strum::Display;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Display)]
+pub enum PadSize {
+    Small,
+    Medium,
+    Large,
+}
+
+impl PadSize {
+    /// largest pad of a station type, for stations we never requested docking at
+    pub fn largest_for(station_type: StationType) -> Option<PadSize> {
+        match station_type {
+            StationType::Outpost => Some(PadSize::Medium),
+            StationType::AsteroidBase
+            | StationType::Bernal
+            | StationType::Coriolis
+            | StationType::CraterPort
+            | StationType::Dodec
+            | StationType::FleetCarrier
+            | StationType::Ocellus
+            | StationType::Orbis => Some(PadSize::Large),
+            _ => None,
+        }
+    }
+}
+
+/// What one event told us about a station, fields it didn't mention are `None`
+#[derive(Clone, Debug)]
+pub struct StationRecord {
+    pub timestamp: DateTime<Utc>,
+    pub source: EDLogEventDiscriminants,
+    pub station_name: EDString,
+    pub station_type: Option<StationType>,
+    pub star_system: Option<EDString>,
+    pub system_address: Option<u64>,
+    pub faction: Option<EDString>,
+    pub allegiance: Option<Allegiance>,
+    pub services: Option<Vec<StationService>>,
+    pub economies: Option<Vec<StationEconomy>>,
+    pub landing_pads: Option<LandingPads>,
+    pub dist_from_star_ls: Option<f64>,
+}
+
+impl StationRecord {
+    fn new(
+        timestamp: DateTime<Utc>,
+        source: EDLogEventDiscriminants,
+        station_name: EDString,
+    ) -> StationRecord {
+        StationRecord {
+            timestamp,
+            source,
+            station_name,
+            station_type: None,
+            star_system: None,
+            system_address: None,
+            faction: None,
+            allegiance: None,
+            services: None,
+            economies: None,
+            landing_pads: None,
+            dist_from_star_ls: None,
+        }
+    }
+
+    fn from_information(
+        timestamp: DateTime<Utc>,
+        source: EDLogEventDiscriminants,
+        info: &StationInformation,
+    ) -> StationRecord {
+        StationRecord {
+            station_type: Some(info.station_type),
+            faction: Some(info.station_faction.name.clone()),
+            allegiance: info.station_allegiance,
+            services: Some(info.station_services.clone()),
+            economies: Some(info.station_economies.clone()),
+            ..StationRecord::new(timestamp, source, info.station_name.clone())
+        }
+    }
+
+    /// newer known values replace older ones
+    fn merge(&mut self, newer: &StationRecord) {
+        self.timestamp = newer.timestamp;
+        self.source = newer.source;
+        self.station_name = newer.station_name.clone();
+        self.station_type = newer.station_type.or(self.station_type);
+        self.star_system = newer
+            .star_system
+            .clone()
+            .or_else(|| self.star_system.take());
+        self.system_address = newer.system_address.or(self.system_address);
+        self.faction = newer.faction.clone().or_else(|| self.faction.take());
+        self.allegiance = newer.allegiance.or(self.allegiance);
+        if newer.services.is_some() {
+            self.services = newer.services.clone();
+        }
+        if newer.economies.is_some() {
+            self.economies = newer.economies.clone();
+        }
+        self.landing_pads = newer.landing_pads.or(self.landing_pads);
+        self.dist_from_star_ls = newer.dist_from_star_ls.or(self.dist_from_star_ls);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct KnownStation {
+    pub market_id: u64,
+    /// everything we know, merged from the history
+    pub latest: StationRecord,
+    /// oldest first
+    pub history: Vec<StationRecord>,
+}
+
+impl KnownStation {
+    pub fn name(&self) -> &str {
+        self.latest.station_name.as_str()
+    }
+
+    pub fn has_service(&self, service: StationService) -> bool {
+        self.latest
+            .services
+            .as_ref()
+            .is_some_and(|s| s.contains(&service))
+    }
+
+    /// largest landing pad, from docking or else from the station type
+    pub fn largest_pad(&self) -> Option<PadSize> {
+        match self.latest.landing_pads {
+            Some(pads) if pads.large > 0 => Some(PadSize::Large),
+            Some(pads) if pads.medium > 0 => Some(PadSize::Medium),
+            Some(pads) if pads.small > 0 => Some(PadSize::Small),
+            Some(_) => None,
+            None => self.latest.station_type.and_then(PadSize::largest_for),
+        }
+    }
+
+    pub fn has_pad(&self, size: PadSize) -> bool {
+        self.largest_pad().is_some_and(|p| p >= size)
+    }
+
+    pub fn last_seen(&self) -> DateTime<Utc> {
+        self.latest.timestamp
+    }
+}
+
+/// Stations we docked at, approached or saw in the system map, by market id
+#[derive(Clone, Debug, Default)]
+pub struct Stations {
+    stations: HashMap<u64, KnownStation>,
+    pub systems: KnownSystems,
+    // station signals in a system that we can't tie to a market id yet
+    signals: HashMap<(u64, EDString), DateTime<Utc>>,
+}
+
+impl Stations {
+    /// build the directory from log lines sorted by timestamp
+    pub fn new(lines: &[EDLogLine]) -> Stations {
+        let mut stations = Stations::default();
+        for line in lines {
+            stations.add_line(line);
+        }
+        stations
+    }
+
+    fn record(&mut self, market_id: u64, record: StationRecord) {
+        let station = self
+            .stations
+            .entry(market_id)
+            .or_insert_with(|| KnownStation {
+                market_id,
+                latest: record.clone(),
+                history: Vec::new(),
+            });
+        station.latest.merge(&record);
+        station.history.push(record);
+    }
+
+    fn find_in_system(&self, system_address: u64, name: EDString) -> Option<u64> {
+        self.stations
+            .values()
+            .find(|s| {
+                s.latest.system_address == Some(system_address) && s.latest.station_name == name
+            })
+            .map(|s| s.market_id)
+    }
+
+    pub fn add_line(&mut self, line: &EDLogLine) {
+        self.systems.add_line(line);
+        let timestamp = *line.timestamp();
+        let source = EDLogEventDiscriminants::from(line.event());
+        match line.event() {
+            EDLogEvent::Docked(docked) => {
+                let id = &docked.station_identification;
+                let Some(market_id) = id.market_id else {
+                    return;
+                };
+                let record = StationRecord {
+                    station_type: id.station_type,
+                    star_system: Some(docked.star_system.clone()),
+                    system_address: Some(docked.system_address),
+                    faction: Some(docked.station_faction.name.clone()),
+                    allegiance: docked.station_allegiance,
+                    services: Some(docked.station_services.clone()),
+                    economies: docked.station_economies.clone(),
+                    landing_pads: docked.landing_pads,
+                    dist_from_star_ls: Some(docked.dist_from_star_ls),
+                    ..StationRecord::new(timestamp, source, id.station_name.clone())
+                };
+                self.record(market_id, record);
+            }
+            EDLogEvent::Location(location) => {
+                let Some(info) = &location.station_information else {
+                    return;
+                };
+                let record = StationRecord {
+                    star_system: Some(location.star_system.clone()),
+                    system_address: location.system_address,
+                    dist_from_star_ls: location.dist_from_star_ls,
+                    ..StationRecord::from_information(timestamp, source, info)
+                };
+                self.record(info.market_id, record);
+            }
+            EDLogEvent::ApproachSettlement(settlement) => {
+                let Some(info) = &settlement.station_information else {
+                    return;
+                };
+                let record = StationRecord {
+                    system_address: settlement
+                        .body_information
+                        .as_ref()
+                        .map(|b| b.system_address),
+                    ..StationRecord::from_information(timestamp, source, info)
+                };
+                self.record(info.market_id, record);
+            }
+            EDLogEvent::FSSSignalDiscovered(signal) if signal.is_station == Some(true) => {
+                let name = &signal.signal_name;
+                match self.find_in_system(signal.system_address, name.clone()) {
+                    Some(market_id) => {
+                        let record = StationRecord {
+                            system_address: Some(signal.system_address),
+                            ..StationRecord::new(timestamp, source, name.clone())
+                        };
+                        self.record(market_id, record);
+                    }
+                    None => {
+                        self.signals
+                            .insert((signal.system_address, name.clone()), timestamp);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn get(&self, market_id: u64) -> Option<&KnownStation> {
+        self.stations.get(&market_id)
+    }
+
+    pub fn find_by_name(&self, name: &str) -> Option<&KnownStation> {
+        self.stations
+            .values()
+            .find(|s| s.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &KnownStation> {
+        self.stations.values()
+    }
+
+    pub fn in_system(&self, system_address: u64) -> impl Iterator<Item = &KnownStation> {
+        self.stations
+            .values()
+            .filter(move |s| s.latest.system_address == Some(system_address))
+    }
+
+    /// station signals seen in the system map of stations we never visited
+    pub fn unvisited(&self) -> impl Iterator<Item = (u64, EDString, DateTime<Utc>)> {
+        self.signals
+            .iter()
+            .filter(|((address, name), _)| self.find_in_system(*address, name.clone()).is_none())
+            .map(|((address, name), timestamp)| (*address, name.clone(), *timestamp))
+    }
+
+    /// position of the station's system, if we recorded its coordinates
+    pub fn position(&self, station: &KnownStation) -> Option<[f64; 3]> {
+        self.systems.position(station.latest.system_address?)
+    }
+
+    /// stations within `radius` light years of `from` that match `filter`, nearest first
+    pub fn search(
+        &self,
+        from: &[f64; 3],
+        radius: f64,
+        filter: impl Fn(&KnownStation) -> bool,
+    ) -> Vec<(&KnownStation, f64)> {
+        let mut result: Vec<_> = self
+            .stations
+            .values()
+            .filter(|s| filter(s))
+            .filter_map(|s| Some((s, distance(from, &self.position(s)?))))
+            .filter(|(_, d)| *d <= radius)
+            .collect();
+        result.sort_by(|a, b| {
+            a.1.total_cmp(&b.1).then(
+                a.0.latest
+                    .dist_from_star_ls
+                    .unwrap_or(f64::MAX)
+                    .total_cmp(&b.0.latest.dist_from_star_ls.unwrap_or(f64::MAX)),
+            )
+        });
+        result
+    }
+
+    /// nearest station that matches `filter`, and its distance in light years
+    pub fn nearest(
+        &self,
+        from: &[f64; 3],
+        filter: impl Fn(&KnownStation) -> bool,
+    ) -> Option<(&KnownStation, f64)> {
+        self.search(from, f64::INFINITY, filter).into_iter().next()
+    }
+}
+
+#[test]
+fn test_stations() {
+    let json = [
+        r#"{ "timestamp":"2025-04-01T18:00:00Z", "event":"Location", "DistFromStarLS":12.5, "Docked":true, "StationName":"Exogene Sciences", "StationType":"AsteroidBase", "MarketID":129038712, "StationFaction":{ "Name":"October Consortium" }, "StationGovernment":"$government_Corporate;", "StationGovernment_Localised":"Corporate", "StationServices":[ "dock", "autodock", "commodities", "refuel" ], "StationEconomy":"$economy_Service;", "StationEconomy_Localised":"Service", "StationEconomies":[ { "Name":"$economy_Service;", "Name_Localised":"Service", "Proportion":1.000000 } ], "Taxi":false, "Multicrew":false, "StarSystem":"Yukoukha", "SystemAddress":22945325796176, "StarPos":[-134.56250,-34.03125,-4.75000], "SystemAllegiance":"", "SystemEconomy":"$economy_None;", "SystemEconomy_Localised":"None", "SystemGovernment":"$government_None;", "SystemGovernment_Localised":"None", "SystemSecurity":"$GAlAXY_MAP_INFO_state_anarchy;", "SystemSecurity_Localised":"Anarchy", "Population":0, "Body":"Yukoukha A 1", "BodyID":3, "BodyType":"Planet" }"#,
+        r#"{ "timestamp":"2025-04-01T18:10:00Z", "event":"FSSSignalDiscovered", "SystemAddress":22945325796176, "SignalName":"Wnuk-Lipinski Installation", "IsStation":true }"#,
+        r#"{ "timestamp":"2025-04-02T18:00:00Z", "event":"FSDJump", "StarSystem":"Kholul", "SystemAddress":2415659059547, "StarPos":[-46.93750,-7.84375,-152.18750], "SystemAllegiance":"", "SystemEconomy":"$economy_None;", "SystemEconomy_Localised":"None", "SystemGovernment":"$government_None;", "SystemGovernment_Localised":"None", "SystemSecurity":"$GAlAXY_MAP_INFO_state_anarchy;", "SystemSecurity_Localised":"Anarchy", "Population":0, "JumpDist":173.499, "FuelUsed":2.0, "FuelLevel":20.0 }"#,
+        r#"{ "timestamp":"2025-04-02T18:20:00Z", "event":"Docked", "StationName":"McDevitt Laboratory", "StationType":"Outpost", "Taxi":false, "Multicrew":false, "StarSystem":"Kholul", "SystemAddress":2415659059547, "MarketID":3228342528, "StationFaction":{ "Name":"SI Terraforming" }, "StationGovernment":"$government_Corporate;", "StationGovernment_Localised":"Corporate", "StationAllegiance":"Independent", "StationServices":[ "dock", "autodock", "commodities", "facilitator", "materialtrader" ], "StationEconomy":"$economy_Industrial;", "StationEconomy_Localised":"Industrial", "StationEconomies":[ { "Name":"$economy_Industrial;", "Name_Localised":"Industrial", "Proportion":1.0 } ], "DistFromStarLS":320.5, "LandingPads":{ "Small":2, "Medium":4, "Large":0 } }"#,
+        r#"{ "timestamp":"2025-04-02T18:30:00Z", "event":"FSSSignalDiscovered", "SystemAddress":2415659059547, "SignalName":"McDevitt Laboratory", "IsStation":true }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let stations = Stations::new(&lines);
+    let exogene = stations.get(129038712).unwrap();
+    assert_eq!("Exogene Sciences", exogene.name());
+    assert_eq!(Some(PadSize::Large), exogene.largest_pad());
+    assert_eq!(Some(12.5), exogene.latest.dist_from_star_ls);
+    assert!(exogene.has_service(StationService::Refuel));
+
+    let mcdevitt = stations.find_by_name("mcdevitt laboratory").unwrap();
+    assert_eq!(2, mcdevitt.history.len());
+    assert_eq!(
+        Some(LandingPads {
+            small: 2,
+            medium: 4,
+            large: 0
+        }),
+        mcdevitt.latest.landing_pads
+    );
+    assert!(!mcdevitt.has_pad(PadSize::Large));
+    assert_eq!(1, stations.in_system(2415659059547).count());
+    assert_eq!(1, stations.unvisited().count());
+
+    let from = [-134.5625, -34.03125, -4.75];
+    let (nearest, distance) = stations
+        .nearest(&from, |s| s.has_service(StationService::MaterialTrader))
+        .unwrap();
+    assert_eq!(3228342528, nearest.market_id);
+    assert!((distance - 173.4985).abs() < 0.001);
+    assert!(
+        stations
+            .nearest(&from, |s| s.has_service(StationService::MaterialTrader)
+                && s.has_pad(PadSize::Large))
+            .is_none()
+    );
+    // Interstellar Factors within 30 ly, from 25 ly out of Kholul and from Yukoukha
+    let near_kholul = [-46.9375, -7.84375, -127.1875];
+    let factors = stations.search(&near_kholul, 30.0, |s| {
+        s.has_service(StationService::Facilitator)
+    });
+    assert_eq!(1, factors.len());
+    assert_eq!(25.0, factors[0].1);
+    assert!(
+        stations
+            .search(&from, 30.0, |s| s.has_service(StationService::Facilitator))
+            .is_empty()
+    );
+    assert_eq!(2, stations.search(&from, 200.0, |_| true).len());
+    assert_eq!(
+        1,
+        stations
+            .search(&from, 10.0, |s| s.has_pad(PadSize::Medium))
+            .len()
+    );
+}
+
+#[test]
+fn test_stations_empty() {
+    let stations = Stations::new(&[]);
+    assert!(stations.get(3228342528).is_none());
+    assert!(stations.find_by_name("McDevitt Laboratory").is_none());
+    assert_eq!(0, stations.unvisited().count());
+    assert!(stations.nearest(&crate::galaxy::SOL, |_| true).is_none());
+}
+
+#[test]
+fn test_stations_signal_before_docking() {
+    let json = [
+        r#"{ "timestamp":"2025-04-02T18:10:00Z", "event":"FSSSignalDiscovered", "SystemAddress":2415659059547, "SignalName":"McDevitt Laboratory", "IsStation":true }"#,
+        // no FSDJump, the position of Kholul is unknown
+        r#"{ "timestamp":"2025-04-02T18:20:00Z", "event":"Docked", "StationName":"McDevitt Laboratory", "StationType":"Outpost", "Taxi":false, "Multicrew":false, "StarSystem":"Kholul", "SystemAddress":2415659059547, "MarketID":3228342528, "StationFaction":{ "Name":"SI Terraforming" }, "StationGovernment":"$government_Corporate;", "StationGovernment_Localised":"Corporate", "StationServices":[ "dock", "autodock", "commodities" ], "StationEconomy":"$economy_Industrial;", "StationEconomy_Localised":"Industrial", "DistFromStarLS":320.5 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let stations = Stations::new(&lines);
+    // the signal is tied to the station once we docked
+    assert_eq!(0, stations.unvisited().count());
+
+    let mcdevitt = stations.get(3228342528).unwrap();
+    assert_eq!(1, mcdevitt.history.len());
+    assert!(mcdevitt.latest.economies.is_none());
+    // no pads in the Docked event, an outpost has medium pads at most
+    assert_eq!(Some(PadSize::Medium), mcdevitt.largest_pad());
+    assert!(!mcdevitt.has_service(StationService::MaterialTrader));
+    assert!(stations.position(mcdevitt).is_none());
+    assert!(
+        stations
+            .search(&crate::galaxy::SOL, f64::INFINITY, |_| true)
+            .is_empty()
+    );
+}
@@ -1,7 +1,9 @@
 use crate::{
     EDString,
     common_types::{Credits, MercCoins},
+    log_line::{EDLogEvent, EDLogLine},
 };
+use chrono::{DateTime, Duration, Utc};
 use ed_parse_log_files_macros::{Extractable, testcase_struct};
 use serde::{Deserialize, Serialize};
 
@@ -750,3 +752,522 @@ pub struct EDLogStatistics {
     pub fleetcarrier: Option<StatisticsFleetcarrier>,
     pub exobiology: Option<StatisticsExobiology>,
 }
+
+/// Field by field difference with an earlier snapshot of the same statistics.
+/// Sections missing in the earlier snapshot count from zero, counters that went down give 0.
+pub trait StatisticsDelta {
+    fn delta(&self, earlier: &Self) -> Self;
+}
+
+impl StatisticsDelta for u64 {
+    fn delta(&self, earlier: &Self) -> Self {
+        self.saturating_sub(*earlier)
+    }
+}
+
+impl StatisticsDelta for f64 {
+    fn delta(&self, earlier: &Self) -> Self {
+        self - earlier
+    }
+}
+
+impl StatisticsDelta for Credits {
+    fn delta(&self, earlier: &Self) -> Self {
+        *self - *earlier
+    }
+}
+
+impl StatisticsDelta for MercCoins {
+    fn delta(&self, earlier: &Self) -> Self {
+        MercCoins(self.0 - earlier.0)
+    }
+}
+
+// text values like the last system of a thargoid encounter keep the latest value
+impl StatisticsDelta for EDString {
+    fn delta(&self, _earlier: &Self) -> Self {
+        self.clone()
+    }
+}
+
+impl<T: StatisticsDelta + Clone> StatisticsDelta for Option<T> {
+    fn delta(&self, earlier: &Self) -> Self {
+        match (self, earlier) {
+            (Some(later), Some(earlier)) => Some(later.delta(earlier)),
+            (later, _) => later.clone(),
+        }
+    }
+}
+
+macro_rules! statistics_delta {
+    ($name:ident { $($field:ident),* $(,)? }) => {
+        impl StatisticsDelta for $name {
+            fn delta(&self, earlier: &Self) -> Self {
+                $name {
+                    $($field: self.$field.delta(&earlier.$field),)*
+                }
+            }
+        }
+    };
+}
+
+statistics_delta!(StatisticsMercCoins {
+    current,
+    total_earned,
+    total_spent,
+    spent_on_mercgear,
+    spent_on_engineering
+});
+statistics_delta!(StatisticsBankAccountOnFoot {
+    spent_on_suits,
+    spent_on_weapons,
+    spent_on_suit_consumables,
+    suits_owned,
+    weapons_owned,
+    spent_on_premium_stock,
+    premium_stock_bought
+});
+statistics_delta!(StatisticsBankAccount {
+    current_wealth,
+    spent_on_ships,
+    spent_on_outfitting,
+    spent_on_repairs,
+    spent_on_fuel,
+    spent_on_ammo_consumables,
+    insurance_claims,
+    spent_on_insurance,
+    owned_ship_count,
+    onfoot_statistics,
+    merc_coins
+});
+statistics_delta!(StatisticsCombatOnFoot {
+    onfoot_combat_bonds,
+    onfoot_combat_bonds_profits,
+    onfoot_vehicles_destroyed,
+    onfoot_ships_destroyed,
+    dropships_taken,
+    dropships_booked,
+    dropships_cancelled,
+    conflictzone_high,
+    conflictzone_medium,
+    conflictzone_low,
+    conflictzone_total,
+    conflictzone_high_wins,
+    conflictzone_medium_wins,
+    conflictzone_low_wins,
+    conflictzone_total_wins,
+    settlement_defended,
+    settlement_conquered,
+    onfoot_skimmers_killed,
+    onfoot_scavs_killed
+});
+statistics_delta!(StatisticsCombat {
+    bounties_claimed,
+    bounty_hunting_profit,
+    combat_bonds,
+    combat_bond_profits,
+    assassinations,
+    assassination_profits,
+    highest_single_reward,
+    skimmers_killed,
+    onfoot_combat_statistics
+});
+statistics_delta!(StatisticsCrimeOnFoot {
+    malware_uploaded,
+    settlements_state_shutdown,
+    production_sabotage,
+    production_theft,
+    total_murders,
+    citizens_murdered,
+    omnipol_murdered,
+    guards_murdered,
+    data_stolen,
+    goods_stolen,
+    sample_stolen,
+    total_stolen,
+    turrets_destroyed,
+    turrets_overloaded,
+    turrets_total,
+    value_stolen_state_change,
+    profiles_cloned
+});
+statistics_delta!(StatisticsCrime {
+    notoriety,
+    fines,
+    total_fines,
+    bounties_received,
+    total_bounties,
+    highest_bounty,
+    statistics_on_foot
+});
+statistics_delta!(StatisticsSmuggling {
+    black_markets_traded_with,
+    black_markets_profits,
+    resources_smuggled,
+    average_profit,
+    highest_single_transaction
+});
+statistics_delta!(StatisticsTradingOnFoot {
+    data_sold,
+    goods_sold,
+    assets_sold
+});
+statistics_delta!(StatisticsTrading {
+    markets_traded_with,
+    market_profits,
+    resources_traded,
+    average_profit,
+    highest_single_transaction,
+    trading_onfoot_statistics
+});
+statistics_delta!(StatisticsMining {
+    mining_profits,
+    quantity_mined,
+    materials_collected
+});
+statistics_delta!(StatisticsExplorationOnFoot {
+    on_foot_distance_travelled,
+    shuttle_journeys,
+    shuttle_distance_travelled,
+    spent_on_shuttles,
+    first_footfalls,
+    planet_footfalls,
+    settlements_visited
+});
+statistics_delta!(StatisticsExploration {
+    systems_visited,
+    exploration_profits,
+    planets_scanned_to_level_2,
+    planets_scanned_to_level_3,
+    efficient_scans,
+    highest_payout,
+    total_hyperspace_distance,
+    total_hyperspace_jumps,
+    greatest_distance_from_start,
+    time_played,
+    statistics_exploration_onfoot
+});
+statistics_delta!(PassengerMissionsDetails {
+    refugees_delivered,
+    tourists_vip_delivered,
+    tourists_bulk_delivered,
+    criminals_vip_delivered,
+    businessmen_vip_delivered,
+    freedomfighters_vip_delivered,
+    scienceteams_vip_delivered,
+    soldiers_vip_delivered,
+    medical_vip_delivered,
+    explorers_vip_delivered,
+    celebrities_vip_delivered,
+    politicians_bulk_delivered
+});
+statistics_delta!(StatisticsPassengers {
+    accepted,
+    passenger_missions_details,
+    bulk,
+    vip,
+    delivered,
+    ejected,
+    disgruntled
+});
+statistics_delta!(StatisticsSeachAndRescueOnFoot {
+    salvage_legal_poi,
+    salvage_legal_settlements,
+    salvage_illegal_poi,
+    salvage_illegal_settlements,
+    maglocks_opened,
+    panels_opened,
+    settlements_state_fire_out,
+    settlements_state_reboot
+});
+statistics_delta!(StatisticSearchAndRescue {
+    search_rescue_traded,
+    search_rescue_profit,
+    search_rescue_count,
+    statistics_search_and_rescue_onfoot
+});
+statistics_delta!(StatisticsSquadron {
+    bank_credits_deposited,
+    bank_credits_withdrawn,
+    bank_commodities_deposited_num,
+    bank_commodities_deposited_value,
+    bank_commodities_withdrawn_num,
+    bank_commodities_withdrawn_value,
+    bank_personal_assets_deposited_num,
+    bank_personal_assets_deposited_value,
+    bank_personal_assets_withdrawn_num,
+    bank_personal_assets_withdrawn_value,
+    bank_ships_deposited_num,
+    bank_ships_deposited_value,
+    leaderboard_aegis_highestcontribution,
+    leaderboard_bgs_highestcontribution,
+    leaderboard_bounty_highestcontribution,
+    leaderboard_colonisation_contribution_highestcontribution,
+    leaderboard_combat_highestcontribution,
+    leaderboard_cqc_highestcontribution,
+    leaderboard_exploration_highestcontribution,
+    leaderboard_mining_highestcontribution,
+    leaderboard_powerplay_highestcontribution,
+    leaderboard_trade_highestcontribution,
+    leaderboard_trade_illicit_highestcontribution,
+    leaderboard_operationscore_highestcontribution,
+    leaderboard_podiums
+});
+statistics_delta!(StatisticsTgEncounters {
+    tg_encounter_imprint,
+    tg_encounter_killed,
+    tg_encounter_total,
+    tg_encounter_total_last_system,
+    tg_encounter_total_last_timestamp,
+    tg_encounter_total_last_ship,
+    tg_encounter_wakes,
+    tg_scout_count
+});
+statistics_delta!(StatisticsCraftingOnFoot {
+    suit_mods_applied,
+    weapon_mods_applied,
+    suits_upgraded,
+    weapons_upgraded,
+    suits_upgraded_full,
+    weapons_upgraded_full,
+    suit_mods_applied_full,
+    weapon_mods_applied_full
+});
+statistics_delta!(StatisticsCrafting {
+    count_of_used_engineers,
+    recipes_generated,
+    recipes_generated_rank_1,
+    recipes_generated_rank_2,
+    recipes_generated_rank_3,
+    recipes_generated_rank_4,
+    recipes_generated_rank_5,
+    statistics_crafting_onfoot
+});
+statistics_delta!(StatisticsCrew {
+    npc_crew_total_wages,
+    npc_crew_hired,
+    npc_crew_fired,
+    npc_crew_died
+});
+statistics_delta!(StatisticsMulticrew {
+    multicrew_time_total,
+    multicrew_gunner_time_total,
+    multicrew_fighter_time_total,
+    multicrew_credits_total,
+    multicrew_fines_total
+});
+statistics_delta!(StatisticsMaterialTraderStatsDetail {
+    encoded_materials_traded,
+    raw_materials_traded,
+    grade_1_materials_traded,
+    grade_2_materials_traded,
+    grade_3_materials_traded,
+    grade_4_materials_traded,
+    grade_5_materials_traded,
+    assets_traded_in,
+    assets_traded_out
+});
+statistics_delta!(StatisticsMaterialTraderStats {
+    trades_completed,
+    materials_traded,
+    detailed_traded_statistics
+});
+statistics_delta!(StatisticsFleetcarrier {
+    fleetcarrier_export_total,
+    fleetcarrier_import_total,
+    fleetcarrier_tradeprofit_total,
+    fleetcarrier_tradespend_total,
+    fleetcarrier_stolenprofit_total,
+    fleetcarrier_stolenspend_total,
+    fleetcarrier_distance_travelled,
+    fleetcarrier_total_jumps,
+    fleetcarrier_shipyard_sold,
+    fleetcarrier_shipyard_profit,
+    fleetcarrier_outfitting_sold,
+    fleetcarrier_outfitting_profit,
+    fleetcarrier_rearm_total,
+    fleetcarrier_refuel_total,
+    fleetcarrier_refuel_profit,
+    fleetcarrier_repairs_total,
+    fleetcarrier_vouchers_redeemed,
+    fleetcarrier_vouchers_profit
+});
+statistics_delta!(StatisticsCQCStats {
+    cqc_credits_earned,
+    cqc_time_played,
+    cqc_kd,
+    cqc_wl,
+    cqc_kills
+});
+statistics_delta!(StatisticsExobiology {
+    organic_genus_encountered,
+    organic_species_encountered,
+    organic_variant_encountered,
+    organic_data_profits,
+    organic_data,
+    first_logged_profits,
+    first_logged,
+    organic_systems,
+    organic_planets,
+    organic_genus,
+    organic_species
+});
+statistics_delta!(EDLogStatistics {
+    bank_account,
+    combat,
+    crime,
+    smuggling,
+    trading,
+    mining,
+    exploration,
+    passengers,
+    search_and_rescue,
+    squadron,
+    tg_encounters,
+    crafting,
+    crew,
+    multicrew,
+    material_trader_stats,
+    cqc_stats,
+    fleetcarrier,
+    exobiology
+});
+
+pub type StatisticsSnapshot = (DateTime<Utc>, EDLogStatistics);
+
+/// Statistics snapshots in the order they were written, one per login
+#[derive(Clone, Debug, Default)]
+pub struct StatisticsHistory {
+    snapshots: Vec<StatisticsSnapshot>,
+}
+
+impl StatisticsHistory {
+    pub fn new(lines: &[EDLogLine]) -> StatisticsHistory {
+        let mut history = StatisticsHistory::default();
+        for line in lines {
+            history.add_line(line);
+        }
+        history
+    }
+
+    pub fn add_line(&mut self, line: &EDLogLine) {
+        if let EDLogEvent::Statistics(statistics) = line.event() {
+            self.snapshots
+                .push((*line.timestamp(), (**statistics).clone()));
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &StatisticsSnapshot> {
+        self.snapshots.iter()
+    }
+
+    pub fn latest(&self) -> Option<&StatisticsSnapshot> {
+        self.snapshots.last()
+    }
+
+    /// the snapshot closest in time to `time`, before or after
+    pub fn nearest(&self, time: DateTime<Utc>) -> Option<&StatisticsSnapshot> {
+        self.snapshots.iter().min_by_key(|(t, _)| (*t - time).abs())
+    }
+
+    /// the snapshots nearest to the start and the end of a period
+    pub fn range(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Option<(&StatisticsSnapshot, &StatisticsSnapshot)> {
+        Some((self.nearest(from)?, self.nearest(to)?))
+    }
+
+    /// change of every statistic between the snapshots nearest to `from` and `to`
+    pub fn delta(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Option<EDLogStatistics> {
+        let ((_, earlier), (_, later)) = self.range(from, to)?;
+        Some(later.delta(earlier))
+    }
+
+    /// change over the `period` up to the latest snapshot, like week over week with `Duration::weeks(1)`
+    pub fn last_period(&self, period: Duration) -> Option<EDLogStatistics> {
+        let (end, _) = self.latest()?;
+        self.delta(*end - period, *end)
+    }
+}
+
+#[test]
+fn test_statistics_delta() {
+    let first: serde_json::Value =
+        serde_json::from_str(include_str!("../testdata/Statistics - minimal.json")).unwrap();
+    let mut second = first.clone();
+    second["timestamp"] = "2025-01-08T10:00:00Z".into();
+    second["Bank_Account"]["Current_Wealth"] = 1000001.into();
+    second["Bank_Account"]["Owned_Ship_Count"] = 0.into();
+    second["Exploration"]["Systems_Visited"] = 43.into();
+    second["Exobiology"] = serde_json::json!({ "Organic_Genus_Encountered":1, "Organic_Species_Encountered":1,
+        "Organic_Variant_Encountered":1, "Organic_Data_Profits":500000, "Organic_Data":2, "First_Logged_Profits":0,
+        "First_Logged":0, "Organic_Systems":1, "Organic_Planets":1, "Organic_Genus":1, "Organic_Species":1 });
+    let lines: Vec<EDLogLine> = [first, second]
+        .into_iter()
+        .map(|j| serde_json::from_value(j).expect("Should parse"))
+        .collect();
+
+    let history = StatisticsHistory::new(&lines);
+    let week = history.last_period(Duration::weeks(1)).unwrap();
+    assert_eq!(Credits(1000000), week.bank_account.current_wealth);
+    assert_eq!(0, week.bank_account.owned_ship_count);
+    assert_eq!(42, week.exploration.systems_visited);
+    assert_eq!(0, week.combat.bounties_claimed);
+    // the exobiology section appeared during the week, so it counts from zero
+    assert_eq!(
+        Credits(500000),
+        week.exobiology.unwrap().organic_data_profits
+    );
+
+    let start: DateTime<Utc> = "2025-01-02T00:00:00Z".parse().unwrap();
+    let (from, to) = history.range(start, start + Duration::days(30)).unwrap();
+    assert_eq!(
+        "2025-01-01T10:00:00Z".parse::<DateTime<Utc>>().unwrap(),
+        from.0
+    );
+    assert_eq!(
+        "2025-01-08T10:00:00Z".parse::<DateTime<Utc>>().unwrap(),
+        to.0
+    );
+}
+
+#[test]
+fn test_statistics_delta_values() {
+    // counters that went down, like ships sold, don't go below zero
+    assert_eq!(0, 2u64.delta(&5));
+    // credits can be spent
+    assert_eq!(Credits(-500), Credits(1000).delta(&Credits(1500)));
+    assert_eq!(Some(3), Some(3u64).delta(&None));
+    assert_eq!(None, None::<u64>.delta(&Some(3)));
+    assert_eq!(
+        "Kholul",
+        EDString::from("Kholul")
+            .delta(&EDString::from("Sol"))
+            .as_str()
+    );
+}
+
+#[test]
+fn test_statistics_history_edges() {
+    let history = StatisticsHistory::new(&[]);
+    assert!(history.latest().is_none());
+    assert!(history.last_period(Duration::weeks(1)).is_none());
+
+    let first: serde_json::Value =
+        serde_json::from_str(include_str!("../testdata/Statistics - minimal.json")).unwrap();
+    let lines: Vec<EDLogLine> = vec![serde_json::from_value(first).expect("Should parse")];
+    let history = StatisticsHistory::new(&lines);
+    // a single snapshot is compared with itself
+    let week = history.last_period(Duration::weeks(1)).unwrap();
+    assert_eq!(Credits(0), week.bank_account.current_wealth);
+    assert_eq!(0, week.exploration.systems_visited);
+    let (from, to) = history
+        .range(
+            "2020-01-01T00:00:00Z".parse().unwrap(),
+            "2030-01-01T00:00:00Z".parse().unwrap(),
+        )
+        .unwrap();
+    assert_eq!(from.0, to.0);
+}
@@ -0,0 +1,684 @@
+use crate::{
+    EDString,
+    common_types::{Credits, OnFootItem},
+    log_line::{EDLogEvent, EDLogLine},
+    on_foot_inventory::OnFootInventory,
+    suits::{SuitMod, SuitModule, UpgradeResource, WeaponMod},
+};
+use std::collections::{BTreeMap, HashMap};
+use strum::Display;
+
+/// highest grade a suit or weapon can be upgraded to
+pub const MAX_CLASS: u8 = 5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Display)]
+pub enum SuitKind {
+    Flight,
+    Maverick,
+    Dominator,
+    Artemis,
+}
+
+impl SuitKind {
+    /// kind and class from a journal name like `utilitysuit_class3`
+    pub fn from_journal_name(name: &str) -> Option<(SuitKind, u8)> {
+        let name = name.to_ascii_lowercase();
+        if name == "flightsuit" {
+            return Some((SuitKind::Flight, 1));
+        }
+        let (kind, class) = name.split_once("_class")?;
+        let kind = match kind {
+            "utilitysuit" => SuitKind::Maverick,
+            "tacticalsuit" => SuitKind::Dominator,
+            "explorationsuit" => SuitKind::Artemis,
+            _ => return None,
+        };
+        Some((kind, class.parse().ok()?))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Display)]
+pub enum WeaponKind {
+    Kinetic,
+    Laser,
+    Plasma,
+}
+
+impl WeaponKind {
+    /// damage type from a journal name like `wpn_m_assaultrifle_kinetic_fauto`
+    pub fn from_journal_name(name: &str) -> Option<WeaponKind> {
+        let name = name.to_ascii_lowercase();
+        if name.contains("_kinetic_") {
+            Some(WeaponKind::Kinetic)
+        } else if name.contains("_laser_") {
+            Some(WeaponKind::Laser)
+        } else if name.contains("_plasma_") {
+            Some(WeaponKind::Plasma)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Upgradable {
+    Suit(SuitKind),
+    Weapon(WeaponKind),
+}
+
+#[derive(Clone, Debug)]
+pub struct OwnedSuit {
+    pub suit_id: u64,
+    pub name: EDString,
+    pub kind: Option<SuitKind>,
+    pub class: u8,
+    pub mods: Vec<SuitMod>,
+}
+
+#[derive(Clone, Debug)]
+pub struct OwnedWeapon {
+    pub suit_module_id: u64,
+    pub name: EDString,
+    pub name_localised: Option<EDString>,
+    pub kind: Option<WeaponKind>,
+    pub class: u8,
+    pub mods: Vec<WeaponMod>,
+}
+
+/// Suits and weapons we own, by SuitID and SuitModuleID
+#[derive(Clone, Debug, Default)]
+pub struct Armoury {
+    pub suits: BTreeMap<u64, OwnedSuit>,
+    pub weapons: BTreeMap<u64, OwnedWeapon>,
+}
+
+impl Armoury {
+    /// build the armoury from log lines sorted by timestamp
+    pub fn new(lines: &[EDLogLine]) -> Armoury {
+        let mut armoury = Armoury::default();
+        for line in lines {
+            armoury.add_line(line);
+        }
+        armoury
+    }
+
+    fn suit(&mut self, suit_id: u64, name: EDString, mods: &[SuitMod]) {
+        let kind = SuitKind::from_journal_name(name.as_str());
+        self.suits.insert(
+            suit_id,
+            OwnedSuit {
+                suit_id,
+                name,
+                kind: kind.map(|(kind, _)| kind),
+                class: kind.map_or(1, |(_, class)| class),
+                mods: mods.to_vec(),
+            },
+        );
+    }
+
+    fn weapon(
+        &mut self,
+        suit_module_id: u64,
+        name: EDString,
+        name_localised: Option<EDString>,
+        class: u64,
+        mods: &[WeaponMod],
+    ) {
+        self.weapons.insert(
+            suit_module_id,
+            OwnedWeapon {
+                suit_module_id,
+                name: name.clone(),
+                name_localised,
+                kind: WeaponKind::from_journal_name(name.as_str()),
+                class: class as u8,
+                mods: mods.to_vec(),
+            },
+        );
+    }
+
+    fn modules(&mut self, modules: &[SuitModule]) {
+        for module in modules {
+            self.weapon(
+                module.suit_module_id,
+                module.module_name.clone(),
+                Some(module.module_name_localised.clone()),
+                module.class,
+                &module.weapon_mods,
+            );
+        }
+    }
+
+    pub fn add_line(&mut self, line: &EDLogLine) {
+        match line.event() {
+            EDLogEvent::BuySuit(buy) => self.suit(buy.suit_id, buy.name.clone(), &buy.suit_mods),
+            EDLogEvent::SellSuit(sell) => {
+                self.suits.remove(&sell.suit_id);
+            }
+            EDLogEvent::UpgradeSuit(upgrade) => {
+                if !self.suits.contains_key(&upgrade.suit_id) {
+                    self.suit(upgrade.suit_id, upgrade.suit_name.clone(), &[]);
+                }
+                if let Some(suit) = self.suits.get_mut(&upgrade.suit_id) {
+                    suit.class = upgrade.class;
+                }
+            }
+            EDLogEvent::SuitLoadout(loadout) | EDLogEvent::SwitchSuitLoadout(loadout) => {
+                self.suit(
+                    loadout.suit_id,
+                    loadout.suit_name.clone(),
+                    &loadout.suit_mods,
+                );
+                self.modules(&loadout.modules);
+            }
+            EDLogEvent::CreateSuitLoadout(loadout) => {
+                self.suit(
+                    loadout.suit_id,
+                    loadout.suit_name.clone(),
+                    &loadout.suit_mods,
+                );
+                self.modules(&loadout.modules);
+            }
+            EDLogEvent::LoadoutEquipModule(equip) => self.weapon(
+                equip.suit_module_id,
+                equip.module_name.clone(),
+                Some(equip.module_name_localised.clone()),
+                equip.class,
+                &equip.weapon_mods,
+            ),
+            EDLogEvent::BuyWeapon(buy) => self.weapon(
+                buy.suit_module_id,
+                buy.name.clone(),
+                Some(buy.name_localised.clone()),
+                buy.class,
+                &buy.weapon_mods,
+            ),
+            EDLogEvent::SellWeapon(sell) => {
+                self.weapons.remove(&sell.suit_module_id);
+            }
+            EDLogEvent::UpgradeWeapon(upgrade) => {
+                if let Some(weapon) = self.weapons.get_mut(&upgrade.suit_module_id) {
+                    weapon.class = upgrade.class as u8;
+                } else {
+                    self.weapon(
+                        upgrade.suit_module_id,
+                        upgrade.name.clone(),
+                        upgrade.name_localised.clone(),
+                        upgrade.class,
+                        &[],
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn suits_of_kind(&self, kind: SuitKind) -> impl Iterator<Item = &OwnedSuit> {
+        self.suits.values().filter(move |s| s.kind == Some(kind))
+    }
+
+    /// what is needed to take a suit from its current class to `class`
+    pub fn plan_suit(
+        &self,
+        suit_id: u64,
+        class: u8,
+        catalog: &UpgradeCatalog,
+        inventory: &OnFootInventory,
+    ) -> Option<UpgradePlan> {
+        let suit = self.suits.get(&suit_id)?;
+        catalog.plan(Upgradable::Suit(suit.kind?), suit.class, class, inventory)
+    }
+
+    /// what is needed to take a weapon from its current class to `class`
+    pub fn plan_weapon(
+        &self,
+        suit_module_id: u64,
+        class: u8,
+        catalog: &UpgradeCatalog,
+        inventory: &OnFootInventory,
+    ) -> Option<UpgradePlan> {
+        let weapon = self.weapons.get(&suit_module_id)?;
+        catalog.plan(
+            Upgradable::Weapon(weapon.kind?),
+            weapon.class,
+            class,
+            inventory,
+        )
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct UpgradeCost {
+    pub credits: Credits,
+    pub resources: BTreeMap<OnFootItem, u64>,
+}
+
+impl UpgradeCost {
+    pub fn new(credits: i64, resources: &[(OnFootItem, u64)]) -> UpgradeCost {
+        UpgradeCost {
+            credits: Credits(credits),
+            resources: resources.iter().cloned().collect(),
+        }
+    }
+
+    fn from_journal(cost: Credits, resources: &[UpgradeResource]) -> UpgradeCost {
+        UpgradeCost {
+            credits: cost,
+            resources: resources
+                .iter()
+                .map(|r| (r.name.clone(), r.count))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct UpgradePlan {
+    pub cost: UpgradeCost,
+    /// resources still to collect, with what we have counted
+    pub missing: BTreeMap<OnFootItem, u64>,
+}
+
+impl UpgradePlan {
+    pub fn is_ready(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+/// Credits and micro-resources needed to upgrade to each class.
+/// The built-in costs are replaced by the ones seen in UpgradeSuit and UpgradeWeapon events.
+#[derive(Clone, Debug)]
+pub struct UpgradeCatalog {
+    costs: HashMap<(Upgradable, u8), UpgradeCost>,
+}
+
+impl Default for UpgradeCatalog {
+    fn default() -> UpgradeCatalog {
+        let mut costs = HashMap::new();
+        for kind in [SuitKind::Maverick, SuitKind::Dominator, SuitKind::Artemis] {
+            for class in 2..=MAX_CLASS {
+                if let Some(cost) = suit_cost(kind, class) {
+                    costs.insert((Upgradable::Suit(kind), class), cost);
+                }
+            }
+        }
+        for kind in [WeaponKind::Kinetic, WeaponKind::Laser, WeaponKind::Plasma] {
+            for class in 2..=MAX_CLASS {
+                if let Some(cost) = weapon_cost(kind, class) {
+                    costs.insert((Upgradable::Weapon(kind), class), cost);
+                }
+            }
+        }
+        UpgradeCatalog { costs }
+    }
+}
+
+/// Pioneer Supplies cost of upgrading a suit to `class`
+fn suit_cost(kind: SuitKind, class: u8) -> Option<UpgradeCost> {
+    use OnFootItem::*;
+
+    let cost = match (kind, class) {
+        (SuitKind::Maverick, 2) => UpgradeCost::new(150_000, &[(SuitSchematic, 1), (Graphene, 5)]),
+        (SuitKind::Maverick, 3) => UpgradeCost::new(
+            500_000,
+            &[
+                (SuitSchematic, 5),
+                (Graphene, 10),
+                (MicroSupercapacitor, 5),
+                (ProductionReports, 1),
+            ],
+        ),
+        (SuitKind::Maverick, 4) => UpgradeCost::new(
+            1_200_000,
+            &[
+                (SuitSchematic, 9),
+                (Graphene, 15),
+                (MicroSupercapacitor, 10),
+                (ProductionReports, 3),
+                (PowerRegulator, 1),
+            ],
+        ),
+        (SuitKind::Maverick, 5) => UpgradeCost::new(
+            2_000_000,
+            &[
+                (SuitSchematic, 12),
+                (Graphene, 20),
+                (MicroSupercapacitor, 15),
+                (ProductionReports, 5),
+                (PowerRegulator, 3),
+            ],
+        ),
+        (SuitKind::Dominator, 2) => {
+            UpgradeCost::new(150_000, &[(SuitSchematic, 1), (TitaniumPlating, 5)])
+        }
+        (SuitKind::Dominator, 3) => UpgradeCost::new(
+            500_000,
+            &[
+                (SuitSchematic, 5),
+                (TitaniumPlating, 10),
+                (MicroThrusters, 5),
+                (CombatTrainingMaterial, 1),
+            ],
+        ),
+        (SuitKind::Dominator, 4) => UpgradeCost::new(
+            1_200_000,
+            &[
+                (SuitSchematic, 9),
+                (TitaniumPlating, 15),
+                (MicroThrusters, 10),
+                (CombatTrainingMaterial, 3),
+                (TacticalPlans, 1),
+            ],
+        ),
+        (SuitKind::Dominator, 5) => UpgradeCost::new(
+            2_000_000,
+            &[
+                (SuitSchematic, 12),
+                (TitaniumPlating, 20),
+                (MicroThrusters, 15),
+                (CombatTrainingMaterial, 5),
+                (TacticalPlans, 3),
+            ],
+        ),
+        (SuitKind::Artemis, 2) => {
+            UpgradeCost::new(150_000, &[(SuitSchematic, 1), (CarbonfibrePlating, 5)])
+        }
+        (SuitKind::Artemis, 3) => UpgradeCost::new(
+            500_000,
+            &[
+                (SuitSchematic, 5),
+                (CarbonfibrePlating, 10),
+                (OpticalLens, 5),
+                (TopographicalSurveys, 1),
+            ],
+        ),
+        (SuitKind::Artemis, 4) => UpgradeCost::new(
+            1_200_000,
+            &[
+                (SuitSchematic, 9),
+                (CarbonfibrePlating, 15),
+                (OpticalLens, 10),
+                (TopographicalSurveys, 3),
+                (GeneticSample, 1),
+            ],
+        ),
+        (SuitKind::Artemis, 5) => UpgradeCost::new(
+            2_000_000,
+            &[
+                (SuitSchematic, 12),
+                (CarbonfibrePlating, 20),
+                (OpticalLens, 15),
+                (TopographicalSurveys, 5),
+                (GeneticSample, 3),
+            ],
+        ),
+        _ => return None,
+    };
+    Some(cost)
+}
+
+/// Pioneer Supplies cost of upgrading a weapon to `class`
+fn weapon_cost(kind: WeaponKind, class: u8) -> Option<UpgradeCost> {
+    use OnFootItem::*;
+
+    let cost = match (kind, class) {
+        (WeaponKind::Kinetic, 2) => {
+            UpgradeCost::new(50_000, &[(WeaponSchematic, 1), (TungstenCarbide, 5)])
+        }
+        (WeaponKind::Kinetic, 3) => UpgradeCost::new(
+            125_000,
+            &[
+                (WeaponSchematic, 5),
+                (TungstenCarbide, 10),
+                (WeaponComponent, 5),
+                (BallisticsData, 1),
+            ],
+        ),
+        (WeaponKind::Kinetic, 4) => UpgradeCost::new(
+            300_000,
+            &[
+                (WeaponSchematic, 9),
+                (TungstenCarbide, 15),
+                (WeaponComponent, 10),
+                (BallisticsData, 3),
+            ],
+        ),
+        (WeaponKind::Kinetic, 5) => UpgradeCost::new(
+            500_000,
+            &[
+                (WeaponSchematic, 12),
+                (TungstenCarbide, 20),
+                (WeaponComponent, 15),
+                (BallisticsData, 5),
+            ],
+        ),
+        (WeaponKind::Laser, 2) => {
+            UpgradeCost::new(50_000, &[(WeaponSchematic, 1), (OpticalLens, 5)])
+        }
+        (WeaponKind::Laser, 3) => UpgradeCost::new(
+            125_000,
+            &[
+                (WeaponSchematic, 5),
+                (OpticalLens, 10),
+                (MicroElectrode, 5),
+                (WeaponTestData, 1),
+            ],
+        ),
+        (WeaponKind::Laser, 4) => UpgradeCost::new(
+            300_000,
+            &[
+                (WeaponSchematic, 9),
+                (OpticalLens, 15),
+                (MicroElectrode, 10),
+                (WeaponTestData, 3),
+            ],
+        ),
+        (WeaponKind::Laser, 5) => UpgradeCost::new(
+            500_000,
+            &[
+                (WeaponSchematic, 12),
+                (OpticalLens, 20),
+                (MicroElectrode, 15),
+                (WeaponTestData, 5),
+            ],
+        ),
+        (WeaponKind::Plasma, 2) => {
+            UpgradeCost::new(50_000, &[(WeaponSchematic, 1), (IonisedGas, 5)])
+        }
+        (WeaponKind::Plasma, 3) => UpgradeCost::new(
+            125_000,
+            &[
+                (WeaponSchematic, 5),
+                (IonisedGas, 10),
+                (ChemicalSuperbase, 5),
+                (ChemicalExperimentData, 1),
+            ],
+        ),
+        (WeaponKind::Plasma, 4) => UpgradeCost::new(
+            300_000,
+            &[
+                (WeaponSchematic, 9),
+                (IonisedGas, 15),
+                (ChemicalSuperbase, 10),
+                (ChemicalExperimentData, 3),
+            ],
+        ),
+        (WeaponKind::Plasma, 5) => UpgradeCost::new(
+            500_000,
+            &[
+                (WeaponSchematic, 12),
+                (IonisedGas, 20),
+                (ChemicalSuperbase, 15),
+                (ChemicalExperimentData, 5),
+            ],
+        ),
+        _ => return None,
+    };
+    Some(cost)
+}
+
+impl UpgradeCatalog {
+    /// learn actual upgrade costs from log lines
+    pub fn add_line(&mut self, line: &EDLogLine) {
+        let (kind, class, cost) = match line.event() {
+            EDLogEvent::UpgradeSuit(upgrade) => {
+                let Some((kind, _)) = SuitKind::from_journal_name(upgrade.suit_name.as_str())
+                else {
+                    return;
+                };
+                (
+                    Upgradable::Suit(kind),
+                    upgrade.class,
+                    UpgradeCost::from_journal(upgrade.cost, &upgrade.resources),
+                )
+            }
+            EDLogEvent::UpgradeWeapon(upgrade) => {
+                let Some(kind) = WeaponKind::from_journal_name(upgrade.name.as_str()) else {
+                    return;
+                };
+                (
+                    Upgradable::Weapon(kind),
+                    upgrade.class as u8,
+                    UpgradeCost::from_journal(upgrade.cost, &upgrade.resources),
+                )
+            }
+            _ => return,
+        };
+        self.costs.insert((kind, class), cost);
+    }
+
+    /// set the cost of the upgrade to `class` from the class below
+    pub fn set_cost(&mut self, kind: Upgradable, class: u8, cost: UpgradeCost) {
+        self.costs.insert((kind, class), cost);
+    }
+
+    /// cost of the upgrade to `class` from the class below
+    pub fn cost(&self, kind: Upgradable, class: u8) -> Option<&UpgradeCost> {
+        self.costs.get(&(kind, class))
+    }
+
+    /// total cost to go from class `from` to class `to`
+    pub fn cost_between(&self, kind: Upgradable, from: u8, to: u8) -> Option<UpgradeCost> {
+        let mut total = UpgradeCost::default();
+        for class in from + 1..=to {
+            let cost = self.cost(kind, class)?;
+            total.credits += cost.credits;
+            for (item, count) in &cost.resources {
+                *total.resources.entry(item.clone()).or_default() += count;
+            }
+        }
+        Some(total)
+    }
+
+    /// cost from `from` to `to`, with what is missing from the ship locker and backpack
+    pub fn plan(
+        &self,
+        kind: Upgradable,
+        from: u8,
+        to: u8,
+        inventory: &OnFootInventory,
+    ) -> Option<UpgradePlan> {
+        let cost = self.cost_between(kind, from, to)?;
+        let missing = cost
+            .resources
+            .iter()
+            .filter_map(|(item, count)| {
+                let missing = count.saturating_sub(inventory.owned(item.clone()));
+                (missing > 0).then_some((item.clone(), missing))
+            })
+            .collect();
+        Some(UpgradePlan { cost, missing })
+    }
+}
+
+#[test]
+fn test_suit_upgrades() {
+    let json = [
+        r#"{ "timestamp":"2025-12-09T19:00:00Z", "event":"BuySuit", "Name":"utilitysuit_class1", "Name_Localised":"$UtilitySuit_Class1_Name;", "Price":150000, "SuitID":1700000000000001, "SuitMods":[] }"#,
+        r#"{ "timestamp":"2025-12-09T19:05:00Z", "event":"UpgradeSuit", "Name":"utilitysuit_class1", "Name_Localised":"$UtilitySuit_Class1_Name;", "SuitID":1700000000000001, "Class":2, "Cost":150000, "Resources":[ { "Name":"suitschematic", "Name_Localised":"Suit Schematic", "Count":1 }, { "Name":"graphene", "Count":5 } ] }"#,
+        r#"{ "timestamp":"2025-12-09T19:10:00Z", "event":"BuyWeapon", "Name":"wpn_m_assaultrifle_kinetic_fauto", "Name_Localised":"Karma AR-50", "Class":1, "Price":125000, "SuitModuleID":1700000000000002, "WeaponMods":[] }"#,
+        r#"{ "timestamp":"2025-12-09T20:19:43Z", "event":"ShipLocker", "Items":[], "Components":[ { "Name":"graphene", "OwnerID":0, "Count":40 } ], "Consumables":[], "Data":[ { "Name":"productionreports", "OwnerID":0, "Count":1 } ] }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let armoury = Armoury::new(&lines);
+    let inventory = OnFootInventory::new(&lines);
+    let maverick = armoury.suits_of_kind(SuitKind::Maverick).next().unwrap();
+    assert_eq!(2, maverick.class);
+    assert_eq!(
+        Some(WeaponKind::Kinetic),
+        armoury.weapons[&1700000000000002].kind
+    );
+
+    let mut catalog = UpgradeCatalog::default();
+    lines.iter().for_each(|line| catalog.add_line(line));
+    assert_eq!(
+        Some(&UpgradeCost::new(
+            150_000,
+            &[(OnFootItem::SuitSchematic, 1), (OnFootItem::Graphene, 5)]
+        )),
+        catalog.cost(Upgradable::Suit(SuitKind::Maverick), 2)
+    );
+
+    let plan = armoury
+        .plan_suit(maverick.suit_id, MAX_CLASS, &catalog, &inventory)
+        .unwrap();
+    assert_eq!(Credits(3_700_000), plan.cost.credits);
+    assert_eq!(
+        Some(&26),
+        plan.cost.resources.get(&OnFootItem::SuitSchematic)
+    );
+    assert_eq!(Some(&45), plan.cost.resources.get(&OnFootItem::Graphene));
+    assert_eq!(
+        Some(&30),
+        plan.cost.resources.get(&OnFootItem::MicroSupercapacitor)
+    );
+    assert_eq!(Some(&5), plan.missing.get(&OnFootItem::Graphene));
+    assert_eq!(Some(&8), plan.missing.get(&OnFootItem::ProductionReports));
+    assert_eq!(Some(&4), plan.missing.get(&OnFootItem::PowerRegulator));
+    assert_eq!(Some(&26), plan.missing.get(&OnFootItem::SuitSchematic));
+    assert!(!plan.is_ready());
+}
+
+#[test]
+fn test_suit_upgrades_edges() {
+    let json = [
+        r#"{ "timestamp":"2025-12-09T19:00:00Z", "event":"BuySuit", "Name":"flightsuit", "Name_Localised":"Flight Suit", "Price":0, "SuitID":1700000000000000, "SuitMods":[] }"#,
+        r#"{ "timestamp":"2025-12-09T19:01:00Z", "event":"BuySuit", "Name":"tacticalsuit_class5", "Name_Localised":"$TacticalSuit_Class1_Name;", "Price":150000, "SuitID":1700000000000001, "SuitMods":[] }"#,
+        r#"{ "timestamp":"2025-12-09T19:02:00Z", "event":"SellSuit", "SuitID":1700000000000001, "SuitMods":[], "Name":"tacticalsuit_class5", "Name_Localised":"$TacticalSuit_Class1_Name;", "Price":100000 }"#,
+        // the weapon was bought before the first journal we have
+        r#"{ "timestamp":"2025-12-09T19:05:00Z", "event":"UpgradeWeapon", "Name":"wpn_s_pistol_plasma_charged", "Name_Localised":"Manticore Tormentor", "Class":4, "SuitModuleID":1700000000000002, "Cost":300000, "Resources":[] }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let armoury = Armoury::new(&lines);
+    let inventory = OnFootInventory::new(&lines);
+    let catalog = UpgradeCatalog::default();
+    assert_eq!(1, armoury.suits.len());
+    assert_eq!(0, armoury.suits_of_kind(SuitKind::Dominator).count());
+    // the flight suit can't be upgraded
+    assert!(
+        armoury
+            .plan_suit(1700000000000000, MAX_CLASS, &catalog, &inventory)
+            .is_none()
+    );
+    assert!(
+        armoury
+            .plan_suit(1700000000000001, MAX_CLASS, &catalog, &inventory)
+            .is_none()
+    );
+
+    let tormentor = &armoury.weapons[&1700000000000002];
+    assert_eq!(Some(WeaponKind::Plasma), tormentor.kind);
+    assert_eq!(4, tormentor.class);
+    let plan = armoury
+        .plan_weapon(1700000000000002, MAX_CLASS, &catalog, &inventory)
+        .unwrap();
+    assert_eq!(Credits(500_000), plan.cost.credits);
+    assert_eq!(Some(&12), plan.missing.get(&OnFootItem::WeaponSchematic));
+
+    // nothing to upgrade, or past the highest grade
+    let kind = Upgradable::Weapon(WeaponKind::Plasma);
+    assert!(catalog.plan(kind, 4, 4, &inventory).unwrap().is_ready());
+    assert!(catalog.cost_between(kind, 4, MAX_CLASS + 1).is_none());
+}
@@ -11,18 +11,18 @@ pub enum UnknownItem {}
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogCollectItems {
-    name: OnFootItem,
+    pub name: OnFootItem,
     #[serde(rename = "Name_Localised")]
-    name_localised: Option<EDString>,
+    pub name_localised: Option<EDString>,
     #[serde(rename = "Type")]
-    item_type: BackpackItemType,
+    pub item_type: BackpackItemType,
     #[serde(rename = "OwnerID")]
-    owner_id: u64,
-    count: u64,
-    stolen: bool,
+    pub owner_id: u64,
+    pub count: u64,
+    pub stolen: bool,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum BackpackItemType {
     Component,
     Data,
@@ -33,45 +33,45 @@ pub enum BackpackItemType {
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct BackpackItem {
-    name: OnFootItem,
+    pub name: OnFootItem,
     #[serde(rename = "Name_Localised")]
-    name_localised: Option<EDString>,
+    pub name_localised: Option<EDString>,
     #[serde(rename = "OwnerID")]
-    owner_id: u64,
+    pub owner_id: u64,
     #[serde(rename = "MissionID")]
-    mission_id: Option<u64>,
-    count: u64,
+    pub mission_id: Option<u64>,
+    pub count: u64,
     #[serde(rename = "Type")]
-    item_type: Option<BackpackItemType>,
+    pub item_type: Option<BackpackItemType>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogBackpackChange {
-    added: Option<Vec<BackpackItem>>,
-    removed: Option<Vec<BackpackItem>>,
+    pub added: Option<Vec<BackpackItem>>,
+    pub removed: Option<Vec<BackpackItem>>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogUseConsumable {
-    name: OnFootItem,
+    pub name: OnFootItem,
     #[serde(rename = "Name_Localised")]
-    name_localised: EDString,
+    pub name_localised: EDString,
     #[serde(rename = "Type")]
-    item_type: BackpackItemType,
+    pub item_type: BackpackItemType,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogBackpack {
-    items: Vec<BackpackItem>,
-    components: Vec<BackpackItem>,
-    consumables: Vec<BackpackItem>,
-    data: Vec<BackpackItem>,
+    pub items: Vec<BackpackItem>,
+    pub components: Vec<BackpackItem>,
+    pub consumables: Vec<BackpackItem>,
+    pub data: Vec<BackpackItem>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum SuitMod {
     #[serde(rename = "suit_backpackcapacity")]
     Backpackcapacity,
@@ -103,7 +103,7 @@ pub enum SuitMod {
     ImprovedArmourRating,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum WeaponMod {
     #[serde(rename = "weapon_clipsize")]
     Clipsize,
@@ -130,29 +130,29 @@ pub enum WeaponMod {
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct SuitModule {
-    slot_name: EDString,
+    pub slot_name: EDString,
     #[serde(rename = "SuitModuleID")]
-    suit_module_id: u64,
-    module_name: EDString,
+    pub suit_module_id: u64,
+    pub module_name: EDString,
     #[serde(rename = "ModuleName_Localised")]
-    module_name_localised: EDString,
-    class: u64,
-    weapon_mods: Vec<WeaponMod>,
+    pub module_name_localised: EDString,
+    pub class: u64,
+    pub weapon_mods: Vec<WeaponMod>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogSuitLoadout {
     #[serde(rename = "SuitID")]
-    suit_id: u64,
-    suit_name: EDString,
+    pub suit_id: u64,
+    pub suit_name: EDString,
     #[serde(rename = "SuitName_Localised")]
-    suit_name_localised: EDString,
-    suit_mods: Vec<SuitMod>,
+    pub suit_name_localised: EDString,
+    pub suit_mods: Vec<SuitMod>,
     #[serde(rename = "LoadoutID")]
-    loadout_id: u64,
-    loadout_name: EDString,
-    modules: Vec<SuitModule>,
+    pub loadout_id: u64,
+    pub loadout_name: EDString,
+    pub modules: Vec<SuitModule>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
@@ -183,15 +183,15 @@ pub struct EDLogSellSuit {
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogCreateSuitLoadout {
     #[serde(rename = "SuitID")]
-    suit_id: u64,
-    suit_name: EDString,
+    pub suit_id: u64,
+    pub suit_name: EDString,
     #[serde(rename = "SuitName_Localised")]
-    suit_name_localised: EDString,
-    suit_mods: Vec<SuitMod>,
+    pub suit_name_localised: EDString,
+    pub suit_mods: Vec<SuitMod>,
     #[serde(rename = "LoadoutID")]
-    loadout_id: u64,
-    loadout_name: EDString,
-    modules: Vec<SuitModule>,
+    pub loadout_id: u64,
+    pub loadout_name: EDString,
+    pub modules: Vec<SuitModule>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
@@ -264,46 +264,72 @@ pub struct EDLogUpgradeSuit {
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogDropItems {
-    name: EDString,
+    pub name: OnFootItem,
     #[serde(rename = "Name_Localised")]
-    name_localised: Option<EDString>,
+    pub name_localised: Option<EDString>,
     #[serde(rename = "Type")]
-    item_type: EDString,
+    pub item_type: BackpackItemType,
     #[serde(rename = "OwnerID")]
-    owner_id: u64,
+    pub owner_id: u64,
     #[serde(rename = "MissionID")]
-    mission_id: Option<u64>,
-    count: u64,
+    pub mission_id: Option<u64>,
+    pub count: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransferDirection {
+    ToBackpack,
+    ToShipLocker,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+pub struct MicroResourceTransfer {
+    pub name: OnFootItem,
+    #[serde(rename = "Name_Localised")]
+    pub name_localised: Option<EDString>,
+    pub category: BackpackItemType,
+    pub locker_old_count: u64,
+    pub locker_new_count: u64,
+    pub direction: TransferDirection,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
+#[serde(rename_all = "PascalCase", deny_unknown_fields)]
+#[testcase({ "timestamp":"2025-12-09T20:21:02Z", "event":"TransferMicroResources",
+    "Transfers":[ { "Name":"healthpack", "Name_Localised":"Medkit", "Category":"Consumable", "LockerOldCount":100, "LockerNewCount":98, "Direction":"ToBackpack" } ] })]
+pub struct EDLogTransferMicroResources {
+    pub transfers: Vec<MicroResourceTransfer>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogLoadoutEquipModule {
-    loadout_name: EDString,
+    pub loadout_name: EDString,
     #[serde(rename = "SuitID")]
-    suit_id: u64,
-    suit_name: EDString,
+    pub suit_id: u64,
+    pub suit_name: EDString,
     #[serde(rename = "SuitName_Localised")]
-    suit_name_localised: EDString,
+    pub suit_name_localised: EDString,
     #[serde(rename = "LoadoutID")]
-    loadout_id: u64,
-    slot_name: EDString,
-    module_name: EDString,
+    pub loadout_id: u64,
+    pub slot_name: EDString,
+    pub module_name: EDString,
     #[serde(rename = "ModuleName_Localised")]
-    module_name_localised: EDString,
-    class: u64,
-    weapon_mods: Vec<WeaponMod>,
+    pub module_name_localised: EDString,
+    pub class: u64,
+    pub weapon_mods: Vec<WeaponMod>,
     #[serde(rename = "SuitModuleID")]
-    suit_module_id: u64,
+    pub suit_module_id: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct UpgradeResource {
-    name: EDString,
+    pub name: OnFootItem,
     #[serde(rename = "Name_Localised")]
-    name_localised: Option<EDString>,
-    count: u64,
+    pub name_localised: Option<EDString>,
+    pub count: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
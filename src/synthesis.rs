@@ -0,0 +1,414 @@
+use crate::{
+    log_line::{EDLogEvent, EDLogLine},
+    ship::EDLogSynthesis,
+};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use strum::{Display, EnumIter, IntoEnumIterator};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Display, EnumIter)]
+pub enum SynthesisGrade {
+    Basic,
+    Standard,
+    Premium,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Display, EnumIter)]
+pub enum SynthesisRecipe {
+    #[strum(to_string = "FSD")]
+    FSDInjection,
+    #[strum(to_string = "AFM Refill")]
+    AFMURefill,
+    #[strum(to_string = "Limpet")]
+    Limpets,
+    #[strum(to_string = "Chaff")]
+    Chaff,
+    #[strum(to_string = "Heat Sink")]
+    HeatSink,
+    #[strum(to_string = "Life Support")]
+    LifeSupport,
+    #[strum(to_string = "Plasma Munitions")]
+    PlasmaMunitions,
+    #[strum(to_string = "Explosive Munitions")]
+    ExplosiveMunitions,
+    #[strum(to_string = "Small Calibre Munitions")]
+    SmallCalibreMunitions,
+    #[strum(to_string = "High Velocity Munitions")]
+    HighVelocityMunitions,
+    #[strum(to_string = "Large Calibre Munitions")]
+    LargeCalibreMunitions,
+    #[strum(to_string = "Flechette Launcher Munitions")]
+    FlechetteLauncherMunitions,
+    #[strum(to_string = "Enzyme Missile Launcher Munitions")]
+    EnzymeMissileMunitions,
+    #[strum(to_string = "AX Explosive Munitions")]
+    AXExplosiveMunitions,
+    #[strum(to_string = "AX Small Calibre Munitions")]
+    AXSmallCalibreMunitions,
+    #[strum(to_string = "AX Remote Flak Munitions")]
+    AXRemoteFlakMunitions,
+    #[strum(to_string = "Guardian Plasma Charger Munitions")]
+    GuardianPlasmaChargerMunitions,
+    #[strum(to_string = "Guardian Gauss Cannon Munitions")]
+    GuardianGaussCannonMunitions,
+    #[strum(to_string = "Guardian Shard Cannon Munitions")]
+    GuardianShardCannonMunitions,
+    #[strum(to_string = "SRV Ammo")]
+    SRVAmmo,
+    #[strum(to_string = "SRV Repair")]
+    SRVRepair,
+    #[strum(to_string = "SRV Refuel")]
+    SRVRefuel,
+}
+
+impl SynthesisRecipe {
+    /// Materials needed for one synthesis of the given grade, or None if the
+    /// recipe is not available in that grade.
+    pub fn cost(&self, grade: SynthesisGrade) -> Option<&'static [(&'static str, u64)]> {
+        use SynthesisGrade::*;
+        use SynthesisRecipe::*;
+
+        let cost: &'static [(&'static str, u64)] = match (self, grade) {
+            (FSDInjection, Basic) => &[("carbon", 5), ("vanadium", 3), ("germanium", 1)],
+            (FSDInjection, Standard) => &[
+                ("carbon", 5),
+                ("vanadium", 3),
+                ("germanium", 1),
+                ("cadmium", 1),
+                ("niobium", 1),
+            ],
+            (FSDInjection, Premium) => &[
+                ("carbon", 5),
+                ("germanium", 1),
+                ("arsenic", 1),
+                ("niobium", 1),
+                ("yttrium", 1),
+                ("polonium", 1),
+            ],
+            (AFMURefill, Basic) => &[("nickel", 3), ("vanadium", 3)],
+            (AFMURefill, Standard) => &[
+                ("nickel", 3),
+                ("chromium", 2),
+                ("manganese", 2),
+                ("zinc", 2),
+            ],
+            (AFMURefill, Premium) => &[
+                ("nickel", 4),
+                ("chromium", 3),
+                ("zinc", 2),
+                ("tin", 1),
+                ("polonium", 1),
+            ],
+            (Limpets, Basic) => &[("iron", 10), ("nickel", 10)],
+            (Chaff, Basic) => &[("iron", 1), ("sulphur", 1)],
+            (HeatSink, Basic) => &[("vanadium", 2), ("germanium", 1), ("iron", 2)],
+            (LifeSupport, Basic) => &[("iron", 2), ("nickel", 1)],
+            (PlasmaMunitions, Basic) => &[("phosphorus", 5), ("sulphur", 5), ("vanadium", 2)],
+            (PlasmaMunitions, Standard) => &[
+                ("phosphorus", 5),
+                ("sulphur", 5),
+                ("manganese", 4),
+                ("germanium", 2),
+            ],
+            (PlasmaMunitions, Premium) => &[
+                ("phosphorus", 4),
+                ("selenium", 3),
+                ("niobium", 2),
+                ("tungsten", 2),
+                ("polonium", 1),
+            ],
+            (ExplosiveMunitions, Basic) => &[("phosphorus", 3), ("sulphur", 3)],
+            (ExplosiveMunitions, Standard) => &[
+                ("phosphorus", 5),
+                ("sulphur", 5),
+                ("manganese", 4),
+                ("arsenic", 2),
+            ],
+            (ExplosiveMunitions, Premium) => &[
+                ("phosphorus", 4),
+                ("sulphur", 5),
+                ("arsenic", 3),
+                ("mercury", 3),
+                ("polonium", 1),
+            ],
+            (SmallCalibreMunitions, Basic) => &[("iron", 2), ("sulphur", 2)],
+            (SmallCalibreMunitions, Standard) => &[
+                ("iron", 2),
+                ("sulphur", 2),
+                ("phosphorus", 2),
+                ("zirconium", 2),
+            ],
+            (SmallCalibreMunitions, Premium) => &[
+                ("iron", 2),
+                ("sulphur", 2),
+                ("zirconium", 2),
+                ("tellurium", 1),
+                ("technetium", 1),
+            ],
+            (HighVelocityMunitions, Basic) => &[("iron", 2), ("vanadium", 1)],
+            (HighVelocityMunitions, Standard) => {
+                &[("iron", 3), ("vanadium", 2), ("zirconium", 2), ("zinc", 1)]
+            }
+            (HighVelocityMunitions, Premium) => &[
+                ("vanadium", 2),
+                ("zirconium", 2),
+                ("yttrium", 1),
+                ("antimony", 1),
+                ("polonium", 1),
+            ],
+            (LargeCalibreMunitions, Basic) => &[("zinc", 2), ("sulphur", 2), ("iron", 2)],
+            (LargeCalibreMunitions, Standard) => &[
+                ("zinc", 2),
+                ("sulphur", 2),
+                ("phosphorus", 2),
+                ("arsenic", 2),
+            ],
+            (LargeCalibreMunitions, Premium) => &[
+                ("zinc", 2),
+                ("sulphur", 2),
+                ("phosphorus", 2),
+                ("arsenic", 2),
+                ("tellurium", 1),
+            ],
+            (FlechetteLauncherMunitions, Premium) => {
+                &[("tungsten", 1), ("phosphorus", 1), ("antimony", 1)]
+            }
+            (EnzymeMissileMunitions, Standard) => &[("phosphorus", 4), ("mercury", 3), ("lead", 3)],
+            (AXExplosiveMunitions, Basic) => &[("iron", 2), ("sulphur", 2), ("phosphorus", 2)],
+            (AXExplosiveMunitions, Standard) => &[
+                ("iron", 3),
+                ("sulphur", 3),
+                ("phosphorus", 2),
+                ("mercury", 1),
+            ],
+            (AXExplosiveMunitions, Premium) => &[
+                ("iron", 3),
+                ("sulphur", 3),
+                ("phosphorus", 3),
+                ("ruthenium", 1),
+                ("selenium", 1),
+            ],
+            (AXSmallCalibreMunitions, Basic) => &[("iron", 2), ("nickel", 2)],
+            (AXSmallCalibreMunitions, Standard) => &[("iron", 2), ("nickel", 2), ("zirconium", 2)],
+            (AXSmallCalibreMunitions, Premium) => &[
+                ("iron", 3),
+                ("nickel", 3),
+                ("zirconium", 2),
+                ("antimony", 1),
+            ],
+            (AXRemoteFlakMunitions, Basic) => &[("iron", 2), ("nickel", 2), ("lead", 1)],
+            (GuardianPlasmaChargerMunitions, Basic) => &[
+                ("chromium", 3),
+                ("heatdispersionplate", 2),
+                ("guardian_powercell", 2),
+                ("guardian_powerconduit", 2),
+            ],
+            (GuardianGaussCannonMunitions, Basic) => &[
+                ("manganese", 3),
+                ("heatresistantceramics", 2),
+                ("guardian_powercell", 2),
+                ("guardian_sentinel_wreckagecomponents", 2),
+            ],
+            (GuardianShardCannonMunitions, Basic) => &[
+                ("carbon", 3),
+                ("vanadium", 2),
+                ("guardian_powercell", 2),
+                ("guardian_sentinel_wreckagecomponents", 2),
+            ],
+            (SRVAmmo, Basic) => &[("phosphorus", 1), ("sulphur", 2)],
+            (SRVAmmo, Standard) => &[
+                ("phosphorus", 1),
+                ("sulphur", 1),
+                ("manganese", 1),
+                ("chromium", 1),
+            ],
+            (SRVAmmo, Premium) => &[
+                ("phosphorus", 2),
+                ("sulphur", 2),
+                ("manganese", 1),
+                ("niobium", 1),
+                ("molybdenum", 1),
+            ],
+            (SRVRepair, Basic) => &[("iron", 2), ("nickel", 1)],
+            (SRVRepair, Standard) => &[("iron", 3), ("nickel", 2), ("chromium", 1), ("zinc", 1)],
+            (SRVRepair, Premium) => &[
+                ("iron", 2),
+                ("nickel", 1),
+                ("vanadium", 1),
+                ("tellurium", 1),
+                ("tungsten", 1),
+            ],
+            (SRVRefuel, Basic) => &[("phosphorus", 1), ("sulphur", 1)],
+            (SRVRefuel, Standard) => &[
+                ("phosphorus", 1),
+                ("sulphur", 2),
+                ("arsenic", 1),
+                ("mercury", 1),
+            ],
+            (SRVRefuel, Premium) => &[
+                ("phosphorus", 1),
+                ("sulphur", 2),
+                ("arsenic", 1),
+                ("mercury", 1),
+                ("polonium", 1),
+            ],
+            _ => return None,
+        };
+        Some(cost)
+    }
+
+    /// Name of the synthesis as used in the journal, e.g. `FSD Premium`
+    pub fn journal_name(&self, grade: SynthesisGrade) -> String {
+        format!("{self} {grade}")
+    }
+
+    /// Parse the name of a Synthesis event into recipe and grade
+    pub fn from_journal_name(name: &str) -> Option<(SynthesisRecipe, SynthesisGrade)> {
+        let (recipe, grade) = name.trim().rsplit_once(' ')?;
+        let grade = SynthesisGrade::iter().find(|g| g.to_string().eq_ignore_ascii_case(grade))?;
+        let recipe =
+            SynthesisRecipe::iter().find(|r| r.to_string().eq_ignore_ascii_case(recipe))?;
+        Some((recipe, grade))
+    }
+
+    /// how many times this recipe can be synthesised with the materials in `inventory`
+    pub fn max_count(&self, grade: SynthesisGrade, inventory: &HashMap<String, u64>) -> u64 {
+        self.cost(grade)
+            .map(|cost| {
+                cost.iter()
+                    .map(|(name, count)| inventory.get(*name).copied().unwrap_or(0) / count)
+                    .min()
+                    .unwrap_or(0)
+            })
+            .unwrap_or(0)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SynthesisCheck {
+    pub timestamp: DateTime<Utc>,
+    pub recipe: SynthesisRecipe,
+    pub grade: SynthesisGrade,
+    /// materials that were used in different amounts than the recipe states
+    /// as (material, expected count, actual count)
+    pub differences: Vec<(String, u64, u64)>,
+}
+
+impl SynthesisCheck {
+    pub fn new(synthesis: &EDLogSynthesis, timestamp: DateTime<Utc>) -> Option<SynthesisCheck> {
+        let (recipe, grade) = SynthesisRecipe::from_journal_name(synthesis.name.as_str())?;
+        let expected = recipe.cost(grade)?;
+
+        let mut actual: HashMap<String, u64> = HashMap::new();
+        for m in &synthesis.materials {
+            *actual.entry(m.name.as_str().to_lowercase()).or_default() += m.count;
+        }
+
+        let mut differences: Vec<(String, u64, u64)> = expected
+            .iter()
+            .map(|(name, count)| {
+                let used = actual.remove(*name).unwrap_or(0);
+                (name.to_string(), *count, used)
+            })
+            .filter(|(_, expected, used)| expected != used)
+            .collect();
+        differences.extend(actual.into_iter().map(|(name, used)| (name, 0, used)));
+
+        Some(SynthesisCheck {
+            timestamp,
+            recipe,
+            grade,
+            differences,
+        })
+    }
+
+    pub fn matches_recipe(&self) -> bool {
+        self.differences.is_empty()
+    }
+}
+
+/// check all Synthesis events against the recipe catalog
+pub fn check_synthesis(lines: &[EDLogLine]) -> Vec<SynthesisCheck> {
+    lines
+        .iter()
+        .filter_map(|line| match line.event() {
+            EDLogEvent::Synthesis(synthesis) => SynthesisCheck::new(synthesis, *line.timestamp()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_synthesis() {
+    let json = [
+        r#"{ "timestamp":"2024-03-10T18:19:38Z", "event":"Synthesis", "Name":"FSD Premium", "Materials":[ { "Name":"carbon", "Count":5 }, { "Name":"germanium", "Count":1 }, { "Name":"arsenic", "Count":1 }, { "Name":"niobium", "Count":1 }, { "Name":"yttrium", "Count":1 }, { "Name":"polonium", "Count":1 } ] }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let checks = check_synthesis(&lines);
+    assert_eq!(1, checks.len());
+    assert_eq!(SynthesisRecipe::FSDInjection, checks[0].recipe);
+    assert_eq!(SynthesisGrade::Premium, checks[0].grade);
+    assert!(checks[0].matches_recipe());
+
+    assert_eq!(
+        Some((SynthesisRecipe::SRVRefuel, SynthesisGrade::Basic)),
+        SynthesisRecipe::from_journal_name("SRV Refuel Basic")
+    );
+    assert_eq!(
+        "AFM Refill Standard",
+        SynthesisRecipe::AFMURefill.journal_name(SynthesisGrade::Standard)
+    );
+
+    let inventory: HashMap<String, u64> = [
+        ("carbon", 23),
+        ("germanium", 10),
+        ("arsenic", 8),
+        ("niobium", 4),
+        ("yttrium", 6),
+        ("polonium", 9),
+    ]
+    .into_iter()
+    .map(|(n, c)| (n.to_string(), c))
+    .collect();
+    assert_eq!(
+        4,
+        SynthesisRecipe::FSDInjection.max_count(SynthesisGrade::Premium, &inventory)
+    );
+    assert_eq!(
+        0,
+        SynthesisRecipe::FSDInjection.max_count(SynthesisGrade::Basic, &inventory)
+    );
+}
+
+#[test]
+fn test_synthesis_mismatch() {
+    assert!(check_synthesis(&[]).is_empty());
+    assert_eq!(None, SynthesisRecipe::from_journal_name("FSD"));
+    assert_eq!(None, SynthesisRecipe::from_journal_name("Made Up Standard"));
+    assert_eq!(
+        0,
+        SynthesisRecipe::FSDInjection.max_count(SynthesisGrade::Basic, &HashMap::new())
+    );
+
+    let json = [
+        r#"{ "timestamp":"2024-03-10T18:19:38Z", "event":"Synthesis", "Name":"Made Up Standard", "Materials":[ { "Name":"carbon", "Count":5 } ] }"#,
+        r#"{ "timestamp":"2024-03-10T18:21:38Z", "event":"Synthesis", "Name":"FSD Basic", "Materials":[ { "Name":"Carbon", "Count":5 }, { "Name":"vanadium", "Count":2 }, { "Name":"iron", "Count":1 } ] }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    // unknown recipes are skipped
+    let checks = check_synthesis(&lines);
+    assert_eq!(1, checks.len());
+    assert!(!checks[0].matches_recipe());
+    let mut differences = checks[0].differences.clone();
+    differences.sort();
+    assert_eq!(
+        vec![
+            ("germanium".to_string(), 1, 0),
+            ("iron".to_string(), 0, 1),
+            ("vanadium".to_string(), 3, 2),
+        ],
+        differences
+    );
+}
@@ -0,0 +1,369 @@
+use crate::log_line::{EDLogEvent, EDLogLine};
+use std::collections::HashMap;
+use std::fmt;
+use strum::Display;
+
+/// size of a sector in light years, on every axis
+pub const SECTOR_SIZE: f64 = 1280.0;
+/// galactic position of the corner of sector (0, 0, 0)
+pub const SECTOR_ORIGIN: [f64; 3] = [-49985.0, -40985.0, -24105.0];
+
+/// Mass code of a procedurally generated system, boxels get bigger from a to h
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum MassCode {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+}
+
+impl MassCode {
+    const ALL: [MassCode; 8] = [
+        MassCode::A,
+        MassCode::B,
+        MassCode::C,
+        MassCode::D,
+        MassCode::E,
+        MassCode::F,
+        MassCode::G,
+        MassCode::H,
+    ];
+
+    pub fn from_index(index: u8) -> Option<MassCode> {
+        MassCode::ALL.get(index as usize).copied()
+    }
+
+    pub fn from_char(c: char) -> Option<MassCode> {
+        MassCode::from_index((c as u8).wrapping_sub(b'a'))
+    }
+
+    pub fn index(&self) -> u8 {
+        *self as u8
+    }
+
+    /// edge length of a boxel in light years
+    pub fn boxel_size(&self) -> f64 {
+        10.0 * (1u32 << self.index()) as f64
+    }
+
+    /// number of bits used by a boxel coordinate in the system address
+    fn boxel_bits(&self) -> u32 {
+        7 - self.index() as u32
+    }
+}
+
+/// Decoded system address (id64)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SystemAddress {
+    pub sector: [u32; 3],
+    pub mass_code: MassCode,
+    pub boxel: [u32; 3],
+    /// index of the system in its boxel
+    pub index: u64,
+}
+
+impl SystemAddress {
+    pub fn decode(id64: u64) -> SystemAddress {
+        let mass_code = MassCode::from_index((id64 & 7) as u8).expect("3 bits");
+        let b = mass_code.boxel_bits();
+        let mut rest = id64 >> 3;
+        let mut take = |bits: u32| {
+            let value = rest & ((1 << bits) - 1);
+            rest >>= bits;
+            value as u32
+        };
+        let z = take(b + 7);
+        let y = take(b + 6);
+        let x = take(b + 7);
+        let mask = (1 << b) - 1;
+        SystemAddress {
+            sector: [x >> b, y >> b, z >> b],
+            mass_code,
+            boxel: [x & mask, y & mask, z & mask],
+            index: rest,
+        }
+    }
+
+    pub fn encode(&self) -> u64 {
+        let b = self.mass_code.boxel_bits();
+        let [x, y, z] = [0, 1, 2].map(|i| ((self.sector[i] << b) | self.boxel[i]) as u64);
+        let mut id64 = self.index;
+        id64 = (id64 << (b + 7)) | x;
+        id64 = (id64 << (b + 6)) | y;
+        id64 = (id64 << (b + 7)) | z;
+        (id64 << 3) | self.mass_code.index() as u64
+    }
+
+    /// centre of the boxel, the system is at most half a boxel away on every axis
+    pub fn estimated_position(&self) -> [f64; 3] {
+        let size = self.mass_code.boxel_size();
+        [0, 1, 2].map(|i| {
+            SECTOR_ORIGIN[i]
+                + self.sector[i] as f64 * SECTOR_SIZE
+                + (self.boxel[i] as f64 + 0.5) * size
+        })
+    }
+}
+
+/// Procedurally generated system name like "Skauduae QM-T c17-4"
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ProcGenName {
+    pub sector: String,
+    pub mass_code: MassCode,
+    /// boxel number within the sector, spelled as letters and the first number of the name
+    pub boxel_index: u32,
+    /// index of the system in its boxel
+    pub index: u64,
+}
+
+impl ProcGenName {
+    pub fn parse(name: &str) -> Option<ProcGenName> {
+        let mut parts = name.trim().rsplitn(3, ' ');
+        let (numbers, letters, sector) = (parts.next()?, parts.next()?, parts.next()?);
+
+        let letters = letters.as_bytes();
+        if letters.len() != 4 || letters[2] != b'-' {
+            return None;
+        }
+        let mut boxel_index = 0;
+        for (c, weight) in [(letters[0], 1), (letters[1], 26), (letters[3], 676)] {
+            if !c.is_ascii_uppercase() {
+                return None;
+            }
+            boxel_index += (c - b'A') as u32 * weight;
+        }
+
+        let mut chars = numbers.chars();
+        let mass_code = MassCode::from_char(chars.next()?)?;
+        let numbers = chars.as_str();
+        let (n1, n2) = match numbers.split_once('-') {
+            Some((n1, n2)) => (n1.parse::<u32>().ok()?, n2.parse().ok()?),
+            None => (0, numbers.parse().ok()?),
+        };
+        Some(ProcGenName {
+            sector: sector.to_string(),
+            mass_code,
+            boxel_index: boxel_index + n1 * 17576,
+            index: n2,
+        })
+    }
+
+    pub fn from_address(sector: &str, address: &SystemAddress) -> ProcGenName {
+        let [x, y, z] = address.boxel;
+        ProcGenName {
+            sector: sector.to_string(),
+            mass_code: address.mass_code,
+            boxel_index: x + y * 128 + z * 16384,
+            index: address.index,
+        }
+    }
+
+    /// boxel coordinates within the sector
+    pub fn boxel(&self) -> [u32; 3] {
+        [
+            self.boxel_index % 128,
+            (self.boxel_index / 128) % 128,
+            self.boxel_index / 16384,
+        ]
+    }
+
+    /// the system address, given the coordinates of the sector
+    pub fn to_address(&self, sector: [u32; 3]) -> SystemAddress {
+        SystemAddress {
+            sector,
+            mass_code: self.mass_code,
+            boxel: self.boxel(),
+            index: self.index,
+        }
+    }
+}
+
+impl fmt::Display for ProcGenName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let letter = |n: u32| (b'A' + (n % 26) as u8) as char;
+        let n1 = self.boxel_index / 17576;
+        write!(
+            f,
+            "{} {}{}-{} {}",
+            self.sector,
+            letter(self.boxel_index),
+            letter(self.boxel_index / 26),
+            letter(self.boxel_index / 676),
+            self.mass_code
+        )?;
+        if n1 > 0 {
+            write!(f, "{n1}-")?;
+        }
+        write!(f, "{}", self.index)
+    }
+}
+
+/// Sector coordinates learned from systems seen with both a procedurally generated name and an address.
+/// Sector names can't be derived from coordinates, so a sector has to be seen once to locate names in it.
+#[derive(Clone, Debug, Default)]
+pub struct SectorIndex {
+    sectors: HashMap<String, [u32; 3]>,
+}
+
+impl SectorIndex {
+    pub fn new(lines: &[EDLogLine]) -> SectorIndex {
+        let mut index = SectorIndex::default();
+        for line in lines {
+            index.add_line(line);
+        }
+        index
+    }
+
+    pub fn add_line(&mut self, line: &EDLogLine) {
+        let (name, system_address) = match line.event() {
+            EDLogEvent::FSDJump(jump) => (jump.star_system.clone(), jump.system_address),
+            EDLogEvent::CarrierJump(jump) => (jump.star_system.clone(), jump.system_address),
+            EDLogEvent::Location(location) => match location.system_address {
+                Some(address) => (location.star_system.clone(), address),
+                None => return,
+            },
+            EDLogEvent::FSDTarget(target) => (target.name.clone(), target.system_address),
+            EDLogEvent::CodexEntry(entry) => (entry.system.clone(), entry.system_address),
+            _ => return,
+        };
+        self.add(name.as_str(), system_address);
+    }
+
+    pub fn add(&mut self, name: &str, system_address: u64) {
+        let Some(name) = ProcGenName::parse(name) else {
+            return;
+        };
+        let address = SystemAddress::decode(system_address);
+        // hand named systems can look procedural, only trust addresses that agree with the name
+        if name.to_address(address.sector) == address {
+            self.sectors
+                .insert(name.sector.to_lowercase(), address.sector);
+        }
+    }
+
+    pub fn sector(&self, name: &str) -> Option<[u32; 3]> {
+        self.sectors.get(&name.to_lowercase()).copied()
+    }
+
+    pub fn address(&self, name: &str) -> Option<SystemAddress> {
+        let name = ProcGenName::parse(name)?;
+        Some(name.to_address(self.sector(&name.sector)?))
+    }
+
+    pub fn estimated_position(&self, name: &str) -> Option<[f64; 3]> {
+        self.address(name).map(|a| a.estimated_position())
+    }
+
+    pub fn len(&self) -> usize {
+        self.sectors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sectors.is_empty()
+    }
+}
+
+#[test]
+fn test_system_address() {
+    let address = SystemAddress::decode(1185175214746);
+    assert_eq!(MassCode::C, address.mass_code);
+    assert_eq!([39, 31, 26], address.sector);
+    assert_eq!([28, 5, 19], address.boxel);
+    assert_eq!(4, address.index);
+    assert_eq!(1185175214746, address.encode());
+
+    let name = ProcGenName::parse("Skauduae QM-T c17-4").unwrap();
+    assert_eq!("Skauduae", name.sector);
+    assert_eq!(address.boxel, name.boxel());
+    assert_eq!(address, name.to_address(address.sector));
+    assert_eq!("Skauduae QM-T c17-4", name.to_string());
+    assert_eq!(name, ProcGenName::from_address("Skauduae", &address));
+    assert_eq!(
+        "Col 285 Sector AB-C d3",
+        ProcGenName::parse("Col 285 Sector AB-C d3")
+            .unwrap()
+            .to_string()
+    );
+    assert!(ProcGenName::parse("Sol").is_none());
+    assert!(ProcGenName::parse("HIP 759").is_none());
+
+    // Sol is hand named but still has a boxel, the estimate is within half a boxel
+    let sol = SystemAddress::decode(10477373803);
+    assert_eq!(MassCode::D, sol.mass_code);
+    assert_eq!([-25.0, 15.0, 15.0], sol.estimated_position());
+    assert_eq!(10477373803, sol.encode());
+
+    let json = [
+        r#"{ "timestamp":"2026-01-26T15:40:00Z", "event":"FSDTarget", "Name":"Skauduae QM-T c17-4", "SystemAddress":1185175214746, "StarClass":"TTS" }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+    let index = SectorIndex::new(&lines);
+    assert_eq!(Some([39, 31, 26]), index.sector("skauduae"));
+    let other = index.address("Skauduae AA-A h0").unwrap();
+    assert_eq!(MassCode::H, other.mass_code);
+    assert_eq!(
+        Some([
+            39.0 * 1280.0 - 49985.0 + 640.0,
+            31.0 * 1280.0 - 40985.0 + 640.0,
+            26.0 * 1280.0 - 24105.0 + 640.0
+        ]),
+        index.estimated_position("Skauduae AA-A h0")
+    );
+    assert!(index.estimated_position("Eorl Auwsy AA-A h0").is_none());
+}
+
+#[test]
+fn test_system_address_bounds() {
+    assert_eq!(Some(MassCode::H), MassCode::from_index(7));
+    assert!(MassCode::from_index(8).is_none());
+    assert!(MassCode::from_char('i').is_none());
+    assert!(MassCode::from_char('A').is_none());
+
+    // every field at its largest value survives the round trip
+    for mass_code in MassCode::ALL {
+        let b = mass_code.boxel_bits();
+        let address = SystemAddress {
+            sector: [127, 63, 127],
+            mass_code,
+            boxel: [(1 << b) - 1; 3],
+            index: (1 << (41 - 3 * b)) - 1,
+        };
+        assert_eq!(address, SystemAddress::decode(address.encode()));
+    }
+}
+
+#[test]
+fn test_proc_gen_name_errors() {
+    for name in [
+        "",
+        "QM-T c17-4",
+        "Skauduae qm-t c17-4",
+        "Skauduae QMT c17-4",
+        "Skauduae QM-T i17-4",
+        "Skauduae QM-T c",
+        "Skauduae QM-T c17-x",
+    ] {
+        assert!(ProcGenName::parse(name).is_none(), "{name}");
+    }
+}
+
+#[test]
+fn test_sector_index_mismatch() {
+    let mut index = SectorIndex::default();
+    assert!(index.is_empty());
+    // a procedural looking name with the address of another boxel isn't trusted
+    index.add("Skauduae QM-T c17-4", 10477373803);
+    index.add("Sol", 10477373803);
+    assert!(index.is_empty());
+    assert!(index.address("Skauduae AA-A h0").is_none());
+
+    index.add("SKAUDUAE QM-T c17-4", 1185175214746);
+    assert_eq!(1, index.len());
+    assert_eq!(Some([39, 31, 26]), index.sector("Skauduae"));
+    assert!(index.address("Sol").is_none());
+}
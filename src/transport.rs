@@ -21,13 +21,13 @@ pub struct EDLogBookTaxi {
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
 #[serde(rename_all = "PascalCase", deny_unknown_fields)]
 pub struct EDLogDropshipDeploy {
-    star_system: EDString,
-    system_address: u64,
-    body: EDString,
+    pub star_system: EDString,
+    pub system_address: u64,
+    pub body: EDString,
     #[serde(rename = "BodyID")]
-    body_id: u64,
-    on_station: bool,
-    on_planet: bool,
+    pub body_id: u64,
+    pub on_station: bool,
+    pub on_planet: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Extractable)]
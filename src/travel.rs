@@ -0,0 +1,338 @@
+use crate::{
+    EDString,
+    galaxy::{KnownSystems, SOL, distance},
+    log_line::{EDLogEvent, EDLogLine},
+    navigation::JumpType,
+    ship_type::ShipType,
+    system_address::SystemAddress,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::{collections::BTreeMap, fmt::Write};
+use strum::Display;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Display)]
+pub enum TravelMode {
+    /// our own ship's frame shift drive
+    Hyperspace,
+    Taxi,
+    Multicrew,
+    #[strum(to_string = "Fleet Carrier")]
+    Carrier,
+    Dropship,
+}
+
+/// jet cone boost taken before a jump
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Display)]
+pub enum Supercharge {
+    Neutron,
+    #[strum(to_string = "White Dwarf")]
+    WhiteDwarf,
+}
+
+// neutron stars boost the jump range by 4, white dwarfs by 1.5
+const NEUTRON_BOOST: f64 = 4.0;
+
+fn json_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[derive(Clone, Debug)]
+pub struct TravelEntry {
+    pub timestamp: DateTime<Utc>,
+    pub mode: TravelMode,
+    pub star_system: EDString,
+    pub system_address: u64,
+    /// estimated from the system address for dropship deployments to systems we don't know
+    pub star_pos: [f64; 3],
+    pub distance: f64,
+    pub ship: Option<ShipType>,
+    pub supercharge: Option<Supercharge>,
+    /// level of the FSD injection used
+    pub boost_used: Option<u64>,
+}
+
+/// Chronological log of how we moved between systems
+#[derive(Clone, Debug, Default)]
+pub struct TravelLog {
+    entries: Vec<TravelEntry>,
+    pub systems: KnownSystems,
+    ship: Option<ShipType>,
+    position: Option<[f64; 3]>,
+    supercharge: Option<Supercharge>,
+    pub supercruise_entries: u64,
+    pub jet_cone_damage: u64,
+}
+
+impl TravelLog {
+    /// build the travel log from log lines sorted by timestamp
+    pub fn new(lines: &[EDLogLine]) -> TravelLog {
+        let mut log = TravelLog::default();
+        for line in lines {
+            log.add_line(line);
+        }
+        log
+    }
+
+    fn travel(
+        &mut self,
+        timestamp: DateTime<Utc>,
+        mode: TravelMode,
+        star_system: EDString,
+        system_address: u64,
+        star_pos: [f64; 3],
+    ) -> &mut TravelEntry {
+        let distance = self.position.map_or(0.0, |from| distance(&from, &star_pos));
+        self.position = Some(star_pos);
+        self.entries.push(TravelEntry {
+            timestamp,
+            mode,
+            star_system,
+            system_address,
+            star_pos,
+            distance,
+            ship: None,
+            supercharge: None,
+            boost_used: None,
+        });
+        self.entries.last_mut().expect("entry was just pushed")
+    }
+
+    pub fn add_line(&mut self, line: &EDLogLine) {
+        self.systems.add_line(line);
+        let timestamp = *line.timestamp();
+        match line.event() {
+            EDLogEvent::Loadout(loadout) => self.ship = Some(loadout.ship),
+            EDLogEvent::Location(location) => self.position = Some(location.star_pos),
+            EDLogEvent::StartJump(jump) if jump.jump_type == JumpType::Supercruise => {
+                self.supercruise_entries += 1;
+            }
+            EDLogEvent::JetConeBoost(boost) => {
+                self.supercharge = Some(if boost.boost_value >= NEUTRON_BOOST {
+                    Supercharge::Neutron
+                } else {
+                    Supercharge::WhiteDwarf
+                });
+            }
+            EDLogEvent::JetConeDamage(_) => self.jet_cone_damage += 1,
+            EDLogEvent::FSDJump(jump) => {
+                let mode = if jump.taxi == Some(true) {
+                    TravelMode::Taxi
+                } else if jump.multicrew == Some(true) {
+                    TravelMode::Multicrew
+                } else {
+                    TravelMode::Hyperspace
+                };
+                let ship = self.ship.filter(|_| mode == TravelMode::Hyperspace);
+                let supercharge = self.supercharge.take();
+                let entry = self.travel(
+                    timestamp,
+                    mode,
+                    jump.star_system.clone(),
+                    jump.system_address,
+                    jump.star_pos,
+                );
+                // the journal distance is more precise than the one between rounded positions
+                entry.distance = jump.jump_dist;
+                entry.ship = ship;
+                entry.supercharge = supercharge;
+                entry.boost_used = jump.boost_used.filter(|b| *b > 0);
+            }
+            EDLogEvent::CarrierJump(jump) => {
+                self.travel(
+                    timestamp,
+                    TravelMode::Carrier,
+                    jump.star_system.clone(),
+                    jump.system_address,
+                    jump.star_pos,
+                );
+            }
+            EDLogEvent::DropshipDeploy(deploy) => {
+                let star_pos = self
+                    .systems
+                    .position(deploy.system_address)
+                    .unwrap_or_else(|| {
+                        SystemAddress::decode(deploy.system_address).estimated_position()
+                    });
+                self.travel(
+                    timestamp,
+                    TravelMode::Dropship,
+                    deploy.star_system.clone(),
+                    deploy.system_address,
+                    star_pos,
+                );
+            }
+            _ => {}
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &TravelEntry> {
+        self.entries.iter()
+    }
+
+    pub fn total_distance(&self) -> f64 {
+        self.entries.iter().map(|e| e.distance).sum()
+    }
+
+    pub fn distance_per_day(&self) -> BTreeMap<NaiveDate, f64> {
+        let mut result = BTreeMap::new();
+        for entry in &self.entries {
+            *result.entry(entry.timestamp.date_naive()).or_default() += entry.distance;
+        }
+        result
+    }
+
+    pub fn distance_per_ship(&self) -> BTreeMap<ShipType, f64> {
+        let mut result = BTreeMap::new();
+        for entry in &self.entries {
+            if let Some(ship) = entry.ship {
+                *result.entry(ship).or_default() += entry.distance;
+            }
+        }
+        result
+    }
+
+    pub fn distance_per_mode(&self) -> BTreeMap<TravelMode, f64> {
+        let mut result = BTreeMap::new();
+        for entry in &self.entries {
+            *result.entry(entry.mode).or_default() += entry.distance;
+        }
+        result
+    }
+
+    pub fn supercharges(&self, supercharge: Supercharge) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| e.supercharge == Some(supercharge))
+            .count()
+    }
+
+    /// the system we travelled to that is farthest from `from`, like our home system
+    pub fn farthest_from(&self, from: &[f64; 3]) -> Option<(&TravelEntry, f64)> {
+        self.entries
+            .iter()
+            .map(|e| (e, distance(from, &e.star_pos)))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    pub fn farthest_from_sol(&self) -> Option<(&TravelEntry, f64)> {
+        self.farthest_from(&SOL)
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("timestamp,star_system,system_address,x,y,z,distance,mode\n");
+        for e in &self.entries {
+            let [x, y, z] = e.star_pos;
+            let _ = writeln!(
+                csv,
+                "{},\"{}\",{},{x},{y},{z},{},{}",
+                e.timestamp.to_rfc3339(),
+                e.star_system.as_str().replace('"', "\"\""),
+                e.system_address,
+                e.distance,
+                e.mode
+            );
+        }
+        csv
+    }
+
+    /// the route as a GeoJSON line on the galactic plane, coordinates are [x, z, y]
+    pub fn to_geojson(&self) -> String {
+        let coordinates: Vec<String> = self
+            .entries
+            .iter()
+            .map(|e| format!("[{},{},{}]", e.star_pos[0], e.star_pos[2], e.star_pos[1]))
+            .collect();
+        let systems: Vec<String> = self
+            .entries
+            .iter()
+            .map(|e| json_string(e.star_system.as_str()))
+            .collect();
+        format!(
+            r#"{{"type":"FeatureCollection","features":[{{"type":"Feature","geometry":{{"type":"LineString","coordinates":[{}]}},"properties":{{"systems":[{}]}}}}]}}"#,
+            coordinates.join(","),
+            systems.join(",")
+        )
+    }
+}
+
+#[test]
+fn test_travel_log() {
+    let json = [
+        r#"{ "timestamp":"2025-05-01T20:00:00Z", "event":"Loadout", "Ship":"krait_mkii", "ShipID":12, "ShipName":"", "ShipIdent":"", "HullValue":42000000, "ModulesValue":50000000, "HullHealth":1.0, "UnladenMass":400.0, "CargoCapacity":0, "MaxJumpRange":30.0, "FuelCapacity":{ "Main":32.0, "Reserve":0.63 }, "Rebuy":4600000, "Modules":[] }"#,
+        r#"{ "timestamp":"2025-05-01T20:05:00Z", "event":"FSDJump", "StarSystem":"Yukoukha", "SystemAddress":22945325796176, "StarPos":[-134.56250,-34.03125,-4.75000], "SystemAllegiance":"", "SystemEconomy":"$economy_None;", "SystemEconomy_Localised":"None", "SystemGovernment":"$government_None;", "SystemGovernment_Localised":"None", "SystemSecurity":"$GAlAXY_MAP_INFO_state_anarchy;", "SystemSecurity_Localised":"Anarchy", "Population":0, "JumpDist":13.790, "FuelUsed":0.371038, "FuelLevel":5.628962 }"#,
+        r#"{ "timestamp":"2025-05-01T20:10:00Z", "event":"StartJump", "JumpType":"Supercruise", "Taxi":false }"#,
+        r#"{ "timestamp":"2025-05-01T20:20:00Z", "event":"JetConeBoost", "BoostValue":4.0 }"#,
+        r#"{ "timestamp":"2025-05-01T20:21:00Z", "event":"FSDJump", "StarSystem":"Kholul", "SystemAddress":2415659059547, "StarPos":[-46.93750,-7.84375,-152.18750], "SystemAllegiance":"", "SystemEconomy":"$economy_None;", "SystemEconomy_Localised":"None", "SystemGovernment":"$government_None;", "SystemGovernment_Localised":"None", "SystemSecurity":"$GAlAXY_MAP_INFO_state_anarchy;", "SystemSecurity_Localised":"Anarchy", "Population":0, "JumpDist":173.499, "FuelUsed":5.0, "FuelLevel":20.0 }"#,
+        r#"{ "timestamp":"2025-05-02T19:00:00Z", "event":"FSDJump", "Taxi":true, "StarSystem":"Sol", "SystemAddress":10477373803, "StarPos":[0.0,0.0,0.0], "SystemAllegiance":"", "SystemEconomy":"$economy_None;", "SystemEconomy_Localised":"None", "SystemGovernment":"$government_None;", "SystemGovernment_Localised":"None", "SystemSecurity":"$GAlAXY_MAP_INFO_state_anarchy;", "SystemSecurity_Localised":"Anarchy", "Population":0, "JumpDist":159.454, "FuelUsed":5.0, "FuelLevel":20.0 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let log = TravelLog::new(&lines);
+    assert_eq!(3, log.iter().count());
+    assert_eq!(1, log.supercruise_entries);
+    assert_eq!(1, log.supercharges(Supercharge::Neutron));
+    assert!((log.total_distance() - 346.743).abs() < 1e-9);
+    let per_day: Vec<f64> = log.distance_per_day().into_values().collect();
+    assert!((per_day[0] - 187.289).abs() < 1e-9);
+    assert_eq!(159.454, per_day[1]);
+    assert!((log.distance_per_ship()[&ShipType::KraitMkII] - 187.289).abs() < 1e-9);
+    assert_eq!(159.454, log.distance_per_mode()[&TravelMode::Taxi]);
+
+    let (farthest, distance) = log.farthest_from_sol().unwrap();
+    assert_eq!("Kholul", farthest.star_system.as_str());
+    assert!((distance - 159.454).abs() < 0.001);
+
+    let csv = log.to_csv();
+    assert_eq!(4, csv.lines().count());
+    assert!(csv.contains(r#","Sol",10477373803,0,0,0,159.454,Taxi"#));
+    let geojson: serde_json::Value = serde_json::from_str(&log.to_geojson()).unwrap();
+    let coordinates = &geojson["features"][0]["geometry"]["coordinates"];
+    assert_eq!(Some(-152.1875), coordinates[1][1].as_f64());
+    assert_eq!("Sol", geojson["features"][0]["properties"]["systems"][2]);
+}
+
+#[test]
+fn test_travel_log_empty() {
+    let log = TravelLog::new(&[]);
+    assert_eq!(0, log.iter().count());
+    assert_eq!(0.0, log.total_distance());
+    assert!(log.farthest_from_sol().is_none());
+    assert_eq!(1, log.to_csv().lines().count());
+    let geojson: serde_json::Value = serde_json::from_str(&log.to_geojson()).unwrap();
+    assert_eq!(
+        0,
+        geojson["features"][0]["geometry"]["coordinates"]
+            .as_array()
+            .unwrap()
+            .len()
+    );
+}
+
+#[test]
+fn test_travel_log_without_loadout() {
+    let json = [
+        r#"{ "timestamp":"2025-05-01T20:20:00Z", "event":"JetConeBoost", "BoostValue":1.5 }"#,
+        r#"{ "timestamp":"2025-05-01T20:20:05Z", "event":"JetConeDamage", "Module":"$int_hyperdrive_size5_class5_name;", "Module_Localised":"FSD" }"#,
+        r#"{ "timestamp":"2025-05-01T20:21:00Z", "event":"FSDJump", "StarSystem":"Kholul", "SystemAddress":2415659059547, "StarPos":[-46.93750,-7.84375,-152.18750], "SystemAllegiance":"", "SystemEconomy":"$economy_None;", "SystemEconomy_Localised":"None", "SystemGovernment":"$government_None;", "SystemGovernment_Localised":"None", "SystemSecurity":"$GAlAXY_MAP_INFO_state_anarchy;", "SystemSecurity_Localised":"Anarchy", "Population":0, "JumpDist":40.5, "FuelUsed":5.0, "FuelLevel":20.0 }"#,
+        // deployed to a system we have never been to
+        r#"{ "timestamp":"2025-05-01T21:00:00Z", "event":"DropshipDeploy", "StarSystem":"Skauduae QM-T c17-4", "SystemAddress":1185175214746, "Body":"Skauduae QM-T c17-4 1", "BodyID":1, "OnStation":false, "OnPlanet":true }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let log = TravelLog::new(&lines);
+    let entries: Vec<&TravelEntry> = log.iter().collect();
+    assert_eq!(2, entries.len());
+    assert_eq!(None, entries[0].ship);
+    assert_eq!(Some(Supercharge::WhiteDwarf), entries[0].supercharge);
+    assert_eq!(40.5, entries[0].distance);
+    assert_eq!(1, log.jet_cone_damage);
+    assert!(log.distance_per_ship().is_empty());
+
+    let estimated = SystemAddress::decode(1185175214746).estimated_position();
+    assert_eq!(estimated, entries[1].star_pos);
+    assert_eq!(None, entries[1].supercharge);
+    assert_eq!(
+        distance(&entries[0].star_pos, &estimated),
+        log.distance_per_mode()[&TravelMode::Dropship]
+    );
+}
@@ -82,3 +82,11 @@ pub fn to_human_readable_string(value: i64) -> String {
         (1_000_000_000..) | (..-1_000_000_000) => format!("{}B", value / 1_000_000_000),
     }
 }
+
+/// parse journal lines in tests, panics on lines that don't parse
+#[cfg(test)]
+pub(crate) fn parse_lines(json: &[impl AsRef<str>]) -> Vec<crate::log_line::EDLogLine> {
+    json.iter()
+        .map(|j| serde_json::from_str(j.as_ref()).expect("Should parse"))
+        .collect()
+}
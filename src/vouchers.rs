@@ -0,0 +1,315 @@
+use crate::{
+    EDString,
+    common_types::Credits,
+    log_line::{EDLogEvent, EDLogLine},
+    mission::VoucherType,
+};
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug)]
+pub struct Voucher {
+    pub timestamp: DateTime<Utc>,
+    pub voucher_type: VoucherType,
+    pub faction: EDString,
+    pub value: Credits,
+}
+
+#[derive(Clone, Debug)]
+pub struct VoucherRedemption {
+    pub timestamp: DateTime<Utc>,
+    pub voucher_type: VoucherType,
+    pub faction: Option<EDString>,
+    /// credits paid out, after the broker fee
+    pub paid: Credits,
+    pub broker_fee: Credits,
+}
+
+#[derive(Clone, Debug)]
+pub struct VoucherForfeiture {
+    pub timestamp: DateTime<Utc>,
+    pub vouchers: Vec<Voucher>,
+}
+
+impl VoucherForfeiture {
+    pub fn value(&self) -> Credits {
+        self.vouchers.iter().map(|v| v.value).sum()
+    }
+}
+
+/// Vouchers earned but not yet redeemed, all of them are lost on death.
+/// Settlement vouchers are not in the journal until they are redeemed.
+#[derive(Clone, Debug, Default)]
+pub struct VoucherWallet {
+    outstanding: Vec<Voucher>,
+    pub redemptions: Vec<VoucherRedemption>,
+    pub forfeitures: Vec<VoucherForfeiture>,
+}
+
+impl VoucherWallet {
+    /// build the wallet from log lines sorted by timestamp
+    pub fn new(lines: &[EDLogLine]) -> VoucherWallet {
+        let mut wallet = VoucherWallet::default();
+        for line in lines {
+            wallet.add_line(line);
+        }
+        wallet
+    }
+
+    fn earn(
+        &mut self,
+        timestamp: DateTime<Utc>,
+        voucher_type: VoucherType,
+        faction: EDString,
+        value: Credits,
+    ) {
+        self.outstanding.push(Voucher {
+            timestamp,
+            voucher_type,
+            faction,
+            value,
+        });
+    }
+
+    // remove vouchers of a faction, or of any faction when redeemed at interstellar factors
+    fn redeem(
+        &mut self,
+        voucher_type: VoucherType,
+        faction: Option<&EDString>,
+        face_value: Credits,
+    ) {
+        let mut remaining = face_value;
+        self.outstanding.retain(|v| {
+            if v.voucher_type != voucher_type || remaining.0 <= 0 {
+                return true;
+            }
+            match faction {
+                Some(faction) if v.faction != *faction => true,
+                _ => {
+                    remaining -= v.value;
+                    false
+                }
+            }
+        });
+    }
+
+    pub fn add_line(&mut self, line: &EDLogLine) {
+        let timestamp = *line.timestamp();
+        match line.event() {
+            EDLogEvent::Bounty(bounty) => match (&bounty.rewards, bounty.reward) {
+                (Some(rewards), _) => {
+                    for reward in rewards {
+                        self.earn(
+                            timestamp,
+                            VoucherType::Bounty,
+                            reward.faction.clone(),
+                            reward.reward,
+                        );
+                    }
+                }
+                (None, Some(reward)) => {
+                    self.earn(
+                        timestamp,
+                        VoucherType::Bounty,
+                        bounty.faction.clone().unwrap_or_else(|| EDString::from("")),
+                        Credits(reward.round() as i64),
+                    );
+                }
+                (None, None) => {}
+            },
+            EDLogEvent::FactionKillBond(bond) => {
+                self.earn(
+                    timestamp,
+                    VoucherType::CombatBond,
+                    bond.awarding_faction.clone(),
+                    bond.reward,
+                );
+            }
+            EDLogEvent::CapitalShipBond(bond) => {
+                self.earn(
+                    timestamp,
+                    VoucherType::CombatBond,
+                    bond.awarding_faction.clone(),
+                    bond.reward,
+                );
+            }
+            // paid by Universal Cartographics, not a faction
+            EDLogEvent::CodexEntry(entry) => {
+                if let Some(amount) = entry.voucher_amount.filter(|a| *a > 0) {
+                    self.earn(
+                        timestamp,
+                        VoucherType::Codex,
+                        EDString::from(""),
+                        Credits(amount as i64),
+                    );
+                }
+            }
+            EDLogEvent::DatalinkVoucher(voucher) => {
+                self.earn(
+                    timestamp,
+                    VoucherType::Scannable,
+                    voucher.payee_faction.clone(),
+                    voucher.reward,
+                );
+            }
+            EDLogEvent::RedeemVoucher(voucher) => {
+                let broker = voucher.broker_percentage.unwrap_or(0.0) as f64 / 100.0;
+                let paid: Vec<(Option<EDString>, Credits)> = match &voucher.factions {
+                    Some(factions) => factions
+                        .iter()
+                        .map(|f| {
+                            (
+                                Some(f.faction.clone()).filter(|f| !f.as_str().is_empty()),
+                                f.amount,
+                            )
+                        })
+                        .collect(),
+                    None => vec![(voucher.faction.clone(), voucher.amount)],
+                };
+                for (faction, amount) in paid {
+                    let face_value = Credits((amount.0 as f64 / (1.0 - broker)).round() as i64);
+                    self.redeem(voucher.voucher_type, faction.as_ref(), face_value);
+                    self.redemptions.push(VoucherRedemption {
+                        timestamp,
+                        voucher_type: voucher.voucher_type,
+                        faction,
+                        paid: amount,
+                        broker_fee: face_value - amount,
+                    });
+                }
+            }
+            EDLogEvent::Died(_) if !self.outstanding.is_empty() => {
+                self.forfeitures.push(VoucherForfeiture {
+                    timestamp,
+                    vouchers: std::mem::take(&mut self.outstanding),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    pub fn outstanding(&self) -> &[Voucher] {
+        &self.outstanding
+    }
+
+    /// credits lost if we die right now
+    pub fn at_risk(&self) -> Credits {
+        self.outstanding.iter().map(|v| v.value).sum()
+    }
+
+    pub fn at_risk_by_type(&self) -> BTreeMap<VoucherType, Credits> {
+        let mut result = BTreeMap::new();
+        for voucher in &self.outstanding {
+            *result.entry(voucher.voucher_type).or_default() += voucher.value;
+        }
+        result
+    }
+
+    pub fn at_risk_by_faction(&self) -> BTreeMap<EDString, Credits> {
+        let mut result = BTreeMap::new();
+        for voucher in &self.outstanding {
+            *result.entry(voucher.faction.clone()).or_default() += voucher.value;
+        }
+        result
+    }
+
+    pub fn total_forfeited(&self) -> Credits {
+        self.forfeitures.iter().map(|f| f.value()).sum()
+    }
+}
+
+#[test]
+fn test_voucher_wallet() {
+    let json = [
+        r#"{ "timestamp":"2024-10-13T18:02:00Z", "event":"Bounty", "Rewards":[ { "Faction":"Mother Gaia", "Reward":120000 }, { "Faction":"Sol Workers' Party", "Reward":10000 } ], "Target":"vulture", "TotalReward":130000, "VictimFaction":"Sol Pirates" }"#,
+        r#"{ "timestamp":"2024-10-13T18:04:00Z", "event":"FactionKillBond", "Reward":52500, "AwardingFaction":"Sirius Inc", "VictimFaction":"Race Marshalls" }"#,
+        r#"{ "timestamp":"2024-10-13T18:30:00Z", "event":"RedeemVoucher", "Type":"bounty", "Amount":90000, "Factions":[ { "Faction":"Mother Gaia", "Amount":90000 } ], "BrokerPercentage":25.000000 }"#,
+        r#"{ "timestamp":"2024-10-13T18:35:00Z", "event":"FactionKillBond", "Reward":20000, "AwardingFaction":"Sirius Inc", "VictimFaction":"Race Marshalls" }"#,
+    ];
+    let mut lines = crate::utils::parse_lines(&json);
+
+    let wallet = VoucherWallet::new(&lines);
+    assert_eq!(3, wallet.outstanding().len());
+    assert_eq!(Credits(82500), wallet.at_risk());
+    assert_eq!(
+        Some(&Credits(72500)),
+        wallet.at_risk_by_type().get(&VoucherType::CombatBond)
+    );
+    assert_eq!(Credits(30000), wallet.redemptions[0].broker_fee);
+
+    lines.push(
+        serde_json::from_str(r#"{ "timestamp":"2024-10-13T18:40:00Z", "event":"Died", "KillerName":"$UNKNOWN;", "KillerName_Localised":"Unknown", "KillerShip":"unknownsaucer", "KillerRank":"Elite" }"#)
+            .expect("Should parse"),
+    );
+    let wallet = VoucherWallet::new(&lines);
+    assert_eq!(Credits(0), wallet.at_risk());
+    assert_eq!(Credits(82500), wallet.total_forfeited());
+}
+
+#[test]
+fn test_voucher_sources() {
+    let json = [
+        r#"{ "timestamp":"2024-10-13T18:02:00Z", "event":"Bounty", "Faction":"Mother Gaia", "Target":"skimmerdrone", "Target_Localised":"Skimmer", "Reward":5000, "VictimFaction":"Sol Pirates" }"#,
+        r#"{ "timestamp":"2024-10-13T18:05:00Z", "event":"Bounty", "Rewards":[ { "Faction":"Mother Gaia", "Reward":15000 } ], "PilotName":"$citizensuitai_scientific;", "PilotName_Localised":"Researcher", "Target":"citizensuitai_scientific", "Target_Localised":"Researcher", "TotalReward":15000, "VictimFaction":"Sol Pirates" }"#,
+        r#"{ "timestamp":"2024-10-13T18:10:00Z", "event":"CodexEntry", "EntryID":2320605, "Name":"$Codex_Ent_Bacterial_06_G_Name;", "Name_Localised":"Bacterium Alcyoneum - Emerald", "SubCategory":"$Codex_SubCategory_Organic_Structures;", "SubCategory_Localised":"Organic structures", "Category":"$Codex_Category_Biology;", "Category_Localised":"Biological and Geological", "Region":"$Codex_RegionName_4;", "Region_Localised":"Odin's Hold", "System":"Dryooe Prou GG-Y f961", "SystemAddress":516065576045, "BodyID":21, "IsNewEntry":true, "VoucherAmount":50000 }"#,
+    ];
+    let mut lines = crate::utils::parse_lines(&json);
+
+    let wallet = VoucherWallet::new(&lines);
+    assert_eq!(
+        Credits(20000),
+        wallet.at_risk_by_faction()[&EDString::from("Mother Gaia")]
+    );
+    assert_eq!(
+        Some(&Credits(50000)),
+        wallet.at_risk_by_type().get(&VoucherType::Codex)
+    );
+
+    lines.push(
+        serde_json::from_str(r#"{ "timestamp":"2024-10-13T18:40:00Z", "event":"Died", "KillerName":"$UNKNOWN;", "KillerName_Localised":"Unknown", "KillerShip":"unknownsaucer", "KillerRank":"Elite" }"#)
+            .expect("Should parse"),
+    );
+    let wallet = VoucherWallet::new(&lines);
+    assert_eq!(Credits(70000), wallet.total_forfeited());
+}
+
+#[test]
+fn test_voucher_wallet_empty() {
+    let json = [
+        r#"{ "timestamp":"2024-10-13T18:40:00Z", "event":"Died", "KillerName":"$UNKNOWN;", "KillerName_Localised":"Unknown", "KillerShip":"unknownsaucer", "KillerRank":"Elite" }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let wallet = VoucherWallet::new(&lines);
+    assert!(wallet.outstanding().is_empty());
+    assert_eq!(Credits(0), wallet.at_risk());
+    assert!(wallet.at_risk_by_type().is_empty());
+    // dying without vouchers forfeits nothing
+    assert!(wallet.forfeitures.is_empty());
+    assert_eq!(Credits(0), wallet.total_forfeited());
+}
+
+#[test]
+fn test_voucher_redemptions() {
+    let json = [
+        // earned before the first journal we have
+        r#"{ "timestamp":"2024-10-13T18:00:00Z", "event":"RedeemVoucher", "Type":"CombatBond", "Amount":40000, "Faction":"Sirius Inc" }"#,
+        r#"{ "timestamp":"2024-10-13T18:02:00Z", "event":"Bounty", "Rewards":[ { "Faction":"Mother Gaia", "Reward":120000 }, { "Faction":"Sol Workers' Party", "Reward":10000 } ], "Target":"vulture", "TotalReward":130000, "VictimFaction":"Sol Pirates" }"#,
+        r#"{ "timestamp":"2024-10-13T18:04:00Z", "event":"FactionKillBond", "Reward":52500, "AwardingFaction":"Sirius Inc", "VictimFaction":"Race Marshalls" }"#,
+        // interstellar factors don't name the faction, the bond stays outstanding
+        r#"{ "timestamp":"2024-10-13T18:30:00Z", "event":"RedeemVoucher", "Type":"bounty", "Amount":97500, "Factions":[ { "Faction":"", "Amount":97500 } ], "BrokerPercentage":25.000000 }"#,
+    ];
+    let lines = crate::utils::parse_lines(&json);
+
+    let wallet = VoucherWallet::new(&lines);
+    assert_eq!(2, wallet.redemptions.len());
+    assert_eq!(Credits(0), wallet.redemptions[0].broker_fee);
+    assert_eq!(None, wallet.redemptions[1].faction);
+    assert_eq!(Credits(32500), wallet.redemptions[1].broker_fee);
+
+    assert_eq!(1, wallet.outstanding().len());
+    assert_eq!(
+        Credits(52500),
+        wallet.at_risk_by_faction()[&EDString::from("Sirius Inc")]
+    );
+}